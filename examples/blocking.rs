@@ -127,8 +127,12 @@ fn read(r: &mut impl io::Read) -> JsonParseResult<(), io::Error> {
 ///
 /// It has two generic arguments which we must provide as part of the signature:
 ///  * The representation of its internal read buffer, which we don't really care about. Its type
-///     bound is `AsMut<[u8]>`.
+///    bound is `AsMut<[u8]>`.
 ///  * The concrete type of the underlying reader.
+///
+/// There is a third generic argument for the buffer that tracks nesting of objects and arrays, but
+///  it has a default that we can rely on here.
+///
 /// It is safe and usually the most concise way to generify over them, as we do here.
 fn read_favorite_colors<B: AsMut<[u8]>, R: io::Read>(r: &mut JsonReader<B, R>) -> JsonParseResult<(), io::Error> {
     // The next token must be the start of a JSON array
@@ -4,12 +4,11 @@
 //! This format is sometimes used for streaming or sending large numbers of simple data, e.g.
 //!  log events.
 //!
-//! While the json-streaming library has no explicit support for the 'jsonlines' format, it is
-//!  straightforward to read and write in a fully streaming fashion.
-//! * When writing, create a new top-level [JsonObject] for each line, and write the `\n` between
-//!    lines
-//! * When reading, use the [new_with_lenient_comma_handling] function to create the JsonReader to
-//!    skip the check for commas between the objects
+//! It is straightforward to read and write in a fully streaming fashion.
+//! * When writing, use a [JsonLinesWriter] which hands out a top-level [JsonObject] for each
+//!   line and writes the `\n` after it
+//! * When reading, wrap the JsonReader in a [JsonLinesReader] which checks that there is one
+//!   value per line and reports errors with the number of the record
 
 use json_streaming::blocking::*;
 use json_streaming::shared::*;
@@ -21,21 +20,19 @@ fn main() {
     read(json_lines).unwrap();
 }
 
-fn read(json_lines: String) -> JsonParseResult<(), io::Error> {
+fn read(json_lines: String) -> Result<(), JsonLinesError<io::Error>> {
     let buf = json_lines.into_bytes();
     let mut read = Cursor::new(buf);
 
-    let mut json_reader = JsonReader::new_with_lenient_comma_handling(1024, &mut read);
+    let mut json_lines_reader = JsonLinesReader::new(JsonReader::new(1024, &mut read));
 
-    while let JsonReadToken::StartObject = json_reader.next()? {
+    while json_lines_reader.next_record()? {
         println!("start object");
-        loop {
-            match json_reader.next()? {
-                JsonReadToken::Key("a") => println!("  a={:?}", json_reader.expect_string()?),
-                JsonReadToken::Key("b") => println!("  b={:?}", json_reader.expect_number::<u32>()?),
-                JsonReadToken::EndObject => break,
-                _ => return Err(JsonParseError::Parse(JsonReadToken::EndObject.kind(), json_reader.location())),
-            }
+        if let Err(e) = read_object(json_lines_reader.json_reader()) {
+            let error = json_lines_reader.record_error(e);
+            println!("skipping bad record: {}", error);
+            json_lines_reader.skip_bad_record(&error)?;
+            continue;
         }
         println!("end object");
     }
@@ -43,23 +40,34 @@ fn read(json_lines: String) -> JsonParseResult<(), io::Error> {
     Ok(())
 }
 
+fn read_object(json_reader: &mut JsonReader<Vec<u8>, Cursor<Vec<u8>>>) -> JsonParseResult<(), io::Error> {
+    json_reader.expect_start_object()?;
+    loop {
+        match json_reader.next()? {
+            JsonReadToken::Key("a") => println!("  a={:?}", json_reader.expect_string()?),
+            JsonReadToken::Key("b") => println!("  b={:?}", json_reader.expect_number::<u32>()?),
+            JsonReadToken::EndObject => break,
+            _ => return json_reader.parse_err("unexpected token"),
+        }
+    }
+    Ok(())
+}
+
 fn write() -> io::Result<String> {
     let mut buf = Vec::new();
-    let mut writer = JsonWriter::new_compact(&mut buf);
+    let mut json_lines_writer = JsonLinesWriter::new(&mut buf);
 
-    let mut obj = JsonObject::new(&mut writer)?;
+    let mut obj = json_lines_writer.start_object()?;
     obj.write_string_value("a", "yo")?;
     obj.write_u32_value("b", 123)?;
     obj.end()?;
-    writer.write_bytes(b"\n")?;
 
-    let mut obj = JsonObject::new(&mut writer)?;
+    let mut obj = json_lines_writer.start_object()?;
     obj.write_u32_value("b", 456)?;
     obj.write_string_value("a", "hey")?;
     obj.end()?;
-    writer.write_bytes(b"\n")?;
 
-    let buf = writer.into_inner().unwrap().to_vec();
+    let buf = json_lines_writer.into_inner()?.to_vec();
     let s = String::from_utf8(buf).unwrap();
     Ok(s)
-}
\ No newline at end of file
+}
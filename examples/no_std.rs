@@ -69,6 +69,16 @@ impl BlockingRead for SliceReadBuffer<'_> {
         self.index += 1;
         Ok(result)
     }
+
+    // The data is in memory anyway, so we can provide slice-oriented access to it. This is
+    //  optional, but it allows the JsonReader to process the data a slice at a time.
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(&self.data[self.index.min(self.data.len())..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.index += amount;
+    }
 }
 
 
@@ -136,7 +146,7 @@ fn do_read(json_reader: &mut JsonReader<&mut [u8;64], SliceReadBuffer>) -> JsonP
                 // do something with the age
             }
             Some(_) => {
-                return Err(JsonParseError::Parse("unexpected key", json_reader.location()));
+                return json_reader.parse_err("unexpected key");
             }
             None => {
                 // end of the object
@@ -121,7 +121,7 @@ where
                 // This means there is some unexpected JSON element in the object. For this example,
                 //  we decide to fail. For details on how to skip data safely, see the 'skipping'
                 //  example.
-                return Err(JsonParseError::Parse("unexpected key parsing 'person'", json_reader.location()));
+                return json_reader.parse_err("unexpected key parsing 'person'");
             },
             None => break,
         }
@@ -134,8 +134,11 @@ where
 ///
 /// It has two generic arguments which we must provide as part of the signature:
 ///  * The representation of its internal read buffer, which we don't really care about. Its type
-///     bound is `AsMut<[u8]>`.
+///    bound is `AsMut<[u8]>`.
 ///  * The concrete type of the underlying reader.
+///
+/// There is a third generic argument for the buffer that tracks nesting of objects and arrays, but
+///  it has a default that we can rely on here.
 async fn read_favorite_colors<B: AsMut<[u8]>, R>(r: &mut JsonReader<'_, B, R>) -> JsonParseResult<(), io::Error>
 where
     R: AsyncRead + Send + Unpin
@@ -53,7 +53,7 @@ fn main() -> JsonParseResult<(), io::Error> {
     //  for non-negative types, or floating point numbers parsed into integer types.
     match json_reader.expect_number::<u32>() {
         Ok(n) => println!("num: {}", n),
-        Err(JsonParseError::Parse(msg, location)) => println!("not a u32 number: {}@{}", msg, location),
+        Err(JsonParseError::Parse(kind, location, _)) => println!("not a u32 number: {}@{}", kind, location),
         Err(_) => panic!("err"),
     }
 
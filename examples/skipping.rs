@@ -113,8 +113,9 @@ fn skip_unexpected_keys_in_array() -> JsonParseResult<(), io::Error> {
             }
             JsonReadToken::EndObject |
             JsonReadToken::Key(_) |
+            JsonReadToken::Comment(_) |
             JsonReadToken::EndOfStream => {
-                return Err(JsonParseError::Parse(JsonReadToken::EndOfStream.kind(), json_reader.location()));
+                return json_reader.parse_err(JsonReadToken::EndOfStream.kind());
             }
         }
     }
@@ -17,6 +17,8 @@ pub struct JsonArray<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat
     writer: &'a mut JsonWriter<'b, W, F, FF>,
     is_initial: bool,
     is_ended: bool,
+    /// a JSON Lines record, see `JsonLinesWriter`
+    is_record: bool,
 }
 
 impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'a, 'b, W, F, FF> {
@@ -30,9 +32,18 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'a,
             writer,
             is_initial: true,
             is_ended: false,
+            is_record: false,
         })
     }
 
+    /// Creates a top-level [JsonArray] that is a record of JSON Lines, writing a newline after the
+    ///  closing bracket
+    pub(crate) fn new_record(writer: &'a mut JsonWriter<'b, W, F, FF>) -> Result<Self, W::Error> {
+        let mut result = Self::new(writer)?;
+        result.is_record = true;
+        Ok(result)
+    }
+
     fn handle_initial(&mut self) -> Result<(), W::Error> {
         if self.is_initial {
             self.is_initial = false;
@@ -81,6 +92,18 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'a,
         self.writer.write_f32(value)
     }
 
+    /// Write an element that is serialized with serde (requires the `serde` feature), honoring
+    ///  this writer's [JsonFormatter] and [FloatFormat]. See [JsonObject::write_serialize] for
+    ///  the representation.
+    ///
+    /// The value is written while it is serialized, so if serialization fails with
+    ///  [JsonSerializeError::Data], the output is incomplete.
+    #[cfg(feature = "serde")]
+    pub fn write_serialize<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonSerializeError<W::Error>> {
+        self.handle_initial()?;
+        serialize_to_sink(self.writer, value)
+    }
+
     /// Start a nested object as an element. This function returns a new [JsonObject] instance
     ///  for writing elements to the nested object. When the returned [JsonObject] goes out of scope
     ///  (per syntactic scope or an explicit call to `end()`), the nested object is closed, and
@@ -113,6 +136,9 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'a,
         self.writer.write_format_before_end_nested(self.is_initial)?;
         self.writer.write_bytes(b"]")?;
         self.is_ended = true;
+        if self.is_record {
+            self.writer.write_bytes(b"\n")?;
+        }
         Ok(())
     }
 }
@@ -155,6 +181,7 @@ impl <'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> Drop for Json
 }
 
 #[cfg(test)]
+#[allow(clippy::type_complexity)]
 mod tests {
     use super::*;
     use rstest::*;
@@ -0,0 +1,404 @@
+use crate::blocking::io::BlockingRead;
+use crate::blocking::read::JsonReader;
+use crate::shared::*;
+use serde::de::value::StrDeserializer;
+use serde::de::{DeserializeOwned, DeserializeSeed, Visitor};
+
+impl<'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> JsonReader<'a, B, R, S> {
+    /// Deserializes the next value with serde (requires the `serde` feature), e.g. a small
+    ///  and regular item in a large array that is otherwise read in a streaming fashion. Only the
+    ///  deserialized value is held in memory.
+    ///
+    /// Strings are read into the reader's buffer, so types that borrow from the input (e.g. a
+    ///  field of type `&str`) can not be deserialized.
+    ///
+    /// If the value does not match the deserialized type ([JsonDeserializeError::Data]), the rest
+    ///  of the value is skipped, so that reading continues after it.
+    ///
+    /// ```
+    /// use json_streaming::blocking::*;
+    /// use json_streaming::shared::*;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Item {
+    ///     name: String,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// let mut r = std::io::Cursor::new(r#"{"count": 2, "items": [{"name": "a", "tags": []}, {"name": "b", "tags": ["x"]}]}"#);
+    /// let mut json_reader = JsonReader::new(64, &mut r);
+    /// json_reader.expect_start_object().unwrap();
+    /// while let Some(key) = json_reader.expect_key().unwrap() {
+    ///     match key {
+    ///         "items" => {
+    ///             json_reader.expect_start_array().unwrap();
+    ///             while json_reader.peek_kind().unwrap() != JsonReadTokenKind::EndArray {
+    ///                 let item: Item = json_reader.deserialize_value().unwrap();
+    ///                 println!("{}: {:?}", item.name, item.tags);
+    ///             }
+    ///             json_reader.expect_end_array().unwrap();
+    ///         }
+    ///         _ => json_reader.skip_value().unwrap(),
+    ///     }
+    /// }
+    /// ```
+    pub fn deserialize_value<T: DeserializeOwned>(&mut self) -> Result<T, JsonDeserializeError<R::Error>> {
+        let depth = self.depth_before_next_token();
+        let result = T::deserialize(&mut JsonDeserializer::new(self))
+            .map_err(|e| e.at_location(self.location()));
+        if let Err(JsonDeserializeError::Data(..)) = result {
+            while self.depth() > depth {
+                self.skip_to_end_of_current_scope()?;
+            }
+        }
+        result
+    }
+}
+
+/// A [JsonDeserializer] is a serde `Deserializer` that reads the next value from a [JsonReader]
+///  (requires the `serde` feature). [JsonReader::deserialize_value] is usually more convenient,
+///  this is for code that works with serde's API directly, e.g. with a `DeserializeSeed`.
+///
+/// Objects and arrays are read entry by entry, and JSON numbers are passed to serde as `u64`,
+///  `i64`, `u128` or `i128` if they are integer literals and fit into one of these types, and as
+///  `f64` otherwise.
+pub struct JsonDeserializer<'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]> = DefaultNestingBuffer> {
+    json_reader: &'r mut JsonReader<'a, B, R, S>,
+}
+
+impl<'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> JsonDeserializer<'r, 'a, B, R, S> {
+    pub fn new(json_reader: &'r mut JsonReader<'a, B, R, S>) -> Self {
+        JsonDeserializer { json_reader }
+    }
+
+    /// Reads the next value of a given (peeked) kind and passes it to a visitor
+    fn visit_next<'de, V: Visitor<'de>>(&mut self, kind: JsonReadTokenKind, location: Location, visitor: V) -> Result<V::Value, JsonDeserializeError<R::Error>> {
+        match kind {
+            JsonReadTokenKind::StartObject => {
+                self.json_reader.expect_start_object()?;
+                let mut map_access = MapAccess { de: self, is_done: false };
+                let value = visitor.visit_map(&mut map_access)?;
+                if !map_access.is_done {
+                    self.json_reader.expect_end_object()?;
+                }
+                Ok(value)
+            }
+            JsonReadTokenKind::StartArray => {
+                self.json_reader.expect_start_array()?;
+                let mut seq_access = SeqAccess { de: self, is_done: false };
+                let value = visitor.visit_seq(&mut seq_access)?;
+                if !seq_access.is_done {
+                    self.json_reader.expect_end_array()?;
+                }
+                Ok(value)
+            }
+            JsonReadTokenKind::StringLiteral => visitor.visit_str(self.json_reader.expect_string()?),
+            JsonReadTokenKind::NumberLiteral => {
+                let n = self.json_reader.expect_raw_number()?;
+                if n.is_integer() {
+                    if let Ok(v) = n.parse::<u64>() {
+                        return visitor.visit_u64(v);
+                    }
+                    if let Ok(v) = n.parse::<i64>() {
+                        return visitor.visit_i64(v);
+                    }
+                    if let Ok(v) = n.parse::<u128>() {
+                        return visitor.visit_u128(v);
+                    }
+                    if let Ok(v) = n.parse::<i128>() {
+                        return visitor.visit_i128(v);
+                    }
+                }
+                match n.parse::<f64>() {
+                    Ok(v) => visitor.visit_f64(v),
+                    Err(_) => Err(JsonParseError::Parse(ParseErrorKind::InvalidNumber, location, None).into()),
+                }
+            }
+            JsonReadTokenKind::BooleanLiteral => visitor.visit_bool(self.json_reader.expect_bool()?),
+            JsonReadTokenKind::NullLiteral => {
+                self.json_reader.next()?;
+                visitor.visit_unit()
+            }
+            found => self.unexpected_token(VALUE_START_KINDS, found, location),
+        }
+    }
+
+    fn unexpected_token<T>(&self, expected: &'static [JsonReadTokenKind], found: JsonReadTokenKind, location: Location) -> Result<T, JsonDeserializeError<R::Error>> {
+        Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, found), location, None).into())
+    }
+}
+
+impl<'de, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> serde::Deserializer<'de> for &mut JsonDeserializer<'r, 'a, B, R, S> {
+    type Error = JsonDeserializeError<R::Error>;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let location = self.json_reader.location();
+        let kind = self.json_reader.peek_kind()?;
+        self.visit_next(kind, location, visitor)
+            .map_err(|e| e.at_location(location))
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.json_reader.peek_kind()? == JsonReadTokenKind::NullLiteral {
+            self.json_reader.next()?;
+            return visitor.visit_none();
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Enums are represented like in `serde_json`: a unit variant as a string, and other variants
+    ///  as an object with the variant's name as its single key
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        let location = self.json_reader.location();
+        let result = match self.json_reader.peek_kind()? {
+            JsonReadTokenKind::StringLiteral => {
+                let variant = self.json_reader.expect_string()?;
+                visitor.visit_enum(StrDeserializer::new(variant))
+            }
+            JsonReadTokenKind::StartObject => {
+                self.json_reader.expect_start_object()?;
+                let value = visitor.visit_enum(EnumAccess { de: &mut *self })?;
+                self.json_reader.expect_end_object()?;
+                Ok(value)
+            }
+            found => self.unexpected_token(&[JsonReadTokenKind::StringLiteral, JsonReadTokenKind::StartObject], found, location),
+        };
+        result.map_err(|e| e.at_location(location))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.json_reader.skip_value()?;
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct SeqAccess<'x, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> {
+    de: &'x mut JsonDeserializer<'r, 'a, B, R, S>,
+    /// the closing `]` was consumed
+    is_done: bool,
+}
+impl<'de, 'x, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> serde::de::SeqAccess<'de> for SeqAccess<'x, 'r, 'a, B, R, S> {
+    type Error = JsonDeserializeError<R::Error>;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        if self.is_done {
+            return Ok(None);
+        }
+        if self.de.json_reader.peek_kind()? == JsonReadTokenKind::EndArray {
+            self.de.json_reader.expect_end_array()?;
+            self.is_done = true;
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+struct MapAccess<'x, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> {
+    de: &'x mut JsonDeserializer<'r, 'a, B, R, S>,
+    /// the closing `}` was consumed
+    is_done: bool,
+}
+impl<'de, 'x, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> serde::de::MapAccess<'de> for MapAccess<'x, 'r, 'a, B, R, S> {
+    type Error = JsonDeserializeError<R::Error>;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        if self.is_done {
+            return Ok(None);
+        }
+        match self.de.json_reader.expect_key()? {
+            Some(key) => seed.deserialize(StrDeserializer::new(key)).map(Some),
+            None => {
+                self.is_done = true;
+                Ok(None)
+            }
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+/// Access to an enum variant that is represented as an object with a single key
+struct EnumAccess<'x, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> {
+    de: &'x mut JsonDeserializer<'r, 'a, B, R, S>,
+}
+impl<'de, 'x, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> serde::de::EnumAccess<'de> for EnumAccess<'x, 'r, 'a, B, R, S> {
+    type Error = JsonDeserializeError<R::Error>;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Self::Error> {
+        let location = self.de.json_reader.location();
+        let value = match self.de.json_reader.expect_key()? {
+            Some(variant) => seed.deserialize(StrDeserializer::<Self::Error>::new(variant))?,
+            None => return self.de.unexpected_token(&[JsonReadTokenKind::Key], JsonReadTokenKind::EndObject, location),
+        };
+        Ok((value, self))
+    }
+}
+impl<'de, 'x, 'r, 'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> serde::de::VariantAccess<'de> for EnumAccess<'x, 'r, 'a, B, R, S> {
+    type Error = JsonDeserializeError<R::Error>;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        serde::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        serde::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        serde::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+    use std::fmt::Debug;
+    use std::io;
+    use std::io::Cursor;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Item {
+        id: u64,
+        name: String,
+        score: Option<f64>,
+        tags: Vec<String>,
+        origin: Point,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Wrapper(u8);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Line(Point, Point),
+        Rect { width: u32, height: u32 },
+    }
+
+    fn deserialize<T: DeserializeOwned>(json: &str) -> Result<T, JsonDeserializeError<io::Error>> {
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        let value = json_reader.deserialize_value()?;
+        json_reader.expect_end_of_stream()?;
+        Ok(value)
+    }
+
+    #[rstest]
+    #[case::bool("true", true)]
+    #[case::u8("255", 255u8)]
+    #[case::i64("-123", -123i64)]
+    #[case::u128("340282366920938463463374607431768211455", u128::MAX)]
+    #[case::i128("-170141183460469231731687303715884105728", i128::MIN)]
+    #[case::f64("1.5e3", 1500.0f64)]
+    #[case::f64_from_int("3", 3.0f64)]
+    #[case::char("\"x\"", 'x')]
+    #[case::string("\"a\\nb\"", "a\nb".to_string())]
+    #[case::unit("null", ())]
+    #[case::option_none("null", None::<u32>)]
+    #[case::option_some("7", Some(7u32))]
+    #[case::vec("[1, 2, 3]", vec![1u32, 2, 3])]
+    #[case::empty_vec("[]", Vec::<u32>::new())]
+    #[case::tuple("[1, \"a\", false]", (1u8, "a".to_string(), false))]
+    #[case::map("{\"b\": 2, \"a\": 1}", BTreeMap::from([("a".to_string(), 1u32), ("b".to_string(), 2)]))]
+    #[case::newtype("5", Wrapper(5))]
+    #[case::struct_from_array("[1, 2]", Point { x: 1, y: 2 })]
+    #[case::unit_variant("\"Empty\"", Shape::Empty)]
+    #[case::newtype_variant("{\"Circle\": 1.5}", Shape::Circle(1.5))]
+    #[case::tuple_variant("{\"Line\": [{\"x\": 0, \"y\": 0}, [1, 1]]}", Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 }))]
+    #[case::struct_variant("{\"Rect\": {\"width\": 3, \"height\": 4}}", Shape::Rect { width: 3, height: 4 })]
+    fn test_deserialize_value<T: DeserializeOwned + PartialEq + Debug>(#[case] json: &str, #[case] expected: T) {
+        assert_eq!(deserialize::<T>(json).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_deserialize_struct() {
+        let json = r#"{"name": "n", "unknown": {"a": [1, {}]}, "id": 1, "tags": ["t"], "origin": {"y": 2, "x": 1}}"#;
+        assert_eq!(deserialize::<Item>(json).unwrap(), Item {
+            id: 1,
+            name: "n".to_string(),
+            score: None,
+            tags: vec!["t".to_string()],
+            origin: Point { x: 1, y: 2 },
+        });
+    }
+
+    #[test]
+    fn test_deserialize_in_stream() -> Result<(), JsonDeserializeError<io::Error>> {
+        let mut r = Cursor::new(br#"{"points": [{"x": 1, "y": 2}, [3, 4]], "after": true}"#.to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.expect_key()?, Some("points"));
+        json_reader.expect_start_array()?;
+        let mut points = Vec::new();
+        while json_reader.peek_kind()? != JsonReadTokenKind::EndArray {
+            points.push(json_reader.deserialize_value::<Point>()?);
+        }
+        json_reader.expect_end_array()?;
+        assert_eq!(points, vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]);
+        assert_eq!(json_reader.expect_key()?, Some("after"));
+        assert!(json_reader.expect_bool()?);
+        json_reader.expect_end_object()?;
+        json_reader.expect_end_of_stream()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_continues_after_data_error() -> Result<(), JsonDeserializeError<io::Error>> {
+        let mut r = Cursor::new(br#"[{"x": "1", "y": 2}, {"x": 3, "y": [4]}, {"x": 5, "y": 6}, {"x": 7}]"#.to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array()?;
+        let mut points = Vec::new();
+        while json_reader.peek_kind()? != JsonReadTokenKind::EndArray {
+            points.push(json_reader.deserialize_value::<Point>().ok());
+        }
+        json_reader.expect_end_array()?;
+        json_reader.expect_end_of_stream()?;
+        assert_eq!(points, vec![None, None, Some(Point { x: 5, y: 6 }), None]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::missing_field("{\"x\": 1}", "data error: missing field `y` @ line 1, column 1 (offset 0)")]
+    #[case::wrong_type("{\"x\": \"1\", \"y\": 2}", "data error: invalid type: string \"1\", expected i32 @ line 1, column 6 (offset 5)")]
+    #[case::out_of_range("{\"x\": 3000000000, \"y\": 2}", "data error: invalid value: integer `3000000000`, expected i32 @ line 1, column 6 (offset 5)")]
+    #[case::too_many_elements("[1, 2, 3]", "parse error: expected ], found number @ line 1, column 10 (offset 9)")]
+    #[case::invalid_json("{\"x\": 1,, \"y\": 2}", "parse error: unexpected comma @ line 1, column 10 (offset 9)")]
+    #[case::end_of_input("{\"x\": 1", "parse error: unexpected end of input @ line 1, column 8 (offset 7)")]
+    fn test_deserialize_fails(#[case] json: &str, #[case] expected: &str) {
+        let error = deserialize::<Point>(json).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::unknown_variant("\"Square\"")]
+    #[case::empty_object("{}")]
+    #[case::number("1")]
+    fn test_deserialize_enum_fails(#[case] json: &str) {
+        assert!(deserialize::<Shape>(json).is_err());
+    }
+}
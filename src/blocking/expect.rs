@@ -0,0 +1,422 @@
+/// Generates the `expect_*` and `skip_*` functions that [crate::blocking::JsonReader] and
+///  [crate::blocking::JsonSliceReader] have in common, based on the reader's `next_non_comment()`
+///  and `check_next_kind()`. The arguments are the reader's type name (for links in the
+///  documentation), the lifetime of returned strings and numbers, the reader's I/O error type, and
+///  additional documentation for `expect_number()`.
+macro_rules! expect_functions {
+    ($reader:ident, $lt:lifetime, $err:ty; $(#[$expect_number_doc:meta])*) => {
+    /// This is the function for the loop to read the members of a JSON object: It returns either
+    ///  a JSON key or `None` if it encounters the `}` that ends the object. All other tokens are
+    ///  invalid and cause the function to fail.
+    pub fn expect_key(&mut self) -> JsonParseResult<Option<&$lt str>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::Key, JsonReadTokenKind::EndObject];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::Key(key) => Ok(Some(key)),
+            JsonReadToken::EndObject => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    /// Returns a JSON number as a [JsonNumber], and fails if the next token is anything other
+    ///  than a number. That includes the case that the next token is `null` - if the number
+    #[doc = concat!("  is optional and `null` a valid value, use [", stringify!($reader), "::expect_opt_raw_number] instead.")]
+    pub fn expect_raw_number(&mut self) -> JsonParseResult<JsonNumber<$lt>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NumberLiteral];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NumberLiteral(n) => Ok(n),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_raw_number], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_raw_number_or_end_array(&mut self) -> JsonParseResult<Option<JsonNumber<$lt>>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NumberLiteral(n) => Ok(Some(n)),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    /// Returns a [JsonNumber] if the next token is a JSON number, or `None` if the next token
+    ///  is `null`. All other tokens cause the function to fail.
+    pub fn expect_opt_raw_number(&mut self) -> JsonParseResult<Option<JsonNumber<$lt>>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(None),
+            JsonReadToken::NumberLiteral(n) => Ok(Some(n)),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_opt_raw_number], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_opt_raw_number_or_end_array(&mut self) -> JsonParseResult<Option<Option<JsonNumber<$lt>>>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(Some(None)),
+            JsonReadToken::NumberLiteral(n) => Ok(Some(Some(n))),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    /// Returns a number parsed to an application-provided type, failing if the next token is not
+    ///  a JSON number or the number is not parseable to the provided type (e.g. trying to
+    ///  get retrieve a floating point number as a u32).
+    $(#[$expect_number_doc])*
+    pub fn expect_number<T: FromStr>(&mut self) -> JsonParseResult<T, $err> {
+        let n = self.expect_raw_number()?;
+        match n.parse::<T>() {
+            Ok(n) => Ok(n),
+            Err(_) => self.inner.parse_err(ParseErrorKind::InvalidNumber),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_number], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_number_or_end_array<T: FromStr>(&mut self) -> JsonParseResult<Option<T>, $err> {
+        match self.expect_raw_number_or_end_array()? {
+            None => Ok(None),
+            Some(n) => {
+                match n.parse::<T>() {
+                    Ok(n) => Ok(Some(n)),
+                    Err(_) => self.inner.parse_err(ParseErrorKind::InvalidNumber),
+                }
+            }
+        }
+    }
+
+    #[doc = concat!(" The same as [", stringify!($reader), "::expect_number], but accepting a `null` literal which it")]
+    ///  returns as `None`.
+    pub fn expect_opt_number<T: FromStr>(&mut self) -> JsonParseResult<Option<T>, $err> {
+        match self.expect_opt_raw_number()? {
+            Some(n) => {
+                match n.parse::<T>() {
+                    Ok(n) => Ok(Some(n)),
+                    Err(_) => self.inner.parse_err(ParseErrorKind::InvalidNumber),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_opt_number], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_opt_number_or_end_array<T: FromStr>(&mut self) -> JsonParseResult<Option<Option<T>>, $err> {
+        match self.expect_opt_raw_number_or_end_array()? {
+            Some(Some(n)) => {
+                match n.parse::<T>() {
+                    Ok(n) => Ok(Some(Some(n))),
+                    Err(_) => self.inner.parse_err(ParseErrorKind::InvalidNumber),
+                }
+            }
+            Some(None) => Ok(Some(None)),
+            None => Ok(None),
+        }
+    }
+
+    /// If the next token is a string literal, return that, and fail for any other token.
+    pub fn expect_string(&mut self) -> JsonParseResult<&$lt str, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::StringLiteral];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::StringLiteral(s) => Ok(s),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_string], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_string_or_end_array(&mut self) -> JsonParseResult<Option<&$lt str>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::StringLiteral(s) => Ok(Some(s)),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" The same as [", stringify!($reader), "::expect_string], but accepting a `null` literal which is")]
+    ///  returned as `None`.
+    pub fn expect_opt_string(&mut self) -> JsonParseResult<Option<&$lt str>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(None),
+            JsonReadToken::StringLiteral(s) => Ok(Some(s)),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_opt_string], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_opt_string_or_end_array(&mut self) -> JsonParseResult<Option<Option<&$lt str>>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(Some(None)),
+            JsonReadToken::StringLiteral(s) => Ok(Some(Some(s))),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    /// `true` and `false` literals are returned as a boolean token, all other tokens cause the
+    ///  function to fail.
+    pub fn expect_bool(&mut self) -> JsonParseResult<bool, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::BooleanLiteral];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::BooleanLiteral(b) => Ok(b),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_bool], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_bool_or_end_array(&mut self) -> JsonParseResult<Option<bool>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::BooleanLiteral(b) => Ok(Some(b)),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" The same as [", stringify!($reader), "::expect_bool], but accepting a `null` literal which is")]
+    ///  returned as `None`.
+    pub fn expect_opt_bool(&mut self) -> JsonParseResult<Option<bool>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(None),
+            JsonReadToken::BooleanLiteral(b) => Ok(Some(b)),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_opt_bool], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_opt_bool_or_end_array(&mut self) -> JsonParseResult<Option<Option<bool>>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(Some(None)),
+            JsonReadToken::BooleanLiteral(b) => Ok(Some(Some(b))),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    /// Fails for any token except the `{` that starts an object.
+    pub fn expect_start_object(&mut self) -> JsonParseResult<(), $err> {
+        self.expect_single_token(&[JsonReadTokenKind::StartObject])
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_start_object], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_start_object_or_end_array(&mut self) -> JsonParseResult<Option<()>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::StartObject => Ok(Some(())),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" The same as [", stringify!($reader), "::expect_start_object], but accepting a `null` literal which is")]
+    ///  returned as `None`.
+    pub fn expect_opt_start_object(&mut self) -> JsonParseResult<Option<()>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(None),
+            JsonReadToken::StartObject => Ok(Some(())),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_opt_start_object], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_opt_start_object_or_end_array(&mut self) -> JsonParseResult<Option<Option<()>>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(Some(None)),
+            JsonReadToken::StartObject => Ok(Some(Some(()))),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    /// Fails for any token except the `}` that ends an object.
+    pub fn expect_end_object(&mut self) -> JsonParseResult<(), $err> {
+        self.expect_single_token(&[JsonReadTokenKind::EndObject])
+    }
+
+    /// Fails for any token except the `[` that starts an array.
+    pub fn expect_start_array(&mut self) -> JsonParseResult<(), $err> {
+        self.expect_single_token(&[JsonReadTokenKind::StartArray])
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_start_array], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_start_array_or_end_array(&mut self) -> JsonParseResult<Option<()>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::StartArray => Ok(Some(())),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" The same as [", stringify!($reader), "::expect_start_array], but accepting a `null` literal which is")]
+    ///  returned as `None`.
+    pub fn expect_opt_start_array(&mut self) -> JsonParseResult<Option<()>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(None),
+            JsonReadToken::StartArray => Ok(Some(())),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    #[doc = concat!(" Same as [", stringify!($reader), "::expect_opt_start_array], except that it returns `Ok(None)` if it reaches the end of an")]
+    ///  array instead
+    pub fn expect_opt_start_array_or_end_array(&mut self) -> JsonParseResult<Option<Option<()>>, $err> {
+        let location = self.location();
+        let expected = &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray];
+        self.check_next_kind(expected, location)?;
+        let next = self.next_non_comment()?;
+        match next {
+            JsonReadToken::NullLiteral => Ok(Some(None)),
+            JsonReadToken::StartArray => Ok(Some(Some(()))),
+            JsonReadToken::EndArray => Ok(None),
+            other => Err(JsonParseError::Parse(ParseErrorKind::unexpected_token(expected, other.token_kind()), location, None)),
+        }
+    }
+
+    /// Fails for any token except the `]` that ends an array.
+    pub fn expect_end_array(&mut self) -> JsonParseResult<(), $err> {
+        self.expect_single_token(&[JsonReadTokenKind::EndArray])
+    }
+
+    fn expect_single_token(&mut self, expected: &'static [JsonReadTokenKind]) -> JsonParseResult<(), $err> {
+        let found = self.next_non_comment()?.token_kind();
+        if !expected.contains(&found) {
+            return self.inner.parse_err(ParseErrorKind::unexpected_token(expected, found));
+        }
+        Ok(())
+    }
+
+    /// Fails if the stream contains any tokens - this is for checking that the entire JSON input
+    ///  was consumed
+    pub fn expect_end_of_stream(&mut self) -> JsonParseResult<(), $err> {
+        self.expect_single_token(&[JsonReadTokenKind::EndOfStream])
+    }
+
+    /// This function assumes that it is called inside an object or array, and silently consumes
+    ///  all tokens until and including the closing `}` or `]`.
+    ///
+    /// This function is useful for gracefully ignoring array elements with an unsupported type. See
+    ///  the `skipping.rs` example for details.
+    pub fn skip_to_end_of_current_scope(&mut self) -> JsonParseResult<(), $err> {
+        let mut nesting_level = 1;
+        loop {
+            match self.next_non_comment()? {
+                JsonReadToken::StartObject | JsonReadToken::StartArray => {
+                    nesting_level += 1;
+                }
+                JsonReadToken::EndObject | JsonReadToken::EndArray=> {
+                    nesting_level -= 1;
+                    if nesting_level == 0 {
+                        break;
+                    }
+                }
+                JsonReadToken::EndOfStream => {
+                    return self.inner.parse_err(ParseErrorKind::UnexpectedEndOfInput);
+                }
+                _ => {
+                    continue;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// This function skips the value starting with the next token. This can be a single-token value,
+    ///  but it can also be an object or array of unknown structure and nesting depth, in which
+    ///  case this function silently consumes tokens until it reaches the matching closing `}` or
+    ///  `]`.
+    ///
+    /// This function is useful for gracefully ignoring object members with an unknown key - see
+    ///  the `skipping.rs` example for details.
+    pub fn skip_value(&mut self) -> JsonParseResult<(), $err> {
+        match self.next_non_comment()?.token_kind() {
+            JsonReadTokenKind::StartObject |
+            JsonReadTokenKind::StartArray => {
+                self.skip_to_end_of_current_scope()
+            }
+            JsonReadTokenKind::StringLiteral |
+            JsonReadTokenKind::NumberLiteral |
+            JsonReadTokenKind::BooleanLiteral |
+            JsonReadTokenKind::NullLiteral => {
+                Ok(())
+            }
+            found => {
+                self.inner.parse_err(ParseErrorKind::unexpected_token(VALUE_START_KINDS, found))
+            }
+        }
+    }
+
+    };
+}
+pub(crate) use expect_functions;
@@ -33,13 +33,53 @@ impl <W: std::io::Write> BlockingWrite for W {
 ///  any implementation of `Read`. The reason for introducing [BlockingRead] is that it allows
 ///  json-streaming in a `no-std` environment.
 ///
-/// Note that json-streaming reads data from a [BlockingRead] in many small chunks without any I/O
-///  buffering. It is the client's responsibility to use `std::io::BufRead` or similar for
-///  improved performance where desired.
+/// The only required method is [BlockingRead::read] which reads a single byte. Implementations
+///  that hold data in memory can additionally provide slice-oriented access through
+///  [BlockingRead::fill_buf] and [BlockingRead::consume] (modelled on `std::io::BufRead`), which
+///  allows [crate::blocking::JsonReader] to scan whitespace, strings and numbers a slice at a time
+///  rather than byte by byte.
+///
+/// The blanket implementation for `Read` does no buffering. [crate::blocking::JsonReader::new]
+///  compensates for that with a read-ahead buffer of its own; in other cases, wrap the reader in
+///  a [BufferedRead] for improved performance, which works both with and without the standard
+///  library.
 pub trait BlockingRead {
     type Error: Error;
 
     fn read(&mut self) -> Result<Option<u8>, Self::Error>;
+
+    /// Reads some bytes into `buf`, returning the number of bytes read. `Ok(0)` signals the end
+    ///  of the stream (or an empty `buf`).
+    ///
+    /// The default implementation reads a single byte using [BlockingRead::read].
+    fn read_slice(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        match self.read()? {
+            Some(b) => {
+                buf[0] = b;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Returns the bytes that are buffered by this reader, reading more data from the underlying
+    ///  source if the buffer is empty. Bytes in the returned slice are not consumed until
+    ///  [BlockingRead::consume] is called.
+    ///
+    /// An empty slice means that there is no more data, or that this reader does not support
+    ///  slice-oriented access. Either way, callers fall back to [BlockingRead::read]. That is what
+    ///  the default implementation does.
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(&[])
+    }
+
+    /// Marks `amount` bytes returned by [BlockingRead::fill_buf] as consumed.
+    fn consume(&mut self, amount: usize) {
+        let _ = amount;
+    }
 }
 
 #[cfg(feature = "std")]
@@ -58,4 +98,136 @@ impl <R: std::io::Read> BlockingRead for R {
             Ok(None)
         }
     }
-}
\ No newline at end of file
+
+    fn read_slice(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        R::read(self, buf)
+    }
+}
+
+/// [BufferedRead] adds an in-memory buffer to a [BlockingRead], similar to `std::io::BufReader`.
+///  It fills the buffer using [BlockingRead::read_slice] and provides slice-oriented access to it,
+///  allowing [crate::blocking::JsonReader] to process data a slice at a time.
+///
+/// The buffer is provided by the caller, so this works in a no-std environment as well:
+///
+/// ```
+/// use json_streaming::blocking::*;
+/// # fn read(r: &mut impl std::io::Read) -> Result<(), json_streaming::shared::JsonParseError<std::io::Error>> {
+/// let mut buffered = BufferedRead::new(r, [0u8; 4096]);
+/// let mut json_reader = JsonReader::new(1024, &mut buffered);
+/// let n: u32 = json_reader.expect_number()?;
+/// # Ok(()) }
+/// ```
+pub struct BufferedRead<R: BlockingRead, B: AsMut<[u8]>> {
+    reader: R,
+    buf: B,
+    pos: usize,
+    end: usize,
+}
+impl<R: BlockingRead, B: AsMut<[u8]>> BufferedRead<R, B> {
+    /// Create a [BufferedRead] wrapping `reader`, using `buf` as its buffer. The buffer's size
+    ///  determines the maximum number of bytes read from `reader` at a time.
+    pub fn new(reader: R, buf: B) -> Self {
+        BufferedRead {
+            reader,
+            buf,
+            pos: 0,
+            end: 0,
+        }
+    }
+
+    /// Returns the wrapped reader, discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BlockingRead, B: AsMut<[u8]>> BlockingRead for BufferedRead<R, B> {
+    type Error = R::Error;
+
+    fn read(&mut self) -> Result<Option<u8>, Self::Error> {
+        let result = self.fill_buf()?.first().cloned();
+        if result.is_some() {
+            self.pos += 1;
+        }
+        Ok(result)
+    }
+
+    fn read_slice(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.pos += len;
+        Ok(len)
+    }
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if self.pos == self.end {
+            self.end = self.reader.read_slice(self.buf.as_mut())?;
+            self.pos = 0;
+        }
+        Ok(&self.buf.as_mut()[self.pos..self.end])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = self.end.min(self.pos + amount);
+    }
+}
+
+/// The reader that a [crate::blocking::JsonReader] consumes, with an optional read-ahead buffer.
+///  The read-ahead buffer is only used if the reader does not provide slice-oriented access
+///  itself, giving plain `std::io::Read` implementations a slice path without a [BufferedRead].
+pub(crate) struct ReadAhead<'a, R: BlockingRead, B: AsMut<[u8]>> {
+    reader: &'a mut R,
+    buf: Option<B>,
+    pos: usize,
+    end: usize,
+}
+impl<'a, R: BlockingRead, B: AsMut<[u8]>> ReadAhead<'a, R, B> {
+    pub fn new(reader: &'a mut R) -> Self {
+        ReadAhead {
+            reader,
+            buf: None,
+            pos: 0,
+            end: 0,
+        }
+    }
+
+    pub fn set_buf(&mut self, buf: B) {
+        self.buf = Some(buf);
+    }
+
+    pub fn read(&mut self) -> Result<Option<u8>, R::Error> {
+        if self.buf.is_none() {
+            return self.reader.read();
+        }
+        let result = self.fill_buf()?.first().cloned();
+        if result.is_some() {
+            self.consume(1);
+        }
+        Ok(result)
+    }
+
+    pub fn fill_buf(&mut self) -> Result<&[u8], R::Error> {
+        let Some(buf) = self.buf.as_mut() else {
+            return self.reader.fill_buf();
+        };
+        if self.pos == self.end {
+            if !self.reader.fill_buf()?.is_empty() {
+                return self.reader.fill_buf();
+            }
+            self.end = self.reader.read_slice(buf.as_mut())?;
+            self.pos = 0;
+        }
+        Ok(&buf.as_mut()[self.pos..self.end])
+    }
+
+    pub fn consume(&mut self, amount: usize) {
+        if self.pos < self.end {
+            self.pos = self.end.min(self.pos + amount);
+        }
+        else {
+            self.reader.consume(amount);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::blocking::array::JsonArray;
+use crate::blocking::io::BlockingWrite;
+use crate::blocking::json_writer::JsonWriter;
+use crate::blocking::object::JsonObject;
+use crate::shared::*;
+
+/// A [JsonLinesWriter] writes a [JSON Lines](https://jsonlines.org) stream, i.e. a stream with one
+///  JSON value per line. It always uses [CompactFormatter] so that each record fits into a single
+///  line.
+///
+/// Each record is a top-level [JsonObject] or [JsonArray] handed out by
+///  [JsonLinesWriter::start_object] or [JsonLinesWriter::start_array], which writes the newline
+///  terminating the record when it goes out of scope or when its `end()` function is called, or
+///  a scalar written by one of the `write_*_value` functions.
+///
+/// ```
+/// use json_streaming::blocking::*;
+///
+/// let mut buf = Vec::new();
+/// let mut json_lines_writer = JsonLinesWriter::new(&mut buf);
+/// for i in 0..2 {
+///     let mut o = json_lines_writer.start_object().unwrap();
+///     o.write_u32_value("a", i).unwrap();
+/// }
+/// json_lines_writer.write_string_value("done").unwrap();
+/// assert_eq!(json_lines_writer.num_records(), 3);
+/// json_lines_writer.flush().unwrap();
+/// assert_eq!(String::from_utf8(buf).unwrap(), "{\"a\":0}\n{\"a\":1}\n\"done\"\n");
+/// ```
+pub struct JsonLinesWriter<'a, W: BlockingWrite, FF: FloatFormat = DefaultFloatFormat> {
+    json_writer: JsonWriter<'a, W, CompactFormatter, FF>,
+    num_records: usize,
+}
+
+impl<'a, W: BlockingWrite> JsonLinesWriter<'a, W, DefaultFloatFormat> {
+    /// Creates a [JsonLinesWriter] with [DefaultFloatFormat]
+    pub fn new(inner: &'a mut W) -> Self {
+        JsonLinesWriter::new_with_float_format(inner, DefaultFloatFormat)
+    }
+}
+
+impl<'a, W: BlockingWrite, FF: FloatFormat> JsonLinesWriter<'a, W, FF> {
+    /// Creates a [JsonLinesWriter] with an explicitly provided [FloatFormat]
+    pub fn new_with_float_format(inner: &'a mut W, float_format: FF) -> Self {
+        JsonLinesWriter {
+            json_writer: JsonWriter::new(inner, CompactFormatter, float_format),
+            num_records: 0,
+        }
+    }
+
+    /// Returns the number of records that were started so far
+    pub fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    /// Starts a record that is an object. The returned [JsonObject] writes the newline after the
+    ///  record when it goes out of scope, or when its `end()` function is called.
+    pub fn start_object(&mut self) -> Result<JsonObject<'_, 'a, W, CompactFormatter, FF>, W::Error> {
+        self.num_records += 1;
+        JsonObject::new_record(&mut self.json_writer)
+    }
+
+    /// Starts a record that is an array. The returned [JsonArray] writes the newline after the
+    ///  record when it goes out of scope, or when its `end()` function is called.
+    pub fn start_array(&mut self) -> Result<JsonArray<'_, 'a, W, CompactFormatter, FF>, W::Error> {
+        self.num_records += 1;
+        JsonArray::new_record(&mut self.json_writer)
+    }
+
+    /// Write a record of type 'string', escaping the provided string value.
+    pub fn write_string_value(&mut self, value: &str) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_escaped_string(value)?;
+        self.end_record()
+    }
+
+    /// Write a record of type 'bool'.
+    pub fn write_bool_value(&mut self, value: bool) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_bool(value)?;
+        self.end_record()
+    }
+
+    /// Write a null literal as a record.
+    pub fn write_null_value(&mut self) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_bytes(b"null")?;
+        self.end_record()
+    }
+
+    /// Write an f64 value as a record. If the value is not finite (i.e. infinite or NaN), a null
+    ///  literal is written instead.
+    pub fn write_f64_value(&mut self, value: f64) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_f64(value)?;
+        self.end_record()
+    }
+
+    /// Write an f32 value as a record. If the value is not finite (i.e. infinite or NaN), a null
+    ///  literal is written instead.
+    pub fn write_f32_value(&mut self, value: f32) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_f32(value)?;
+        self.end_record()
+    }
+
+    fn end_record(&mut self) -> Result<(), W::Error> {
+        self.json_writer.write_bytes(b"\n")
+    }
+
+    /// Returns any unreported error that occurred when a record went out of scope, see
+    ///  [JsonWriter::flush]
+    pub fn flush(&mut self) -> Result<(), W::Error> {
+        self.json_writer.flush()
+    }
+
+    /// End this [JsonLinesWriter]'s lifetime, returning the [BlockingWrite] instance it owned.
+    ///  This function returns any unreported errors.
+    pub fn into_inner(self) -> Result<&'a mut W, W::Error> {
+        self.json_writer.into_inner()
+    }
+}
+
+macro_rules! write_lines_int {
+    ($t:ty ; $f:ident) => {
+impl<'a, W: BlockingWrite, FF: FloatFormat> JsonLinesWriter<'a, W, FF> {
+    /// Write a record with an int value of type $t.
+    pub fn $f(&mut self, value: $t) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_raw_num(value)?;
+        self.end_record()
+    }
+}
+    };
+}
+write_lines_int!(i8; write_i8_value);
+write_lines_int!(u8; write_u8_value);
+write_lines_int!(i16; write_i16_value);
+write_lines_int!(u16; write_u16_value);
+write_lines_int!(i32; write_i32_value);
+write_lines_int!(u32; write_u32_value);
+write_lines_int!(i64; write_i64_value);
+write_lines_int!(u64; write_u64_value);
+write_lines_int!(i128; write_i128_value);
+write_lines_int!(u128; write_u128_value);
+write_lines_int!(isize; write_isize_value);
+write_lines_int!(usize; write_usize_value);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocking::JsonReader;
+    use crate::blocking::JsonLinesReader;
+    use rstest::*;
+    use std::io;
+
+    type LW<'a> = JsonLinesWriter<'a, Vec<u8>>;
+    type RecordsCode = Box<dyn Fn(&mut LW) -> io::Result<()>>;
+
+    #[rstest]
+    #[case::none(Box::new(|_: &mut LW| Ok(())), "", 0)]
+    #[case::objects(Box::new(|w: &mut LW| { w.start_object()?.write_u32_value("a", 1)?; let mut o = w.start_object()?; o.start_array("b")?.write_null_value() }), "{\"a\":1}\n{\"b\":[null]}\n", 2)]
+    #[case::arrays(Box::new(|w: &mut LW| { w.start_array()?.end()?; w.start_array()?.start_object()?.end() }), "[]\n[{}]\n", 2)]
+    #[case::scalars(Box::new(|w: &mut LW| { w.write_string_value("x\ny")?; w.write_bool_value(true)?; w.write_null_value()?; w.write_f64_value(1.5)?; w.write_f32_value(f32::NAN)?; w.write_i32_value(-3) }), "\"x\\ny\"\ntrue\nnull\n1.5\nnull\n-3\n", 6)]
+    #[case::mixed(Box::new(|w: &mut LW| { w.write_u8_value(1)?; w.start_object()?.end()?; w.write_usize_value(2) }), "1\n{}\n2\n", 3)]
+    fn test_json_lines_writer(#[case] code: RecordsCode, #[case] expected: &str, #[case] expected_num_records: usize) -> io::Result<()> {
+        let mut buf = Vec::new();
+        let mut json_lines_writer = JsonLinesWriter::new(&mut buf);
+        code(&mut json_lines_writer)?;
+        assert_eq!(json_lines_writer.num_records(), expected_num_records);
+        json_lines_writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> io::Result<()> {
+        let mut buf = Vec::new();
+        let mut json_lines_writer = JsonLinesWriter::new(&mut buf);
+        for i in 0..3 {
+            let mut o = json_lines_writer.start_object()?;
+            o.write_string_value("text", "multi\nline")?;
+            o.start_array("values")?.write_u32_value(i)?;
+        }
+        json_lines_writer.flush()?;
+
+        let mut r = io::Cursor::new(buf);
+        let mut json_lines_reader = JsonLinesReader::new(JsonReader::new(64, &mut r));
+        let mut num_records = 0;
+        while json_lines_reader.next_record().unwrap() {
+            json_lines_reader.json_reader().skip_value().unwrap();
+            num_records += 1;
+        }
+        assert_eq!(num_records, 3);
+        Ok(())
+    }
+}
@@ -43,29 +43,8 @@ impl <'a, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonWriter<'a, W,
     /// Internal API for writing a string as an escaped JSON string.
     pub fn write_escaped_string(&mut self, s: &str) -> Result<(), W::Error> {
         self.write_bytes(b"\"")?;
-        for b in s.bytes() {
-            match b {
-                b'"' => self.write_bytes(b"\\\"")?,
-                b'\\' => self.write_bytes(b"\\\\")?,
-                b'\x08' => self.write_bytes(b"\\b")?,
-                b'\x0c' => self.write_bytes(b"\\f")?,
-                b'\n' => self.write_bytes(b"\\n")?,
-                b'\r' => self.write_bytes(b"\\r")?,
-                b'\t' => self.write_bytes(b"\\t")?,
-                b if b < 0x20 => {
-                    static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
-                    let bytes = &[
-                        b'\\',
-                        b'u',
-                        b'0',
-                        b'0',
-                        HEX_DIGITS[(b >> 4) as usize],
-                        HEX_DIGITS[(b & 0xF) as usize],
-                    ];
-                    self.write_bytes(bytes)?
-                },
-                b => self.write_bytes(&[b])?,
-            }
+        for chunk in json_escaped_chunks(s) {
+            self.write_bytes(chunk)?;
         }
         self.write_bytes(b"\"")?;
         Ok(())
@@ -130,6 +109,19 @@ impl <'a, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonWriter<'a, W,
         self.inner.write_all(self.formatter.indent().as_bytes())
     }
 
+    /// Starts a record of a JSON text sequence ([RFC 7464](https://www.rfc-editor.org/rfc/rfc7464),
+    ///  `application/json-seq`) by writing a record separator. The record's value is written as
+    ///  usual, e.g. with a `JsonObject`, followed by [JsonWriter::end_seq_record].
+    pub fn start_seq_record(&mut self) -> Result<(), W::Error> {
+        self.write_bytes(&[RECORD_SEPARATOR])
+    }
+
+    /// Ends a record of a JSON text sequence by writing a newline, which also marks a top-level
+    ///  number as complete for readers
+    pub fn end_seq_record(&mut self) -> Result<(), W::Error> {
+        self.write_bytes(b"\n")
+    }
+
     /// Check and return any unreported error that occurred when an object / array went out of 
     ///  scope. Applications should call this function when serialization is complete to ensure
     ///  that no errors get lost.    
@@ -177,7 +169,7 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> FormatWrapper<
         Self {
             inner,
             cached_error: None,
-            pd: PhantomData::default(),
+            pd: PhantomData,
         }
     }
 
@@ -222,6 +214,7 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> core::fmt::Wri
 
 
 #[cfg(test)]
+#[allow(clippy::io_other_error)]
 mod tests {
     use super::*;
     use rstest::*;
@@ -425,4 +418,20 @@ mod tests {
 
         assert_eq!(as_written_string(writer), "yo");
     }
+
+    #[test]
+    fn test_seq_records() -> io::Result<()> {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_pretty(&mut buf);
+        writer.start_seq_record()?;
+        let mut o = crate::blocking::JsonObject::new(&mut writer)?;
+        o.write_u32_value("a", 1)?;
+        o.end()?;
+        writer.end_seq_record()?;
+        writer.start_seq_record()?;
+        writer.write_raw_num(2)?;
+        writer.end_seq_record()?;
+        assert_eq!(as_written_string(writer), "\x1e{\n  \"a\": 1\n}\n\x1e2\n");
+        Ok(())
+    }
 }
@@ -1,21 +1,54 @@
 
+pub(crate) mod json_lines_read;
+pub(crate) mod json_lines_write;
+pub(crate) mod json_seq_read;
 pub(crate) mod json_writer;
 pub(crate) mod object;
+#[cfg(feature = "std")]
+pub(crate) mod parallel_json_lines;
+pub(crate) mod query;
 pub(crate) mod array;
+#[cfg(feature = "serde")]
+pub(crate) mod deserialize;
+pub(crate) mod expect;
 pub(crate) mod read;
+#[cfg(feature = "serde")]
+pub(crate) mod serialize;
+pub(crate) mod slice_read;
+#[cfg(feature = "std")]
+pub(crate) mod validate;
 pub (crate) mod io;
 
 #[allow(unused_imports)]
 pub use array::*;
+#[cfg(feature = "serde")]
+#[allow(unused_imports)]
+pub use deserialize::*;
 #[allow(unused_imports)]
 pub use io::*;
 #[allow(unused_imports)]
+pub use json_lines_read::*;
+#[allow(unused_imports)]
+pub use json_lines_write::*;
+#[allow(unused_imports)]
+pub use json_seq_read::*;
+#[allow(unused_imports)]
 pub use json_writer::*;
 #[allow(unused_imports)]
 pub use object::*;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use parallel_json_lines::*;
+#[allow(unused_imports)]
+pub use query::*;
 
 #[allow(unused_imports)]
 pub use read::*;
+#[allow(unused_imports)]
+pub use slice_read::*;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use validate::*;
 
 
 #[cfg(test)]
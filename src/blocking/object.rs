@@ -17,6 +17,8 @@ pub struct JsonObject<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatForma
     writer: &'a mut JsonWriter<'b, W, F, FF>,
     is_initial: bool,
     is_ended: bool,
+    /// a JSON Lines record, see `JsonLinesWriter`
+    is_record: bool,
 }
 impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<'a, 'b, W, F, FF> {
     /// Create a new [JsonObject] instance. Application code can do this explicitly only initially
@@ -28,9 +30,18 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<'a,
             writer,
             is_initial: true,
             is_ended: false,
+            is_record: false,
         })
     }
 
+    /// Creates a top-level [JsonObject] that is a record of JSON Lines, writing a newline after the
+    ///  closing bracket
+    pub(crate) fn new_record(writer: &'a mut JsonWriter<'b, W, F, FF>) -> Result<Self, W::Error> {
+        let mut result = Self::new(writer)?;
+        result.is_record = true;
+        Ok(result)
+    }
+
     fn write_key(&mut self, key: &str) -> Result<(), W::Error> {
         if !self.is_initial {
             self.writer.write_bytes(b",")?;
@@ -79,6 +90,19 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<'a,
         self.writer.write_f32(value)
     }
 
+    /// Write a key/value pair with a value that is serialized with serde (requires the `serde`
+    ///  feature), honoring this writer's [JsonFormatter] and [FloatFormat]. The representation is
+    ///  the same as `serde_json`'s: structs and maps as objects, sequences and tuples as arrays, and
+    ///  enum variants as a string or an object with the variant's name as its single key.
+    ///
+    /// The value is written while it is serialized, so if serialization fails with
+    ///  [JsonSerializeError::Data], the output is incomplete.
+    #[cfg(feature = "serde")]
+    pub fn write_serialize<T: serde::Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), JsonSerializeError<W::Error>> {
+        self.write_key(key)?;
+        serialize_to_sink(self.writer, value)
+    }
+
     /// Start a nested object under a given key. This function returns a new [JsonObject] instance
     ///  for writing elements to the nested object. When the returned [JsonObject] goes out of scope
     ///  (per syntactic scope or an explicit call to `end()`), the nested object is closed, and
@@ -111,6 +135,9 @@ impl<'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<'a,
         self.writer.write_format_before_end_nested(self.is_initial)?;
         self.writer.write_bytes(b"}")?;
         self.is_ended = true;
+        if self.is_record {
+            self.writer.write_bytes(b"\n")?;
+        }
         Ok(())
     }
 }
@@ -151,6 +178,7 @@ impl <'a, 'b, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> Drop for Json
 
 
 #[cfg(test)]
+#[allow(clippy::type_complexity)]
 mod tests {
     use super::*;
     use rstest::*;
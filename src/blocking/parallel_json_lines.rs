@@ -0,0 +1,512 @@
+use crate::blocking::json_lines_read::JsonLinesReader;
+use crate::blocking::read::JsonReader;
+use crate::shared::*;
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// The default size of the chunks into which [ParallelJsonLines] splits its input
+pub const DEFAULT_JSON_LINES_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The result of reading a single record, delivered together with the record's number
+pub type JsonLinesResult<T> = Result<T, JsonLinesError<io::Error>>;
+
+/// [ParallelJsonLines] reads a [JSON Lines](https://jsonlines.org) stream on several threads.
+///  It splits the stream into chunks at line boundaries, and worker threads read the chunks with
+///  a [JsonReader] each, calling a parse function for every record. The results are delivered
+///  to a second function on the calling thread, either in the order of the records or in the
+///  order in which the chunks are finished.
+///
+/// Records are read with the same grammar checks as [JsonLinesReader]. The parse function
+///  receives a [JsonReader] that is positioned at the start of a record, and it is expected to
+///  read the record's value. If it does not read the entire record, the rest of the record's line
+///  is skipped, and if the record's line contains another value, this is delivered as an error
+///  with [ParseErrorKind::MultipleTopLevelValues]. If the parse function returns an error, the
+///  error is delivered as a [JsonLinesError] with the record's number and the error's location in
+///  the stream, and reading continues with the next line. Blank lines are skipped and not
+///  counted as records.
+///
+/// If the parse function panics, the panic is propagated to the calling thread after the other
+///  threads stopped.
+///
+/// Splitting happens on a separate thread and looks only for newlines, so this scales with the
+///  number of threads as long as parsing is the bottleneck. A record must not span more than one
+///  line.
+///
+/// ```
+/// use json_streaming::blocking::*;
+///
+/// let json_lines = b"{\"a\": 1}\n{\"a\": 2}\n{\"a\": x}\n{\"a\": 4}\n";
+/// let mut sum = 0;
+/// let mut errors = Vec::new();
+/// ParallelJsonLines::new()
+///     .with_num_threads(2)
+///     .process_slice(json_lines, |json_reader| {
+///         json_reader.expect_start_object()?;
+///         json_reader.expect_key()?;
+///         let a: u32 = json_reader.expect_number()?;
+///         json_reader.expect_end_object()?;
+///         Ok(a)
+///     }, |record, result| match result {
+///         Ok(a) => sum += a,
+///         Err(e) => errors.push((record, e.offset)),
+///     });
+/// assert_eq!(sum, 7);
+/// assert_eq!(errors, vec![(2, 25)]);
+/// ```
+pub struct ParallelJsonLines {
+    num_threads: usize,
+    chunk_size: usize,
+    buf_size: usize,
+    ordered: bool,
+}
+
+impl Default for ParallelJsonLines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A chunk of the input, which starts at the start of a line
+struct Chunk<D> {
+    index: usize,
+    /// the location of the chunk's first byte in the input
+    base: Location,
+    /// the number of records in the input before the chunk
+    first_record: usize,
+    data: D,
+}
+
+impl ParallelJsonLines {
+    /// Creates a [ParallelJsonLines] with one thread per available CPU, chunks of
+    ///  [DEFAULT_JSON_LINES_CHUNK_SIZE] bytes, a read buffer of 1024 bytes per thread, and
+    ///  ordered delivery.
+    pub fn new() -> ParallelJsonLines {
+        ParallelJsonLines {
+            num_threads: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            chunk_size: DEFAULT_JSON_LINES_CHUNK_SIZE,
+            buf_size: 1024,
+            ordered: true,
+        }
+    }
+
+    /// Sets the number of worker threads, which must be at least 1
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        assert!(num_threads > 0, "at least one thread is required");
+        self.num_threads = num_threads;
+        self
+    }
+
+    /// Sets the approximate size of the chunks into which the input is split: A chunk ends at the
+    ///  end of the line that reaches this size. Smaller chunks distribute work more evenly, and
+    ///  larger chunks reduce the overhead of coordinating the threads.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the size of each [JsonReader]'s read buffer, i.e. the maximum length of keys, strings
+    ///  and numbers
+    pub fn with_buf_size(mut self, buf_size: usize) -> Self {
+        self.buf_size = buf_size;
+        self
+    }
+
+    /// Deliver results as soon as a chunk is finished, rather than in the order of the records.
+    ///  The records of each chunk are still delivered in order. This keeps fewer results in
+    ///  memory when chunks take very different amounts of time.
+    pub fn with_unordered_delivery(mut self) -> Self {
+        self.ordered = false;
+        self
+    }
+
+    /// Processes JSON Lines in memory, calling `parse` for each record on a worker thread and
+    ///  `deliver` for each result on the calling thread.
+    pub fn process_slice<T, P, D>(&self, input: &[u8], parse: P, deliver: D)
+    where
+        T: Send,
+        P: Fn(&mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<T, io::Error> + Sync,
+        D: FnMut(usize, JsonLinesResult<T>),
+    {
+        let chunk_size = self.chunk_size;
+        let mut remaining = input;
+        let chunks = std::iter::from_fn(move || {
+            if remaining.is_empty() {
+                return None;
+            }
+            let search_start = chunk_size.min(remaining.len()) - 1;
+            let len = remaining[search_start..].iter().position(|&b| b == b'\n')
+                .map(|pos| search_start + pos + 1)
+                .unwrap_or(remaining.len());
+            let (chunk, rest) = remaining.split_at(len);
+            remaining = rest;
+            Some(Ok(chunk))
+        });
+        // reading from a slice can not fail
+        let _ = self.process_chunks(chunks, &parse, deliver);
+    }
+
+    /// Processes JSON Lines from a reader, calling `parse` for each record on a worker thread and
+    ///  `deliver` for each result on the calling thread. The reader is read in chunks on a
+    ///  separate thread, and an I/O error stops processing after the results of all previous
+    ///  chunks were delivered.
+    ///
+    /// There is no need to wrap the reader in a [std::io::BufReader] since it is read in large
+    ///  chunks anyway.
+    pub fn process_reader<R, T, P, D>(&self, reader: R, parse: P, deliver: D) -> io::Result<()>
+    where
+        R: io::Read + Send,
+        T: Send,
+        P: Fn(&mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<T, io::Error> + Sync,
+        D: FnMut(usize, JsonLinesResult<T>),
+    {
+        let mut chunked = ChunkedRead {
+            reader,
+            chunk_size: self.chunk_size,
+            carry: Vec::new(),
+            is_done: false,
+        };
+        let chunks = std::iter::from_fn(move || chunked.next_chunk().transpose());
+        self.process_chunks(chunks, &parse, deliver)
+    }
+
+    fn process_chunks<I, C, T, P, D>(&self, chunks: I, parse: &P, mut deliver: D) -> io::Result<()>
+    where
+        I: Iterator<Item = io::Result<C>> + Send,
+        C: AsRef<[u8]> + Send,
+        T: Send,
+        P: Fn(&mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<T, io::Error> + Sync,
+        D: FnMut(usize, JsonLinesResult<T>),
+    {
+        // a bounded channel limits the amount of input that is held in memory
+        let (chunk_tx, chunk_rx) = mpsc::sync_channel::<Chunk<C>>(2 * self.num_threads);
+        // the workers own the receiver so that it is dropped when the last of them exits (e.g.
+        //  because `parse` panicked), which stops the splitter instead of blocking it forever
+        let chunk_rx = Arc::new(Mutex::new(chunk_rx));
+        let (result_tx, result_rx) = mpsc::channel::<(usize, Vec<(usize, JsonLinesResult<T>)>)>();
+
+        thread::scope(|scope| {
+            for _ in 0..self.num_threads {
+                let result_tx = result_tx.clone();
+                let chunk_rx = Arc::clone(&chunk_rx);
+                scope.spawn(move || {
+                    loop {
+                        let chunk = chunk_rx.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                        let Ok(chunk) = chunk else {
+                            break;
+                        };
+                        let results = self.process_chunk(&chunk, parse);
+                        if result_tx.send((chunk.index, results)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(result_tx);
+            drop(chunk_rx);
+
+            let splitter = scope.spawn(move || {
+                let mut base = Location::start();
+                let mut first_record = 0;
+                for (index, data) in chunks.enumerate() {
+                    let data = data?;
+                    let bytes = data.as_ref();
+                    let next_base = Location {
+                        offset: base.offset + bytes.len(),
+                        line: base.line + bytes.iter().filter(|&&b| b == b'\n').count(),
+                        column: 1,
+                    };
+                    let next_first_record = first_record + count_records(bytes);
+                    if chunk_tx.send(Chunk { index, base, first_record, data }).is_err() {
+                        break;
+                    }
+                    base = next_base;
+                    first_record = next_first_record;
+                }
+                Ok(())
+            });
+
+            let mut pending = BTreeMap::new();
+            let mut next_index = 0;
+            for (index, results) in result_rx {
+                if !self.ordered {
+                    results.into_iter().for_each(|(record, result)| deliver(record, result));
+                    continue;
+                }
+                pending.insert(index, results);
+                while let Some(results) = pending.remove(&next_index) {
+                    results.into_iter().for_each(|(record, result)| deliver(record, result));
+                    next_index += 1;
+                }
+            }
+
+            splitter.join().expect("the splitter thread panicked")
+        })
+    }
+
+    fn process_chunk<C, T, P>(&self, chunk: &Chunk<C>, parse: &P) -> Vec<(usize, JsonLinesResult<T>)>
+    where
+        C: AsRef<[u8]>,
+        P: Fn(&mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<T, io::Error>,
+    {
+        let mut input = chunk.data.as_ref();
+        let mut json_lines_reader = JsonLinesReader::new(JsonReader::new(self.buf_size, &mut input));
+        let mut results = Vec::new();
+
+        loop {
+            match json_lines_reader.next_record() {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(error) => {
+                    // another value in the previous record's line, whose rest was skipped
+                    let can_continue = matches!(error.error, JsonParseError::Parse(..));
+                    let error = shift_json_lines_error(error, chunk);
+                    results.push((error.record, Err(error)));
+                    if can_continue {
+                        continue;
+                    }
+                    break;
+                }
+            }
+
+            match parse(json_lines_reader.json_reader()) {
+                Ok(value) => results.push((chunk.first_record + json_lines_reader.record(), Ok(value))),
+                Err(e) => {
+                    let error = json_lines_reader.record_error(e);
+                    let skipped = json_lines_reader.skip_bad_record(&error);
+                    let error = shift_json_lines_error(error, chunk);
+                    results.push((error.record, Err(error)));
+                    if skipped.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        results
+    }
+}
+
+/// Converts a [JsonLinesError] with a record number and locations in a chunk into one for the
+///  entire input
+fn shift_json_lines_error<D>(error: JsonLinesError<io::Error>, chunk: &Chunk<D>) -> JsonLinesError<io::Error> {
+    JsonLinesError {
+        record: chunk.first_record + error.record,
+        offset: chunk.base.offset + error.offset,
+        error: shift_error_location(error.error, chunk.base),
+    }
+}
+
+/// Counts the records in a chunk, i.e. the lines that are not blank
+fn count_records(bytes: &[u8]) -> usize {
+    let mut num_records = 0;
+    let mut is_blank = true;
+    for &b in bytes {
+        match b {
+            b'\n' => is_blank = true,
+            b' ' | b'\t' | b'\r' => {}
+            _ => {
+                if is_blank {
+                    num_records += 1;
+                }
+                is_blank = false;
+            }
+        }
+    }
+    num_records
+}
+
+/// Reads chunks that end at the end of a line and have (at least) a given size, unless a line
+///  is longer than that or the input ends
+struct ChunkedRead<R: io::Read> {
+    reader: R,
+    chunk_size: usize,
+    /// the start of the next chunk, which was read as part of the previous chunk
+    carry: Vec<u8>,
+    is_done: bool,
+}
+impl <R: io::Read> ChunkedRead<R> {
+    fn next_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut buf = std::mem::take(&mut self.carry);
+        loop {
+            if self.is_done {
+                return Ok((!buf.is_empty()).then_some(buf));
+            }
+            if buf.len() >= self.chunk_size {
+                if let Some(pos) = buf[self.chunk_size - 1..].iter().position(|&b| b == b'\n') {
+                    self.carry = buf.split_off(self.chunk_size + pos);
+                    return Ok(Some(buf));
+                }
+            }
+
+            let len = buf.len();
+            buf.resize(len + self.chunk_size, 0);
+            match self.reader.read(&mut buf[len..]) {
+                Ok(num_read) => {
+                    buf.truncate(len + num_read);
+                    self.is_done = num_read == 0;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => buf.truncate(len),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::io::Cursor;
+
+    const JSON_LINES: &str = "{\"a\": 1}\n2\n\n{\"a\": x}\n  {\"a\": 4}  \n{\"a\": 5} 6\n[7]\n8";
+
+    fn parse_record(json_reader: &mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<u32, io::Error> {
+        if json_reader.peek_kind()? == JsonReadTokenKind::StartObject {
+            json_reader.expect_start_object()?;
+            json_reader.expect_key()?;
+            let a = json_reader.expect_number()?;
+            json_reader.expect_end_object()?;
+            Ok(a)
+        }
+        else {
+            json_reader.expect_number()
+        }
+    }
+
+    fn expected_results() -> Vec<(usize, Result<u32, Location>)> {
+        vec![
+            (0, Ok(1)),
+            (1, Ok(2)),
+            (2, Err(Location { offset: 19, line: 4, column: 8 })),
+            (3, Ok(4)),
+            (4, Ok(5)),
+            (4, Err(Location { offset: 43, line: 6, column: 10 })),
+            (5, Err(Location { offset: 46, line: 7, column: 2 })),
+            (6, Ok(8)),
+        ]
+    }
+
+    fn to_comparable((record, result): (usize, JsonLinesResult<u32>)) -> (usize, Result<u32, Location>) {
+        (record, result.map_err(|e| {
+            assert_eq!(e.record, record);
+            let location = error_location(&e.error).unwrap();
+            assert_eq!(e.offset, location.offset);
+            location
+        }))
+    }
+
+    #[rstest]
+    fn test_process_slice(#[values(1, 10, 1024)] chunk_size: usize, #[values(1, 3)] num_threads: usize, #[values(true, false)] ordered: bool) {
+        let mut processor = ParallelJsonLines::new().with_chunk_size(chunk_size).with_num_threads(num_threads);
+        if !ordered {
+            processor = processor.with_unordered_delivery();
+        }
+        let mut results = Vec::new();
+        processor.process_slice(JSON_LINES.as_bytes(), parse_record, |record, result| results.push(to_comparable((record, result))));
+        if !ordered {
+            results.sort_by_key(|(record, _)| *record);
+        }
+        assert_eq!(results, expected_results());
+    }
+
+    #[rstest]
+    fn test_process_reader(#[values(1, 10, 1024)] chunk_size: usize, #[values(1, 3)] num_threads: usize) -> io::Result<()> {
+        let processor = ParallelJsonLines::new().with_chunk_size(chunk_size).with_num_threads(num_threads);
+        let mut results = Vec::new();
+        processor.process_reader(Cursor::new(JSON_LINES), parse_record, |record, result| results.push(to_comparable((record, result))))?;
+        assert_eq!(results, expected_results());
+        Ok(())
+    }
+
+    type ParseFn = fn(&mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<u32, io::Error>;
+
+    fn skip_record(_json_reader: &mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<u32, io::Error> {
+        Ok(0)
+    }
+
+    fn parse_numbers_only(json_reader: &mut JsonReader<'_, Vec<u8>, &[u8]>) -> JsonParseResult<u32, io::Error> {
+        if json_reader.peek_kind()? == JsonReadTokenKind::NumberLiteral {
+            json_reader.expect_number()
+        }
+        else {
+            Ok(0)
+        }
+    }
+
+    #[rstest]
+    #[case::not_read(skip_record, vec![0, 0, 0, 0, 0, 0, 0])]
+    #[case::peeked(parse_numbers_only, vec![0, 2, 0, 0, 0, 0, 8])]
+    fn test_process_slice_skipped_records(
+        #[case] parse: ParseFn,
+        #[case] expected: Vec<u32>,
+        #[values(1, 10, 1024)] chunk_size: usize,
+    ) {
+        let mut results = Vec::new();
+        ParallelJsonLines::new()
+            .with_chunk_size(chunk_size)
+            .with_num_threads(2)
+            .process_slice(JSON_LINES.as_bytes(), parse, |record, result| results.push(to_comparable((record, result))));
+        let expected = expected.into_iter().enumerate().map(|(record, n)| (record, Ok(n))).collect::<Vec<_>>();
+        assert_eq!(results, expected);
+    }
+
+    struct FailingRead<'a> {
+        data: &'a [u8],
+    }
+    impl io::Read for FailingRead<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.data.is_empty() {
+                return Err(io::Error::other("failing"));
+            }
+            io::Read::read(&mut self.data, buf)
+        }
+    }
+
+    #[test]
+    fn test_process_reader_fails() {
+        let processor = ParallelJsonLines::new().with_chunk_size(4).with_num_threads(2);
+        let mut results = Vec::new();
+        let result = processor.process_reader(FailingRead { data: b"1\n2\n3\n4" }, parse_record, |record, result| results.push(to_comparable((record, result))));
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Other);
+        assert_eq!(results, vec![(0, Ok(1)), (1, Ok(2))]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_process_slice_parse_panics() {
+        let json_lines = "1\n".repeat(100);
+        ParallelJsonLines::new()
+            .with_chunk_size(4)
+            .with_num_threads(1)
+            .process_slice(json_lines.as_bytes(), |_| -> JsonParseResult<u32, io::Error> { panic!() }, |_, _| {});
+    }
+
+    #[test]
+    fn test_process_slice_empty() {
+        let mut num_results = 0;
+        ParallelJsonLines::new().process_slice(b"", parse_record, |_, _| num_results += 1);
+        ParallelJsonLines::new().process_slice(b"\n \n", parse_record, |_, _| num_results += 1);
+        assert_eq!(num_results, 0);
+    }
+
+    #[rstest]
+    #[case::shorter_than_chunk(b"1\n2\n3\n", 100, vec![b"1\n2\n3\n".as_slice()])]
+    #[case::split(b"1\n2\n3\n", 3, vec![b"1\n2\n".as_slice(), b"3\n"])]
+    #[case::long_line(b"12345\n6\n", 2, vec![b"12345\n".as_slice(), b"6\n"])]
+    #[case::no_trailing_newline(b"1\n2", 1, vec![b"1\n".as_slice(), b"2"])]
+    fn test_chunked_read(#[case] input: &[u8], #[case] chunk_size: usize, #[case] expected: Vec<&[u8]>) -> io::Result<()> {
+        let mut chunked = ChunkedRead {
+            reader: input,
+            chunk_size,
+            carry: Vec::new(),
+            is_done: false,
+        };
+        let mut chunks = Vec::new();
+        while let Some(chunk) = chunked.next_chunk()? {
+            chunks.push(chunk);
+        }
+        assert_eq!(chunks, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use crate::blocking::io::BlockingRead;
+use crate::blocking::read::JsonReader;
+use crate::shared::*;
+
+/// A [JsonQueryMatcher] evaluates a [JsonQuery] while reading a document with a [JsonReader],
+///  stopping at each matching value so that application code can read it with the regular
+///  `expect_*` functions. Everything else is skipped with [JsonReader::skip_value], so subtrees
+///  that do not match are never held in memory.
+///
+/// After [JsonQueryMatcher::next_match] returns `true`, application code must consume exactly one
+///  value (possibly using [JsonReader::skip_value]) before calling it again. Since a matched value
+///  is consumed as a whole, matches nested inside another match (e.g. for `$..a` in
+///  `{"a": {"a": 1}}`) are not reported.
+///
+/// ```
+/// use json_streaming::blocking::*;
+/// use json_streaming::shared::*;
+///
+/// # fn read_ids() -> Result<(), Box<dyn std::error::Error>> {
+/// let json = r#"{"data": [{"id": 1, "name": "a"}, {"name": "b", "id": 2}], "id": 99}"#;
+/// let mut r = std::io::Cursor::new(json.as_bytes());
+/// let mut json_reader = JsonReader::new(64, &mut r);
+///
+/// let mut matcher = JsonQueryMatcher::new(JsonQuery::parse("$.data[*].id")?);
+/// let mut ids = Vec::new();
+/// while matcher.next_match(&mut json_reader)? {
+///     ids.push(json_reader.expect_number::<u32>()?);
+/// }
+/// assert_eq!(ids, vec![1, 2]);
+/// # Ok(()) }
+/// ```
+pub struct JsonQueryMatcher<'q, L: AsMut<[JsonQueryLevel]> = DefaultQueryLevels> {
+    inner: QueryMatcherInner<'q, L>,
+}
+
+impl<'q> JsonQueryMatcher<'q> {
+    /// Create a [JsonQueryMatcher] that can descend into up to [DEFAULT_MAX_QUERY_DEPTH] levels
+    ///  of nested objects and arrays, i.e. as deep as a [JsonReader] with its default nesting
+    ///  buffer. The levels are stored inline, taking a few bytes each - see
+    ///  [JsonQueryMatcher::new_with_provided_buffer] for a smaller (or larger) buffer.
+    pub fn new(query: JsonQuery<'q>) -> Self {
+        Self::new_with_provided_buffer(query, [JsonQueryLevel::default(); DEFAULT_MAX_QUERY_DEPTH])
+    }
+}
+
+impl<'q, L: AsMut<[JsonQueryLevel]>> JsonQueryMatcher<'q, L> {
+    /// Create a [JsonQueryMatcher] that uses an externally provided buffer for tracking the objects
+    ///  and arrays it descended into. The buffer's length is the maximum nesting depth.
+    pub fn new_with_provided_buffer(query: JsonQuery<'q>, levels: L) -> Self {
+        Self {
+            inner: QueryMatcherInner::new(query, levels),
+        }
+    }
+
+    /// Reads ahead to the next value that matches the query, returning `true` if there is one.
+    ///  The reader is then positioned immediately before the value. Returns `false` when the
+    ///  (top-level) document was read completely.
+    pub fn next_match<B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>>(&mut self, json_reader: &mut JsonReader<'_, B, R, S>) -> JsonParseResult<bool, R::Error> {
+        if !self.inner.is_started {
+            self.inner.is_started = true;
+            let states = self.inner.query.initial_states();
+            if self.inner.query.is_match(states) {
+                return Ok(true);
+            }
+            self.enter_value(json_reader, states)?;
+        }
+
+        loop {
+            let states = match self.inner.next_step() {
+                QueryStep::Done => return Ok(false),
+                QueryStep::ReadKey(parent_states) => {
+                    match json_reader.expect_key()? {
+                        Some(key) => self.inner.query.key_states(parent_states, key),
+                        None => {
+                            self.inner.pop();
+                            continue;
+                        }
+                    }
+                }
+                QueryStep::ReadElement(parent_states, index) => {
+                    if json_reader.peek_structural_kind()? == Some(JsonReadTokenKind::EndArray) {
+                        json_reader.next()?;
+                        self.inner.pop();
+                        continue;
+                    }
+                    self.inner.on_element();
+                    self.inner.query.index_states(parent_states, index)
+                }
+            };
+
+            if self.inner.query.is_match(states) {
+                return Ok(true);
+            }
+            self.enter_value(json_reader, states)?;
+        }
+    }
+
+    /// Skips a value that can not contain matches, and descends into an object or array that can
+    fn enter_value<B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>>(&mut self, json_reader: &mut JsonReader<'_, B, R, S>, states: u32) -> JsonParseResult<(), R::Error> {
+        if states == 0 {
+            return json_reader.skip_value();
+        }
+        // this skips comments
+        let is_array = match json_reader.peek_structural_kind()? {
+            Some(JsonReadTokenKind::StartObject) => false,
+            Some(JsonReadTokenKind::StartArray) => true,
+            _ => return json_reader.skip_value(),
+        };
+        json_reader.next()?;
+        if !self.inner.push(states, is_array) {
+            return Err(JsonParseError::MaxNestingDepthExceeded(json_reader.location()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::io;
+    use std::io::Cursor;
+
+    const JSON: &str = r#"{"data": [{"id": 1, "tags": ["a", "b"]}, {"id": 2, "nested": {"id": 3}}, 7], "id": 99}"#;
+
+    fn collect_matches(query: &str, json: &str) -> JsonParseResult<Vec<String>, io::Error> {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        let mut matcher = JsonQueryMatcher::new(JsonQuery::parse(query).unwrap());
+
+        let mut result = Vec::new();
+        while matcher.next_match(&mut json_reader)? {
+            let value = match json_reader.next()? {
+                JsonReadToken::StartObject => {
+                    json_reader.skip_to_end_of_current_scope()?;
+                    "{}".to_string()
+                }
+                JsonReadToken::StartArray => {
+                    json_reader.skip_to_end_of_current_scope()?;
+                    "[]".to_string()
+                }
+                JsonReadToken::StringLiteral(s) => s.to_string(),
+                JsonReadToken::NumberLiteral(n) => n.0.to_string(),
+                JsonReadToken::BooleanLiteral(b) => b.to_string(),
+                JsonReadToken::NullLiteral => "null".to_string(),
+                other => panic!("unexpected token {:?}", other),
+            };
+            result.push(value);
+        }
+        json_reader.expect_end_of_stream()?;
+        Ok(result)
+    }
+
+    #[rstest]
+    #[case::root("$", vec!["{}"])]
+    #[case::child("$.id", vec!["99"])]
+    #[case::missing_child("$.missing", vec![])]
+    #[case::wildcard_child("$.data[*].id", vec!["1", "2"])]
+    #[case::wildcard_root("$.*", vec!["[]", "99"])]
+    #[case::index_object("$.data[1]", vec!["{}"])]
+    #[case::index_number("$.data[2]", vec!["7"])]
+    #[case::index_out_of_range("$.data[3]", vec![])]
+    #[case::bracket_names(r#"$['data'][0]["tags"][1]"#, vec!["b"])]
+    #[case::slice("$.data[0:2].id", vec!["1", "2"])]
+    #[case::slice_step("$.data[::2]", vec!["{}", "7"])]
+    #[case::slice_open("$.data[1:]", vec!["{}", "7"])]
+    #[case::name_in_array("$.data.id", vec![])]
+    #[case::child_of_scalar("$.id.x", vec![])]
+    #[case::descendant("$..id", vec!["1", "2", "3", "99"])]
+    #[case::descendant_nested("$..nested.id", vec!["3"])]
+    #[case::descendant_wildcard("$..tags[*]", vec!["a", "b"])]
+    #[case::descendant_index("$..[1]", vec!["b", "{}"])]
+    #[case::descendant_inside_match("$..[0]", vec!["{}"])]
+    fn test_next_match(#[case] query: &str, #[case] expected: Vec<&str>) -> JsonParseResult<(), io::Error> {
+        assert_eq!(collect_matches(query, JSON)?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::root_scalar("$", "12", vec!["12"])]
+    #[case::child_of_root_scalar("$.a", "12", vec![])]
+    #[case::empty_object("$.*", "{}", vec![])]
+    #[case::empty_array("$[*]", "[]", vec![])]
+    #[case::empty_nested("$..a", r#"[[], {}, [{}], {"b": []}]"#, vec![])]
+    #[case::skipped_strings("$.b", r#"{"a": ["x", {"y": "z"}], "b": "c"}"#, vec!["c"])]
+    fn test_next_match_documents(#[case] query: &str, #[case] json: &str, #[case] expected: Vec<&str>) -> JsonParseResult<(), io::Error> {
+        assert_eq!(collect_matches(query, json)?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::invalid_json("$.a", r#"{"a" 1}"#)]
+    #[case::unterminated("$..a", r#"{"b": [1, 2"#)]
+    #[case::empty("$.a", "")]
+    fn test_next_match_fails(#[case] query: &str, #[case] json: &str) {
+        assert!(collect_matches(query, json).is_err());
+    }
+
+    #[test]
+    fn test_next_match_long_strings() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(br#"{"data": ["a string that exceeds the buffer", "another long string"]}"#);
+        let mut json_reader = JsonReader::new(8, &mut r);
+        let mut matcher = JsonQueryMatcher::new(JsonQuery::parse("$.data[*]").unwrap());
+        let mut result = Vec::new();
+        while matcher.next_match(&mut json_reader)? {
+            let mut s = String::new();
+            json_reader.expect_string_chunked(|chunk| s.push_str(chunk))?;
+            result.push(s);
+        }
+        assert_eq!(result, vec!["a string that exceeds the buffer", "another long string"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_match_default_max_depth() -> JsonParseResult<(), io::Error> {
+        // the arrays and the innermost object are nested as deeply as the reader allows
+        let depth = DEFAULT_MAX_NESTING_DEPTH - 1;
+        let json = format!("{}{{\"x\": 1}}{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(collect_matches("$..x", &json)?, vec!["1"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_match_max_depth() {
+        let mut r = Cursor::new(br#"[[1]]"#);
+        let mut json_reader = JsonReader::new(64, &mut r);
+        let mut matcher = JsonQueryMatcher::new_with_provided_buffer(JsonQuery::parse("$..x").unwrap(), [JsonQueryLevel::default(); 1]);
+        match matcher.next_match(&mut json_reader) {
+            Err(JsonParseError::MaxNestingDepthExceeded(location)) => assert_eq!(location.offset, 2),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use crate::blocking::io::BlockingRead;
+use crate::blocking::expect::expect_functions;
+use crate::blocking::io::{BlockingRead, ReadAhead};
 use core::str::FromStr;
 use crate::shared::*;
 
@@ -17,6 +18,14 @@ use crate::shared::*;
 ///  don't care about that, there is a blanket implementation of [BlockingRead] for [std::io::Read],
 ///  letting you ignore distinction between the two.
 ///
+/// [JsonReader] scans whitespace, strings and numbers a slice at a time if the reader supports
+///  slice-oriented access through [BlockingRead::fill_buf], which is usually a lot faster for large
+///  documents than reading byte by byte. For other readers, the constructors that allocate
+///  buffers on the heap (like [JsonReader::new]) add a read-ahead buffer, so the [JsonReader] may
+///  read past the end of a document. Without such a buffer (see
+///  [JsonReader::with_provided_read_buffer]), wrapping the reader in a
+///  [BufferedRead](crate::blocking::BufferedRead) adds slice-oriented access to it.
+///
 /// The [JsonReader] holds a mutable reference to the reader rather than taking ownership of it.
 ///  That means it needs to have a lifetime parameter, allowing the compiler to ensure that the
 ///  reader lives at least as long as the wrapping [JsonReader].
@@ -33,6 +42,14 @@ use crate::shared::*;
 ///  working as a denial-of-service attack. JSON parsers that work on materialized documents often
 ///  address this by placing a restriction on the maximum size of the whole document.
 ///
+/// [JsonReader] keeps track of the objects and arrays that are currently open, checking that each
+///  closing `}` or `]` matches and that keys occur only inside objects. It uses a small fixed-size
+///  buffer for this with a single bit per nesting level, limiting the maximum nesting depth. The
+///  default buffer supports [DEFAULT_MAX_NESTING_DEPTH] levels, and an application can provide its
+///  own buffer using [JsonReader::new_with_provided_buffers]. Like the limit on token size, this is
+///  a safeguard against malicious documents, and [JsonReader::with_max_nesting_depth] allows
+///  tightening it further.
+///
 /// The following code snippet shows a small working example of reading a JSON document with a
 ///  single object:
 ///
@@ -49,7 +66,7 @@ use crate::shared::*;
 ///             Some("a") => println!("a: {}", json_reader.expect_string()?),
 ///             Some("b") => println!("b: {}", json_reader.expect_string()?),
 ///             Some(_other) => {
-///                 return Err(JsonParseError::Parse("unexpected key parsing 'person'", json_reader.location()));
+///                 return json_reader.parse_err("unexpected key parsing 'person'");
 ///             },
 ///             None => break,
 ///         }
@@ -57,9 +74,9 @@ use crate::shared::*;
 ///     Ok(())
 /// }
 /// ```
-pub struct JsonReader<'a, B: AsMut<[u8]>, R: BlockingRead> {
-    inner: ReaderInner<B, R::Error>,
-    reader: &'a mut R,
+pub struct JsonReader<'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]> = DefaultNestingBuffer> {
+    inner: ReaderInner<B, S, R::Error>,
+    reader: ReadAhead<'a, R, B>,
 }
 
 #[cfg(feature = "std")]
@@ -68,6 +85,7 @@ impl<'a, R: BlockingRead> JsonReader<'a, Vec<u8>, R> {
     pub fn new(buf_size: usize, reader: &'a mut R) -> Self {
         let buf = vec![0u8; buf_size];
         Self::new_with_provided_buffer(buf, reader, false)
+            .with_provided_read_buffer(vec![0u8; DEFAULT_READ_AHEAD_SIZE])
     }
 
     /// Create a [JsonReader] without requiring commas between objects. This is intended for
@@ -75,55 +93,269 @@ impl<'a, R: BlockingRead> JsonReader<'a, Vec<u8>, R> {
     pub fn new_with_lenient_comma_handling(buf_size: usize, reader: &'a mut R) -> Self {
         let buf = vec![0u8; buf_size];
         Self::new_with_provided_buffer(buf, reader, true)
+            .with_provided_read_buffer(vec![0u8; DEFAULT_READ_AHEAD_SIZE])
+    }
+
+    /// Create a [JsonReader] that continues reading where a [ReaderCheckpoint] was taken,
+    ///  allocating a read buffer of given size on the heap. The reader must be positioned at the
+    ///  checkpoint's location, i.e. at `checkpoint.location.offset` bytes into the input.
+    pub fn new_from_checkpoint(buf_size: usize, checkpoint: ReaderCheckpoint, reader: &'a mut R) -> Self {
+        let buf = vec![0u8; buf_size];
+        Self::new_from_checkpoint_with_provided_buffer(buf, checkpoint, reader)
+            .with_provided_read_buffer(vec![0u8; DEFAULT_READ_AHEAD_SIZE])
+    }
+
+    /// Create a [JsonReader] for reading a top-level array from the element with a given index on,
+    ///  seeking the reader to the element's location in a [JsonArrayIndex] (see
+    ///  [JsonReader::index_array_elements]). The [JsonReader] is inside the array, so it can read
+    ///  the element right away, and [JsonReader::expect_end_array] after the last element.
+    ///
+    /// Fails with [std::io::ErrorKind::InvalidInput] if the array has no element with that index.
+    ///
+    /// ```
+    /// use json_streaming::blocking::*;
+    /// use json_streaming::shared::*;
+    ///
+    /// let mut r = std::io::Cursor::new(r#"[{"id": 1}, {"id": 2}, {"id": 3}]"#);
+    /// let index = JsonReader::new(64, &mut r).index_array_elements().unwrap();
+    /// assert_eq!(index.len(), 3);
+    ///
+    /// let mut json_reader = JsonReader::new_at_array_element(64, &index, 1, &mut r).unwrap();
+    /// json_reader.expect_start_object().unwrap();
+    /// assert_eq!(json_reader.expect_key().unwrap(), Some("id"));
+    /// assert_eq!(json_reader.expect_number::<u32>().unwrap(), 2);
+    /// ```
+    pub fn new_at_array_element(buf_size: usize, index: &JsonArrayIndex, element: usize, reader: &'a mut R) -> std::io::Result<Self>
+    where R: std::io::Seek
+    {
+        let checkpoint = index.checkpoint(element)
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "array element index out of range"))?;
+        reader.seek(std::io::SeekFrom::Start(checkpoint.location.offset as u64))?;
+        Ok(Self::new_from_checkpoint(buf_size, checkpoint, reader))
+    }
+
+    /// Track the JSON path of the current token (see [JsonReader::current_path]), allocating a
+    ///  buffer of given size for it on the heap. Path segments that do not fit into the buffer
+    ///  are omitted, and the path is marked as truncated.
+    pub fn with_path_tracking(self, max_path_len: usize) -> Self {
+        self.with_provided_path_buffer(vec![0u8; max_path_len])
+    }
+
+    /// Fail with a parse error at a key's location if it occurs twice in the same object. JSON
+    ///  does not define which value wins for duplicate keys, and parsers disagree, which makes
+    ///  them a vector for attacks where two components interpret a document differently.
+    ///
+    /// This keeps the keys of all open objects in hash sets - see
+    ///  [JsonReader::with_provided_key_table] for an alternative without heap allocation.
+    pub fn with_duplicate_key_detection(mut self) -> Self {
+        self.inner.key_sets = Some(Vec::new());
+        self
+    }
+
+    /// Keep the most recently consumed bytes of input for [JsonReader::error_report], allocating
+    ///  a buffer of given size for them on the heap.
+    pub fn with_error_context(self, context_size: usize) -> Self {
+        self.with_provided_context_buffer(vec![0u8; context_size])
     }
 }
 
 impl<'a, B: AsMut<[u8]>, R: BlockingRead> JsonReader<'a, B, R> {
     /// Create a [JsonReader] that uses an externally provided buffer as its read buffer. The main
     ///  reason to do this is to avoid heap allocation in a no-std environment.
+    ///
+    /// The [JsonReader] supports up to [DEFAULT_MAX_NESTING_DEPTH] levels of nested objects and
+    ///  arrays - see [JsonReader::new_with_provided_buffers] for more control.
     pub fn new_with_provided_buffer(buf: B, reader: &'a mut R, lenient_comma_handling: bool) -> Self {
         Self {
-            inner: ReaderInner::new(buf, lenient_comma_handling),
-            reader,
+            inner: ReaderInner::new(buf, default_nesting_buffer(), lenient_comma_handling),
+            reader: ReadAhead::new(reader),
+        }
+    }
+}
+
+impl<'a, B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>> JsonReader<'a, B, R, S> {
+    /// Create a [JsonReader] that uses externally provided buffers both as its read buffer and for
+    ///  tracking the nesting of objects and arrays. The nesting buffer stores a single bit per
+    ///  nesting level, so its size in bytes times 8 is the maximum nesting depth.
+    ///
+    /// This is the most flexible way to create a [JsonReader], allowing e.g. deeply nested
+    ///  documents in a no-std environment.
+    pub fn new_with_provided_buffers(buf: B, nesting_buf: S, reader: &'a mut R, lenient_comma_handling: bool) -> Self {
+        Self {
+            inner: ReaderInner::new(buf, nesting_buf, lenient_comma_handling),
+            reader: ReadAhead::new(reader),
+        }
+    }
+
+    /// Create a [JsonReader] that continues reading where a [ReaderCheckpoint] was taken, using
+    ///  an externally provided buffer as its read buffer. The nesting buffer is taken from the
+    ///  checkpoint. The reader must be positioned at the checkpoint's location, i.e. at
+    ///  `checkpoint.location.offset` bytes into the input.
+    ///
+    /// Options like [JsonReader::with_max_nesting_depth] are not part of the checkpoint and
+    ///  need to be applied again.
+    pub fn new_from_checkpoint_with_provided_buffer(buf: B, checkpoint: ReaderCheckpoint<S>, reader: &'a mut R) -> Self {
+        Self {
+            inner: ReaderInner::from_checkpoint(buf, checkpoint),
+            reader: ReadAhead::new(reader),
         }
     }
 
+    /// Limit the nesting depth of objects and arrays, failing with
+    ///  [JsonParseError::MaxNestingDepthExceeded] for documents that are nested more deeply. This
+    ///  is a safeguard against maliciously crafted documents, e.g. with a million `[` characters,
+    ///  that could exhaust the stack of application code recursing into nested values.
+    ///
+    /// The nesting buffer's size is an upper bound for the maximum nesting depth regardless of
+    ///  this setting.
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: usize) -> Self {
+        self.inner.max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    /// Replace unpaired UTF-16 surrogates in `\u` escapes with U+FFFD (the Unicode replacement
+    ///  character) instead of failing. JSON producers in languages with UTF-16 strings (notably
+    ///  JavaScript) sometimes emit lone surrogates, e.g. when truncating a string in the middle
+    ///  of a surrogate pair.
+    pub fn with_lossy_surrogate_handling(mut self) -> Self {
+        self.inner.lossy_surrogate_handling = true;
+        self
+    }
+
+    /// Reject unescaped control characters (U+0000 to U+001F) in strings. RFC 8259 requires them
+    ///  to be escaped, but by default they are accepted for compatibility with sloppy producers.
+    pub fn with_control_character_check(mut self) -> Self {
+        self.inner.reject_control_characters = true;
+        self
+    }
+
+    /// Track the JSON path of the current token (see [JsonReader::current_path]) in an externally
+    ///  provided buffer. Path segments that do not fit into the buffer are omitted, and the path
+    ///  is marked as truncated.
+    ///
+    /// If the `std` feature is active, [JsonParseError::Parse] contains the path at which the
+    ///  error occurred, i.e. the path of the unexpected token for `expect_*` functions, and the
+    ///  path of the last valid token for syntax errors.
+    pub fn with_provided_path_buffer(mut self, path_buf: B) -> Self {
+        self.inner.path_buf = Some(path_buf);
+        self
+    }
+
+    /// Fail with a parse error at a key's location if it occurs twice in the same object, using
+    ///  an externally provided table to keep track of the keys of all open objects.
+    ///
+    /// The table needs 24 bytes per open object and per key read so far in an open object: it
+    ///  holds the 64-bit hash of each key, both in a stack and in a hash set for looking it up.
+    ///  [JsonParseError::BufferOverflow] signals that the table is too small.
+    ///
+    /// Keys are compared by their hashes, so there is a (tiny) probability of false positives,
+    ///  i.e. of two different keys being reported as duplicates. The hashes depend on `seed`, and
+    ///  an application that reads untrusted input should pass a random seed so that such keys
+    ///  can not be crafted deliberately.
+    pub fn with_provided_key_table(mut self, key_table: B, seed: u64) -> Self {
+        self.inner.key_table = Some(key_table);
+        self.inner.key_table_seed = seed;
+        self
+    }
+
+    /// Read from the reader a slice at a time through an externally provided read-ahead buffer,
+    ///  unless the reader provides slice-oriented access itself. This allows the [JsonReader] to
+    ///  scan whitespace, strings and numbers a slice at a time with any reader, but it means that
+    ///  the [JsonReader] can read past the end of a document.
+    ///
+    /// The constructors that allocate buffers on the heap allocate a read-ahead buffer as well.
+    pub fn with_provided_read_buffer(mut self, read_buf: B) -> Self {
+        self.reader.set_buf(read_buf);
+        self
+    }
+
+    /// Keep the most recently consumed bytes of input in an externally provided ring buffer, so
+    ///  that [JsonReader::error_report] can show the input around an error's location. The
+    ///  buffer's size is the number of bytes that are kept.
+    pub fn with_provided_context_buffer(mut self, context_buf: B) -> Self {
+        self.inner.context_buf = Some(context_buf);
+        self
+    }
+
+    /// Accept [JSON5](https://json5.org) syntax: comments, trailing commas, single-quoted strings,
+    ///  unquoted keys, hexadecimal numbers, `Infinity` and `NaN`, an explicit `+` sign and leading
+    ///  or trailing decimal points. This results in the same tokens as the equivalent JSON, so
+    ///  application code works unchanged.
+    ///
+    /// JSON5 numbers are converted to JSON syntax where possible, so they can be parsed with
+    ///  [JsonReader::expect_number] as usual: `0x1F` is returned as `31`, `+.5` as `0.5`, while
+    ///  `Infinity`, `-Infinity` and `NaN` are returned as they are (and can be parsed as floats).
+    ///
+    /// Unquoted keys are restricted to ASCII letters and digits, `_`, `$` and non-ASCII characters
+    ///  (without escapes), and only ASCII whitespace is supported.
+    pub fn with_json5(mut self) -> Self {
+        self.inner.json5 = true;
+        self.inner.allow_comments = true;
+        self
+    }
+
+    /// Treat `//` and `/* */` comments as whitespace, e.g. for reading JSONC configuration files
+    ///  (JSON with comments) like those of VS Code.
+    pub fn with_comments(mut self) -> Self {
+        self.inner.allow_comments = true;
+        self
+    }
+
+    /// Return comments from [JsonReader::next] as [JsonReadToken::Comment] tokens, including
+    ///  their delimiters, so that tools can inspect or preserve them. A comment must fit into the
+    ///  buffer.
+    ///
+    /// Comments after a string (or key) are returned after the string. They are read ahead
+    ///  together with the string to find out whether it is a key, so the string and these
+    ///  comments must fit into the buffer together.
+    ///
+    /// The `expect_*` and `skip_*` functions as well as [JsonReader::peek_kind] silently skip
+    ///  comments.
+    pub fn with_comment_tokens(mut self) -> Self {
+        self.inner.allow_comments = true;
+        self.inner.report_comments = true;
+        self
+    }
+
     /// Return the next JSON token. This is the work horse of [JsonReader] and the foundation for
     ///  other convenience abstraction.
     ///
-    /// The function does only limited checks of JSON grammar and basically returns whatever tokens
-    ///  it finds.
+    /// The function checks JSON grammar as far as it can be done based on the current token and the
+    ///  stack of open objects and arrays, e.g. it rejects missing commas, mismatched closing
+    ///  brackets, and keys outside of objects. It does not check that the stream contains only a
+    ///  single top-level value, or that all objects and arrays are closed at the end of the stream.
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
-        self.consume_whitespace()?;
+        if let Some((kind, bool_value)) = self.inner.parked_token.take() {
+            return self.inner.unpark_token(kind, bool_value);
+        }
+        if let Some(parked_comments) = self.inner.parked_comments {
+            return self.inner.unpark_comment(parked_comments);
+        }
 
-        match self.read_next_byte()? {
+        match self.read_token_start()? {
             None => {
                 Ok(JsonReadToken::EndOfStream)
             },
-            Some(b',') => {
-                self.inner.on_comma()?;
-                self.next()
-            }
             Some(b'{') => {
-                self.inner.ensure_accept_value()?;
-                self.inner.state = ReaderState::Initial;
+                self.inner.on_start_nested(ContainerKind::Object)?;
                 Ok(JsonReadToken::StartObject)
             },
             Some(b'}') => {
-                self.inner.ensure_accept_end_nested()?;
-                self.inner.state = ReaderState::AfterValue;
+                self.inner.on_end_nested(ContainerKind::Object)?;
                 Ok(JsonReadToken::EndObject)
             },
             Some(b'[') => {
-                self.inner.ensure_accept_value()?;
-                self.inner.state = ReaderState::Initial;
+                self.inner.on_start_nested(ContainerKind::Array)?;
                 Ok(JsonReadToken::StartArray)
             },
             Some(b']') => {
-                self.inner.ensure_accept_end_nested()?;
-                self.inner.state = ReaderState::AfterValue;
+                self.inner.on_end_nested(ContainerKind::Array)?;
                 Ok(JsonReadToken::EndArray)
             },
+            Some(b) if self.inner.json5 && self.inner.expects_key() && is_json5_identifier_start(b) => {
+                self.parse_json5_identifier_key(b)
+            },
 
             Some(b'n') => {
                 self.inner.state_change_for_value()?;
@@ -138,427 +370,280 @@ impl<'a, B: AsMut<[u8]>, R: BlockingRead> JsonReader<'a, B, R> {
                 self.consume_false_literal()
             },
 
-            Some(b'"') => self.parse_after_quote(), // key or string value based on following ':'
+            Some(b'/') if self.inner.report_comments => self.parse_comment(),
+            Some(b'"') => self.parse_after_quote(b'"'), // key or string value based on following ':'
+            Some(b'\'') if self.inner.json5 => self.parse_after_quote(b'\''),
             Some(b) => {
                 self.inner.state_change_for_value()?;
                 match b {
+                    b'-' | b'0'..=b'9' | b'+' | b'.' | b'I' | b'N' if self.inner.json5 => self.parse_json5_number_literal(b),
                     b'-' | b'0'..=b'9' => self.parse_number_literal(b),
-                    _ => self.parse_err("invalid JSON literal")
+                    _ => self.inner.parse_err(ParseErrorKind::InvalidLiteral)
                 }
             },
         }
     }
 
-    /// This is the function for the loop to read the members of a JSON object: It returns either
-    ///  a JSON key or `None` if it encounters the `}` that ends the object. All other tokens are
-    ///  invalid and cause the function to fail.
-    pub fn expect_key(&mut self) -> JsonParseResult<Option<&str>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::Key(key) => Ok(Some(key)),
-            JsonReadToken::EndObject => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Returns a JSON number as a [JsonNumber], and fails if the next token is anything other
-    ///  than a number. That includes the case that the next token is `null` - if the number
-    ///  is optional and `null` a valid value, use [JsonReader::expect_opt_raw_number] instead.
-    pub fn expect_raw_number(&mut self) -> JsonParseResult<JsonNumber<'_>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NumberLiteral(n) => Ok(n),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Same as [JsonReader::expect_raw_number], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_raw_number_or_end_array(&mut self) -> JsonParseResult<Option<JsonNumber<'_>>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NumberLiteral(n) => Ok(Some(n)),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Returns a [JsonNumber] if the next token is a JSON number, or `None` if the next token
-    ///  is `null`. All other tokens cause the function to fail.
-    pub fn expect_opt_raw_number(&mut self) -> JsonParseResult<Option<JsonNumber<'_>>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(None),
-            JsonReadToken::NumberLiteral(n) => Ok(Some(n)),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Same as [JsonReader::expect_opt_raw_number], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_opt_raw_number_or_end_array(&mut self) -> JsonParseResult<Option<Option<JsonNumber<'_>>>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(Some(None)),
-            JsonReadToken::NumberLiteral(n) => Ok(Some(Some(n))),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
+    /// Returns the kind of the next token without consuming it: The following call to `next()`
+    ///  or an `expect_*` function returns the token as usual. This allows application code to
+    ///  branch on the type of an upcoming value, e.g. to accept either an object or a string
+    ///  as shorthand for it, while still using the `expect_*` functions for reading the value.
+    ///
+    /// Peeking reads the entire token ahead, so a string must fit into the buffer, and
+    ///  [JsonReader::location] is the location after the peeked token. Comments are skipped.
+    pub fn peek_kind(&mut self) -> JsonParseResult<JsonReadTokenKind, R::Error> {
+        if let Some((kind, _)) = self.inner.parked_token {
+            return Ok(kind);
         }
-    }
-
-    /// Returns a number parsed to an application-provided type, failing if the next token is not
-    ///  a JSON number or the number is not parseable to the provided type (e.g. trying to
-    ///  get retrieve a floating point number as a u32).
+        loop {
+            let token = self.next()?;
+            if let JsonReadToken::Comment(_) = token {
+                continue;
+            }
+            let parked = (token.token_kind(), matches!(token, JsonReadToken::BooleanLiteral(true)));
+            self.inner.parked_token = Some(parked);
+            return Ok(parked.0);
+        }
+    }
+
+    /// Returns the next token other than a comment
+    fn next_non_comment(&mut self) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
+        if self.inner.report_comments {
+            // this skips comments, leaving the next token parked
+            self.peek_kind()?;
+        }
+        self.next()
+    }
+
+    /// If the path is tracked, this checks the kind of the next token before it is consumed by an
+    ///  `expect_*` function. That way, an error can contain the path of the unexpected token.
+    ///  Without path tracking, the `expect_*` functions check the token themselves.
+    fn check_next_kind(&mut self, expected: &'static [JsonReadTokenKind], location: Location) -> JsonParseResult<(), R::Error> {
+        if self.inner.path_buf.is_none() {
+            return Ok(());
+        }
+        let kind = self.peek_kind()?;
+        if expected.contains(&kind) {
+            return Ok(());
+        }
+        self.inner.parked_token = None;
+        self.inner.parse_err_at(ParseErrorKind::unexpected_token(expected, kind), location)
+    }
+
+    expect_functions!(JsonReader, '_, R::Error;
+        ///
+        /// The expected numeric type can be provided explicitly, like this:
+        /// ```
+        /// # use json_streaming::blocking::*;
+        /// # use json_streaming::shared::*;
+        /// # fn get_num() -> JsonParseResult<(), std::io::Error> {
+        /// # let buf = "123";
+        /// # let mut r = std::io::Cursor::new(buf);
+        /// # let mut json_reader = JsonReader::new(128, &mut r);
+        /// let n = json_reader.expect_number::<u32>()?;
+        /// # Ok(()) }
+        /// ```
+        /// or inferred by the compiler:
+        /// ```
+        /// # use json_streaming::blocking::*;
+        /// # use json_streaming::shared::*;
+        /// # fn get_num() -> JsonParseResult<(), std::io::Error> {
+        /// # let buf = "123";
+        /// # let mut r = std::io::Cursor::new(buf);
+        /// # let mut json_reader = JsonReader::new(128, &mut r);
+        /// let n:u32 = json_reader.expect_number()?;
+        /// # Ok(()) }
+        /// ```
+    );
+
+    /// Reads a string value of arbitrary length, passing its contents to a callback in chunks
+    ///  rather than returning it as a whole. This is for strings that can be too big to fit into
+    ///  the [JsonReader]'s buffer, e.g. embedded base64 payloads. All other tokens cause the function
+    ///  to fail.
     ///
-    /// The expected numeric type can be provided explicitly, like this:
-    /// ```
-    /// # use json_streaming::blocking::*;
-    /// # use json_streaming::shared::*;
-    /// # fn get_num() -> JsonParseResult<(), std::io::Error> {
-    /// # let buf = "123";
-    /// # let mut r = std::io::Cursor::new(buf);
-    /// # let mut json_reader = JsonReader::new(128, &mut r);
-    /// let n = json_reader.expect_number::<u32>()?;
-    /// # Ok(()) }
-    /// ```
-    /// or inferred by the compiler:
-    /// ```
-    /// # use json_streaming::blocking::*;
-    /// # use json_streaming::shared::*;
-    /// # fn get_num() -> JsonParseResult<(), std::io::Error> {
-    /// # let buf = "123";
-    /// # let mut r = std::io::Cursor::new(buf);
-    /// # let mut json_reader = JsonReader::new(128, &mut r);
-    /// let n:u32 = json_reader.expect_number()?;
-    /// # Ok(()) }
-    /// ```
-    pub fn expect_number<T: FromStr>(&mut self) -> JsonParseResult<T, R::Error> {
-        let n = self.expect_raw_number()?;
-        match n.parse::<T>() {
-            Ok(n) => Ok(n),
-            Err(_) => self.parse_err("invalid number"),
-        }
-    }
-
-    /// Same as [JsonReader::expect_number], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_number_or_end_array<T: FromStr>(&mut self) -> JsonParseResult<Option<T>, R::Error> {
-        match self.expect_raw_number_or_end_array()? {
-            None => Ok(None),
-            Some(n) => {
-                match n.parse::<T>() {
-                    Ok(n) => Ok(Some(n)),
-                    Err(_) => self.parse_err("invalid number"),
+    /// Each chunk is at most the size of the buffer, and the string is split only at UTF-8 character
+    ///  boundaries. Concatenating the chunks yields the (unescaped) string. The callback is not
+    ///  called for an empty string.
+    ///
+    /// Note that the buffer must be at least 8 bytes long for this function to work.
+    pub fn expect_string_chunked(&mut self, mut on_chunk: impl FnMut(&str)) -> JsonParseResult<(), R::Error> {
+        let location = self.location();
+        if self.inner.parked_token.is_some() {
+            // a string that was read ahead by 'peek_kind()' is in the buffer as a whole
+            return match self.next()? {
+                JsonReadToken::StringLiteral(s) => {
+                    if !s.is_empty() {
+                        on_chunk(s);
+                    }
+                    Ok(())
                 }
-            }
-        }
-    }
-
-    /// The same as [JsonReader::expect_number], but accepting a `null` literal which it
-    ///  returns as `None`.
-    pub fn expect_opt_number<T: FromStr>(&mut self) -> JsonParseResult<Option<T>, R::Error> {
-        match self.expect_opt_raw_number()? {
-            Some(n) => {
-                match n.parse::<T>() {
-                    Ok(n) => Ok(Some(n)),
-                    Err(_) => self.parse_err("invalid number"),
+                other => {
+                    let found = other.token_kind();
+                    self.inner.parse_err_at(ParseErrorKind::unexpected_token(&[JsonReadTokenKind::StringLiteral], found), location)
                 }
-            }
-            None => Ok(None),
+            };
         }
-    }
-
-    /// Same as [JsonReader::expect_opt_number], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_opt_number_or_end_array<T: FromStr>(&mut self) -> JsonParseResult<Option<Option<T>>, R::Error> {
-        match self.expect_opt_raw_number_or_end_array()? {
-            Some(Some(n)) => {
-                match n.parse::<T>() {
-                    Ok(n) => Ok(Some(Some(n))),
-                    Err(_) => self.parse_err("invalid number"),
+        let quote = loop {
+            match self.read_token_start()? {
+                Some(b'"') => break b'"',
+                Some(b'\'') if self.inner.json5 => break b'\'',
+                Some(b'/') if self.inner.report_comments => self.consume_comment(false)?,
+                other => {
+                    // let 'next()' parse the token to report its kind
+                    self.inner.parked_next = other;
+                    let found = self.next()?.token_kind();
+                    return self.inner.parse_err_at(ParseErrorKind::unexpected_token(&[JsonReadTokenKind::StringLiteral], found), location);
                 }
             }
-            Some(None) => Ok(Some(None)),
-            None => Ok(None),
-        }
-    }
-
-    /// If the next token is a string literal, return that, and fail for any other token.
-    pub fn expect_string(&mut self) -> JsonParseResult<&str, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::StringLiteral(s) => Ok(s),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Same as [JsonReader::expect_string], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_string_or_end_array(&mut self) -> JsonParseResult<Option<&str>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::StringLiteral(s) => Ok(Some(s)),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// The same as [JsonReader::expect_string], but accepting a `null` literal which is
-    ///  returned as `None`.
-    pub fn expect_opt_string(&mut self) -> JsonParseResult<Option<&str>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(None),
-            JsonReadToken::StringLiteral(s) => Ok(Some(s)),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Same as [JsonReader::expect_opt_string], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_opt_string_or_end_array(&mut self) -> JsonParseResult<Option<Option<&str>>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(Some(None)),
-            JsonReadToken::StringLiteral(s) => Ok(Some(Some(s))),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// `true` and `false` literals are returned as a boolean token, all other tokens cause the
-    ///  function to fail.
-    pub fn expect_bool(&mut self) -> JsonParseResult<bool, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::BooleanLiteral(b) => Ok(b),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Same as [JsonReader::expect_bool], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_bool_or_end_array(&mut self) -> JsonParseResult<Option<bool>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::BooleanLiteral(b) => Ok(Some(b)),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// The same as [JsonReader::expect_bool], but accepting a `null` literal which is
-    ///  returned as `None`.
-    pub fn expect_opt_bool(&mut self) -> JsonParseResult<Option<bool>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(None),
-            JsonReadToken::BooleanLiteral(b) => Ok(Some(b)),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Same as [JsonReader::expect_opt_bool], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_opt_bool_or_end_array(&mut self) -> JsonParseResult<Option<Option<bool>>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(Some(None)),
-            JsonReadToken::BooleanLiteral(b) => Ok(Some(Some(b))),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
-
-    /// Fails for any token except the `{` that starts an object.
-    pub fn expect_start_object(&mut self) -> JsonParseResult<(), R::Error> {
-        self.expect_single_token(JsonReadToken::StartObject)
-    }
-
-    /// Same as [JsonReader::start_object], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_start_object_or_end_array(&mut self) -> JsonParseResult<Option<()>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::StartObject => Ok(Some(())),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
-        }
-    }
+        };
 
-    /// The same as [JsonReader::expect_start_object], but accepting a `null` literal which is
-    ///  returned as `None`.
-    pub fn expect_opt_start_object(&mut self) -> JsonParseResult<Option<()>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(None),
-            JsonReadToken::StartObject => Ok(Some(())),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
+        if self.inner.buf.as_mut().len() < 8 {
+            return self.inner.buf_overflow();
         }
-    }
-
-    /// Same as [JsonReader::expect_opt_start_object], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_opt_start_object_or_end_array(&mut self) -> JsonParseResult<Option<Option<()>>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(Some(None)),
-            JsonReadToken::StartObject => Ok(Some(Some(()))),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
+        self.inner.state_change_for_value()?;
+        self.inner.ind_end_buf = 0;
+        self.inner.pending_high_surrogate = None;
+        while !self.read_string_literal(true, quote)? {
+            self.inner.flush_chunk(&mut on_chunk)?;
         }
-    }
-
-    /// Fails for any token except the `}` that ends an object.
-    pub fn expect_end_object(&mut self) -> JsonParseResult<(), R::Error> {
-        self.expect_single_token(JsonReadToken::EndObject)
-    }
-
-    /// Fails for any token except the `[` that starts an array.
-    pub fn expect_start_array(&mut self) -> JsonParseResult<(), R::Error> {
-        self.expect_single_token(JsonReadToken::StartArray)
-    }
-
-    /// Same as [JsonReader::expect_start_array], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_start_array_or_end_array(&mut self) -> JsonParseResult<Option<()>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::StartArray => Ok(Some(())),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
+        let rest = self.inner.buf_as_str()?;
+        if !rest.is_empty() {
+            on_chunk(rest);
         }
-    }
 
-    /// The same as [JsonReader::expect_start_array], but accepting a `null` literal which is
-    ///  returned as `None`.
-    pub fn expect_opt_start_array(&mut self) -> JsonParseResult<Option<()>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(None),
-            JsonReadToken::StartArray => Ok(Some(())),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
+        self.inner.ind_end_buf = 0;
+        self.consume_whitespace_after_string()?;
+        match self.read_next_byte()? {
+            Some(b':') => self.inner.parse_err(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::Key }),
+            other => {
+                self.inner.parked_next = other;
+                Ok(())
+            }
         }
     }
 
-    /// Same as [JsonReader::opt_start_array], except that it returns `Ok(None)` if it reaches the end of an
-    ///  array instead
-    pub fn expect_opt_start_array_or_end_array(&mut self) -> JsonParseResult<Option<Option<()>>, R::Error> {
-        let location = self.location();
-        let next = self.next()?;
-        match next {
-            JsonReadToken::NullLiteral => Ok(Some(None)),
-            JsonReadToken::StartArray => Ok(Some(Some(()))),
-            JsonReadToken::EndArray => Ok(None),
-            other => Err(JsonParseError::Parse(other.kind(), location)),
+    /// Skips whitespace and commas between tokens, returning the first byte of the next token.
+    fn read_token_start(&mut self) -> JsonParseResult<Option<u8>, R::Error> {
+        // comments after a string are skipped along with other comments if the string is not
+        //  read with 'next()'
+        self.inner.parked_comments = None;
+        loop {
+            self.consume_whitespace(self.inner.report_comments)?;
+            let next = self.read_next_byte()?;
+            if let Some(b) = next {
+                self.inner.check_record_line(b)?;
+            }
+            match next {
+                Some(b',') => self.inner.on_comma()?,
+                Some(RECORD_SEPARATOR) if self.inner.record_separators => return self.inner.truncated_record(),
+                other => return Ok(other),
+            }
         }
     }
 
-    /// Fails for any token except the `]` that ends an array.
-    pub fn expect_end_array(&mut self) -> JsonParseResult<(), R::Error> {
-        self.expect_single_token(JsonReadToken::EndArray)
-    }
-
-    fn expect_single_token(&mut self, expected: JsonReadToken) -> JsonParseResult<(), R::Error> {
-        let next = self.next()?;
-        if next != expected {
-            let kind = next.kind();
-            self.parse_err(kind)?;
+    /// Skips whitespace and (if enabled) comments. If `stop_at_comment` is set, a comment's
+    ///  initial `/` is parked instead, so it can be returned as a token.
+    fn consume_whitespace(&mut self, stop_at_comment: bool) -> JsonParseResult<(), R::Error> {
+        if self.inner.parked_next.is_none() {
+            loop {
+                let bytes = self.reader.fill_buf()?;
+                if bytes.is_empty() {
+                    // end of stream, or no support for slices: continue byte by byte
+                    break;
+                }
+                let len = self.inner.scan_whitespace(bytes);
+                let is_done = len < bytes.len();
+                let is_comment = is_done && self.inner.allow_comments && bytes[len] == b'/';
+                self.reader.consume(len);
+                if is_comment {
+                    // comments are handled byte by byte
+                    break;
+                }
+                if is_done {
+                    return Ok(());
+                }
+            }
         }
-        Ok(())
-    }
-
-    /// Fails if the stream contains any tokens - this is for checking that the entire JSON input
-    ///  was consumed
-    pub fn expect_end_of_stream(&mut self) -> JsonParseResult<(), R::Error> {
-        self.expect_single_token(JsonReadToken::EndOfStream)
-    }
 
-    /// This function assumes that it is called inside an object or array, and silently consumes
-    ///  all tokens until and including the closing `}` or `]`.
-    ///
-    /// This function is useful for gracefully ignoring array elements with an unsupported type. See
-    ///  the `skipping.rs` example for details.
-    pub fn skip_to_end_of_current_scope(&mut self) -> JsonParseResult<(), R::Error> {
-        let mut nesting_level = 1;
-        loop {
-            match self.next()? {
-                JsonReadToken::StartObject | JsonReadToken::StartArray => {
-                    nesting_level += 1;
+        while let Some(next) = self.read_next_byte()? {
+            match next {
+                b' ' | b'\t' | b'\n' | b'\r' => {
                 }
-                JsonReadToken::EndObject | JsonReadToken::EndArray=> {
-                    nesting_level -= 1;
-                    if nesting_level == 0 {
-                        break;
-                    }
+                0x0b | 0x0c if self.inner.json5 => {
                 }
-                JsonReadToken::EndOfStream => {
-                    return Err(JsonParseError::Parse(JsonReadToken::EndOfStream.kind(), self.location()));
+                b'/' if self.inner.allow_comments && !stop_at_comment => {
+                    self.consume_comment(false)?;
                 }
-                _ => {
-                    continue;
+                next => {
+                    self.inner.parked_next = Some(next);
+                    break;
                 }
             }
         }
         Ok(())
     }
 
-    /// This function skips the value starting with the next token. This can be a single-token value,
-    ///  but it can also be an object or array of unknown structure and nesting depth, in which
-    ///  case this function silently consumes tokens until it reaches the matching closing `}` or
-    ///  `]`.
-    ///
-    /// This function is useful for gracefully ignoring object members with an unknown key - see
-    ///  the `skipping.rs` example for details.
-    pub fn skip_value(&mut self) -> JsonParseResult<(), R::Error> {
-        match self.next()? {
-            JsonReadToken::Key(_) |
-            JsonReadToken::EndObject |
-            JsonReadToken::EndArray |
-            JsonReadToken::EndOfStream => {
-                Err(JsonParseError::Parse(JsonReadToken::EndOfStream.kind(), self.location()))
-            }
-            JsonReadToken::StartObject |
-            JsonReadToken::StartArray => {
-                self.skip_to_end_of_current_scope()
-            }
-            JsonReadToken::StringLiteral(_) |
-            JsonReadToken::NumberLiteral(_) |
-            JsonReadToken::BooleanLiteral(_) |
-            JsonReadToken::NullLiteral => {
-                Ok(())
+    /// Skips whitespace and comments after a string or a JSON5 identifier that may turn out to be
+    ///  a key. If comments are returned as tokens, the comments are appended to the buffer behind
+    ///  the string's contents and parked, so that they can be returned after the string.
+    fn consume_whitespace_after_string(&mut self) -> JsonParseResult<(), R::Error> {
+        if !self.inner.report_comments {
+            return self.consume_whitespace(false);
+        }
+        let string_end = self.inner.ind_end_buf;
+        loop {
+            self.consume_whitespace(true)?;
+            if self.inner.parked_next != Some(b'/') {
+                break;
             }
+            self.inner.parked_next = None;
+            let len_pos = self.inner.start_parked_comment()?;
+            self.consume_comment(true)?;
+            self.inner.end_parked_comment(len_pos);
+        }
+        self.inner.park_comments(string_end);
+        Ok(())
+    }
+
+    fn parse_comment(&mut self) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
+        self.inner.ind_end_buf = 0;
+        self.consume_comment(true)?;
+        if self.inner.buf.as_mut()[..self.inner.ind_end_buf].ends_with(b"\r") {
+            // a line comment ending with "\r\n"
+            self.inner.ind_end_buf -= 1;
         }
+        Ok(JsonReadToken::Comment(self.inner.buf_as_str()?))
     }
 
-    fn consume_whitespace(&mut self) -> JsonParseResult<(), R::Error> {
+    /// Consumes a comment after its initial `/`, either skipping it or appending it to the buffer
+    ///  including its delimiters. A line comment's terminating newline is consumed, but it is not
+    ///  part of the comment.
+    fn consume_comment(&mut self, into_buf: bool) -> JsonParseResult<(), R::Error> {
+        let is_block = match self.read_next_byte()? {
+            Some(b'/') => false,
+            Some(b'*') => true,
+            _ => return self.inner.parse_err(ParseErrorKind::InvalidComment),
+        };
+        if into_buf {
+            self.inner.append_to_buf(b'/')?;
+            self.inner.append_to_buf(if is_block { b'*' } else { b'/' })?;
+        }
+
+        let mut prev = 0u8;
         while let Some(next) = self.read_next_byte()? {
-            match next {
-                b' ' | b'\t' | b'\n' | b'\r' => {
-                }
-                next => {
-                    self.inner.parked_next = Some(next);
-                    break;
-                }
+            if !is_block && next == b'\n' {
+                return Ok(());
+            }
+            if into_buf {
+                self.inner.append_to_buf(next)?;
             }
+            if is_block && prev == b'*' && next == b'/' {
+                return Ok(());
+            }
+            prev = next;
+        }
+        if is_block {
+            return self.inner.parse_err(ParseErrorKind::UnterminatedComment);
         }
         Ok(())
     }
@@ -570,7 +655,7 @@ impl<'a, B: AsMut<[u8]>, R: BlockingRead> JsonReader<'a, B, R> {
         }
 
         if let Some(byte) =self.reader.read()? {
-            self.inner.cur_location.after_byte(byte);
+            self.inner.on_consumed_byte(byte);
             Ok(Some(byte))
         }
         else {
@@ -580,55 +665,102 @@ impl<'a, B: AsMut<[u8]>, R: BlockingRead> JsonReader<'a, B, R> {
 
     fn consume_null_literal(&mut self) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
         if self.read_next_byte()? != Some(b'u') {
-            return self.parse_err("incomplete null literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("null"));
         }
         if self.read_next_byte()? != Some(b'l') {
-            return self.parse_err("incomplete null literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("null"));
         }
         if self.read_next_byte()? != Some(b'l') {
-            return self.parse_err("incomplete null literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("null"));
         }
         Ok(JsonReadToken::NullLiteral)
     }
 
     fn consume_true_literal(&mut self) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
         if self.read_next_byte()? != Some(b'r') {
-            return self.parse_err("incomplete true literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("true"));
         }
         if self.read_next_byte()? != Some(b'u') {
-            return self.parse_err("incomplete true literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("true"));
         }
         if self.read_next_byte()? != Some(b'e') {
-            return self.parse_err("incomplete true literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("true"));
         }
         Ok(JsonReadToken::BooleanLiteral(true))
     }
 
     fn consume_false_literal(&mut self) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
         if self.read_next_byte()? != Some(b'a') {
-            return self.parse_err("incomplete false literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("false"));
         }
         if self.read_next_byte()? != Some(b'l') {
-            return self.parse_err("incomplete false literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("false"));
         }
         if self.read_next_byte()? != Some(b's') {
-            return self.parse_err("incomplete false literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("false"));
         }
         if self.read_next_byte()? != Some(b'e') {
-            return self.parse_err("incomplete false literal");
+            return self.inner.parse_err(ParseErrorKind::IncompleteLiteral("false"));
         }
         Ok(JsonReadToken::BooleanLiteral(false))
     }
 
-    fn parse_after_quote(&mut self) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
+    fn parse_after_quote(&mut self, quote: u8) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
+        let location = self.inner.token_start_location();
         self.inner.ind_end_buf = 0;
+        self.inner.pending_high_surrogate = None;
+        self.read_string_literal(false, quote)?;
 
+        // the buffer contains the string's contents - the next character determines whether this
+        //  is key or a string value. Recall that we don't check for valid JSON.
+
+        self.consume_whitespace_after_string()?;
+        match self.read_next_byte()? {
+            Some(b':') => {
+                self.inner.on_key()?;
+                self.inner.check_duplicate_key(location)?;
+                Ok(JsonReadToken::Key(self.inner.buf_as_str()?))
+            },
+            other => {
+                self.inner.state_change_for_value()?;
+                self.inner.parked_next = other;
+                Ok(JsonReadToken::StringLiteral(self.inner.buf_as_str()?))
+            }
+        }
+    }
+
+    /// Reads the contents of a string literal into the buffer, consuming the closing quote. In
+    ///  chunked mode, it returns `Ok(false)` when the buffer is full, giving the caller a chance
+    ///  to hand the buffer's contents to the application and continue. Otherwise it returns
+    ///  `Ok(true)` after reaching the end of the string.
+    fn read_string_literal(&mut self, chunked: bool, quote: u8) -> JsonParseResult<bool, R::Error> {
+        self.inner.in_string = Some(quote);
         loop {
+            if chunked && self.inner.buf.as_mut().len() - self.inner.ind_end_buf < 4 {
+                // room for at least one more code point's UTF-8 representation
+                return Ok(false);
+            }
+            if self.inner.parked_next.is_none() {
+                let bytes = self.reader.fill_buf()?;
+                let len = self.inner.scan_string_run(bytes, quote);
+                if len > 0 {
+                    self.reader.consume(len);
+                    continue;
+                }
+            }
             if let Some(next) = self.read_next_byte()? {
                 match next {
-                    b'"' => break,
+                    b if b == quote => {
+                        self.inner.in_string = None;
+                        self.inner.end_utf16_sequence()?;
+                        return Ok(true);
+                    }
                     b'\\' => {
-                        match self.read_next_byte()? {
+                        let escaped = self.read_next_byte()?;
+                        if escaped != Some(b'u') {
+                            self.inner.end_utf16_sequence()?;
+                        }
+                        match escaped {
                             Some(b'"') => self.inner.append_to_buf(b'"')?,
                             Some(b'\\') => self.inner.append_to_buf(b'\\')?,
                             Some(b'/') => self.inner.append_to_buf(b'/')?,
@@ -638,47 +770,58 @@ impl<'a, B: AsMut<[u8]>, R: BlockingRead> JsonReader<'a, B, R> {
                             Some(b'r') => self.inner.append_to_buf(b'\r')?,
                             Some(b't') => self.inner.append_to_buf(b'\t')?,
                             Some(b'u') => {
-                                let cp = self.parse_unicode_codepoint()?;
-                                self.inner.append_code_point(cp)?;
+                                let unit = self.parse_unicode_codepoint()?;
+                                self.inner.append_utf16_code_unit(unit)?;
                             },
-                            _ => return self.parse_err("invalid escape in string literal"),
+                            Some(escaped) if self.inner.json5 => self.parse_json5_escape(escaped)?,
+                            _ => return self.inner.parse_err(ParseErrorKind::InvalidEscape),
                         }
                     },
+                    RECORD_SEPARATOR if self.inner.record_separators => {
+                        return self.inner.truncated_record();
+                    }
+                    ch if ch < 0x20 && self.inner.reject_control_characters => {
+                        return self.inner.parse_err(ParseErrorKind::ControlCharacterInString);
+                    }
                     ch => {
+                        self.inner.end_utf16_sequence()?;
                         self.inner.append_to_buf(ch)?;
                     }
                 }
             }
             else {
-                return self.parse_err("unterminated string literal");
+                return self.inner.parse_err(ParseErrorKind::UnterminatedString);
             }
         }
+    }
 
-        // the buffer contains the string's contents - the next character determines whether this
-        //  is key or a string value. Recall that we don't check for valid JSON.
-
-        self.consume_whitespace()?;
-        match self.read_next_byte()? {
-            Some(b':') => {
-                match self.inner.state {
-                    ReaderState::Initial |
-                    ReaderState::BeforeEntry => {
-                        self.inner.state = ReaderState::AfterKey;
-                    }
-                    ReaderState::AfterKey => {
-                        return self.parse_err("two keys without value");
-                    }
-                    ReaderState::AfterValue => {
-                        return self.parse_err("missing comma");
+    /// Handles the escapes that JSON5 adds to JSON: `\'`, `\v`, `\0`, `\x` with two hex digits,
+    ///  line continuations, and other characters representing themselves
+    fn parse_json5_escape(&mut self, escaped: u8) -> JsonParseResult<(), R::Error> {
+        match escaped {
+            b'v' => self.inner.append_to_buf(0x0b),
+            b'0' => self.inner.append_to_buf(0),
+            b'x' => {
+                let mut cp = 0u32;
+                for _ in 0..2 {
+                    match self.read_next_byte()? {
+                        Some(b) if b.is_ascii_hexdigit() => cp = (cp << 4) + (b as char).to_digit(16).unwrap_or_default(),
+                        _ => return self.inner.parse_err(ParseErrorKind::InvalidHexEscape),
                     }
                 }
-                Ok(JsonReadToken::Key(self.inner.buf_as_str()?))
-            },
-            other => {
-                self.inner.state_change_for_value()?;
-                self.inner.parked_next = other;
-                Ok(JsonReadToken::StringLiteral(self.inner.buf_as_str()?))
+                self.inner.append_code_point(cp)
             }
+            b'\n' => Ok(()),
+            b'\r' => {
+                // line continuation, possibly with "\r\n"
+                match self.read_next_byte()? {
+                    Some(b'\n') => {}
+                    other => self.inner.parked_next = other,
+                }
+                Ok(())
+            }
+            b'1'..=b'9' => self.inner.parse_err(ParseErrorKind::InvalidEscape),
+            other => self.inner.append_to_buf(other),
         }
     }
 
@@ -687,46 +830,103 @@ impl<'a, B: AsMut<[u8]>, R: BlockingRead> JsonReader<'a, B, R> {
         let mut cp: u16 = 0;
         for _ in 0..4 {
             if let Some(b) = self.read_next_byte()? {
-                cp = cp << 4;
+                cp <<= 4;
                 match b {
                     b'0'..=b'9' => cp += (b - b'0') as u16,
                     b'a'..=b'f' => cp += (b - b'a' + 10) as u16,
-                    b'A'..=b'Z' => cp += (b - b'A' + 10) as u16,
+                    b'A'..=b'F' => cp += (b - b'A' + 10) as u16,
                     _ => {
-                        return self.parse_err("not a four-digit hex number after \\u");
+                        return self.inner.parse_err(ParseErrorKind::InvalidUnicodeEscape);
                     }
                 }
             }
             else {
-                return self.parse_err("incomplete UTF codepoint in string literal");
+                return self.inner.parse_err(ParseErrorKind::IncompleteUtf8Sequence);
             }
         }
         Ok(cp)
     }
 
     fn parse_number_literal(&mut self, b: u8) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
+        let location = self.inner.token_start_location();
+        let mut state = match NumberState::start(b) {
+            Some(state) => state,
+            None => return self.inner.parse_err(ParseErrorKind::InvalidLiteral),
+        };
         self.inner.buf.as_mut()[0] = b;
         self.inner.ind_end_buf = 1;
 
-        while let Some(next) = self.read_next_byte()? {
-            match next {
-                b'0'..=b'9' |
-                b'+' | b'-' | b'e' | b'E' |
-                b'.' => {
-                    self.inner.append_to_buf(next)?;
-                }
-                other => {
-                    self.inner.parked_next = Some(other);
+        let mut is_end = false;
+        while !is_end {
+            let bytes = self.reader.fill_buf()?;
+            if bytes.is_empty() {
+                break;
+            }
+            let (len, ends_in_slice) = self.inner.scan_number_run(&mut state, bytes)?;
+            self.reader.consume(len);
+            is_end = ends_in_slice;
+        }
+        if !is_end {
+            while let Some(next) = self.read_next_byte()? {
+                if !NumberState::is_number_byte(next) {
+                    self.inner.parked_next = Some(next);
                     break;
                 }
+                match state.next(next) {
+                    Some(s) => state = s,
+                    None => return self.inner.parse_err(ParseErrorKind::InvalidNumberLiteral),
+                }
+                self.inner.append_to_buf(next)?;
+            }
+        }
+        if !state.is_complete() {
+            return self.inner.parse_err(ParseErrorKind::IncompleteNumberLiteral);
+        }
+        self.inner.check_top_level_number_end(location)?;
+        Ok(JsonReadToken::NumberLiteral(JsonNumber(self.inner.buf_as_str()?)))
+    }
+
+    fn parse_json5_identifier_key(&mut self, b: u8) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
+        let location = self.inner.token_start_location();
+        self.inner.ind_end_buf = 0;
+        self.inner.append_to_buf(b)?;
+        while let Some(next) = self.read_next_byte()? {
+            if !is_json5_identifier_part(next) {
+                self.inner.parked_next = Some(next);
+                break;
+            }
+            self.inner.append_to_buf(next)?;
+        }
+
+        self.consume_whitespace_after_string()?;
+        if self.read_next_byte()? != Some(b':') {
+            return self.inner.parse_err(ParseErrorKind::MissingColon);
+        }
+        self.inner.on_key()?;
+        self.inner.check_duplicate_key(location)?;
+        Ok(JsonReadToken::Key(self.inner.buf_as_str()?))
+    }
+
+    fn parse_json5_number_literal(&mut self, b: u8) -> JsonParseResult<JsonReadToken<'_>, R::Error> {
+        let location = self.inner.token_start_location();
+        self.inner.ind_end_buf = 0;
+        self.inner.append_to_buf(b)?;
+        while let Some(next) = self.read_next_byte()? {
+            if !is_json5_number_byte(next) {
+                self.inner.parked_next = Some(next);
+                break;
             }
+            self.inner.append_to_buf(next)?;
         }
-        Ok(JsonReadToken::NumberLiteral(JsonNumber(self.inner.buf_as_str().unwrap())))
+        self.inner.finish_json5_number()?;
+        self.inner.check_top_level_number_end(location)?;
+        Ok(JsonReadToken::NumberLiteral(JsonNumber(self.inner.buf_as_str()?)))
     }
 
-    /// convenience function for creating a parse error with the current location
-    pub fn parse_err<T>(&self, msg: &'static str) -> JsonParseResult<T, R::Error> {
-        self.inner.parse_err(msg)
+    /// convenience function for creating a parse error with the current location and an
+    ///  application specific message, see [ParseErrorKind::Custom]
+    pub fn parse_err<T>(&mut self, msg: &'static str) -> JsonParseResult<T, R::Error> {
+        self.inner.parse_err(ParseErrorKind::Custom(msg))
     }
 
     /// Returns the current parse location in the underlying reader - offset, row and column.
@@ -734,12 +934,281 @@ impl<'a, B: AsMut<[u8]>, R: BlockingRead> JsonReader<'a, B, R> {
     pub fn location(&self) -> Location {
         self.inner.cur_location
     }
+
+    /// Returns the number of objects and arrays that were started but not ended yet, i.e. `0` at
+    ///  the top level of the stream.
+    pub fn depth(&self) -> usize {
+        self.inner.depth
+    }
+
+    /// Returns the kind of the innermost object or array that was started but not ended yet, or
+    ///  `None` at the top level of the stream.
+    pub fn current_container(&self) -> Option<ContainerKind> {
+        self.inner.current_container
+    }
+
+    /// Returns the JSON path of the most recent token as a JSON Pointer, e.g.
+    ///  `/orders/17/items/3/price`, or `None` if path tracking is not enabled. A key's path
+    ///  includes the key itself, and the path of a `}` or `]` is that of the object or array
+    ///  it ends.
+    pub fn current_path(&mut self) -> Option<JsonPath<'_>> {
+        self.inner.current_path()
+    }
+
+    /// Returns a [JsonErrorReport] for an error that this reader returned, which renders it
+    ///  together with the line of input around its location (if the reader keeps a context
+    ///  buffer) and the current JSON path (if the reader tracks it).
+    ///
+    /// ```
+    /// # use json_streaming::blocking::*;
+    /// # use json_streaming::shared::*;
+    /// let mut r = std::io::Cursor::new("{\n  \"a\": [1, 2, 3 4]\n}");
+    /// let mut json_reader = JsonReader::new(64, &mut r)
+    ///     .with_error_context(64)
+    ///     .with_path_tracking(64);
+    /// let error = json_reader.skip_value().unwrap_err();
+    /// assert_eq!(json_reader.error_report(&error).to_string(), r#"parse error: missing comma @ line 2, column 18 (offset 19) (path /a/2)
+    ///   "a": [1, 2, 3 4
+    ///                  ^"#);
+    /// ```
+    pub fn error_report<'e>(&'e mut self, error: &'e JsonParseError<R::Error>) -> JsonErrorReport<'e, R::Error> {
+        self.inner.error_report(error)
+    }
+
+    /// Captures the reader's state as a [ReaderCheckpoint], so that reading can be resumed later
+    ///  with [JsonReader::new_from_checkpoint_with_provided_buffer], e.g. after a crash. Returns
+    ///  `None` if a token was peeked but not read yet.
+    ///
+    /// The checkpoint's location is right after the last byte that the reader consumed. A byte
+    ///  that the reader read ahead while looking for the end of a token is stored in the
+    ///  checkpoint, so the input is not read again from an earlier position.
+    pub fn checkpoint(&self) -> Option<ReaderCheckpoint<S>> where S: Clone {
+        self.inner.checkpoint()
+    }
+
+    /// Reads a top-level array, recording the location at which each of its elements starts in a
+    ///  [JsonArrayIndex], see [JsonReader::new_at_array_element]. The elements are skipped without
+    ///  being checked beyond the grammar.
+    #[cfg(feature = "std")]
+    pub fn index_array_elements(&mut self) -> JsonParseResult<JsonArrayIndex, R::Error> {
+        self.expect_start_array()?;
+        let mut locations = Vec::new();
+        while let Some(location) = self.next_entry_location()? {
+            locations.push(location);
+            self.skip_value()?;
+        }
+        self.expect_end_array()?;
+        Ok(JsonArrayIndex::from_locations(locations))
+    }
+
+    /// Consumes whitespace and a separating comma before the current container's next entry, and
+    ///  returns the location at which the entry starts, or `None` at the end of the container or
+    ///  the stream
+    #[cfg(feature = "std")]
+    fn next_entry_location(&mut self) -> JsonParseResult<Option<Location>, R::Error> {
+        while self.skip_whitespace()? {
+            match self.inner.parked_next {
+                Some(b',') => {
+                    self.inner.parked_next = None;
+                    self.inner.on_comma()?;
+                }
+                Some(b']' | b'}') => return Ok(None),
+                _ => return Ok(Some(self.inner.token_start_location())),
+            }
+        }
+        Ok(None)
+    }
+
+    pub(crate) fn enable_control_character_check(&mut self) {
+        self.inner.reject_control_characters = true;
+    }
+
+    /// Skips whitespace (and comments if they are enabled) before the next token, so that
+    ///  [JsonReader::location] is on the token's line. Returns `false` at the end of the stream.
+    pub(crate) fn skip_whitespace(&mut self) -> JsonParseResult<bool, R::Error> {
+        if self.inner.parked_token.is_some() {
+            return Ok(true);
+        }
+        self.consume_whitespace(false)?;
+        if self.inner.parked_next.is_none() {
+            self.inner.parked_next = self.read_next_byte()?;
+        }
+        Ok(self.inner.parked_next.is_some())
+    }
+
+    /// Prepares for another top-level value after one was read completely. The grammar checks
+    ///  would otherwise reject it as a missing comma.
+    pub(crate) fn expect_next_top_level_value(&mut self) {
+        if self.inner.depth == 0 {
+            self.inner.state = ReaderState::Initial;
+        }
+    }
+
+    /// Treats the ASCII record separator as the end of a record, see `ReaderInner::record_separators`
+    pub(crate) fn set_record_separators(&mut self, record_separators: bool) {
+        self.inner.record_separators = record_separators;
+        self.inner.consumed_record_separator = false;
+    }
+
+    /// Returns `true` if a top-level value was read completely
+    pub(crate) fn is_after_top_level_value(&self) -> bool {
+        self.inner.depth == 0 && self.inner.state == ReaderState::AfterValue && self.inner.parked_token.is_none()
+    }
+
+    /// Skips whitespace and a record separator after it. Returns `false` if the next byte is not
+    ///  a record separator, or at the end of the stream.
+    pub(crate) fn skip_record_separator(&mut self) -> JsonParseResult<bool, R::Error> {
+        if self.skip_whitespace()? && self.inner.parked_next == Some(RECORD_SEPARATOR) {
+            self.inner.parked_next = None;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Skips input up to the next record separator of a JSON text sequence, which is parked so
+    ///  that the next record can start with it, and discards all open objects and arrays. Returns
+    ///  `false` if the stream ends before the next record separator.
+    pub(crate) fn skip_to_record_separator(&mut self) -> JsonParseResult<bool, R::Error> {
+        self.inner.reset_to_top_level();
+        if self.inner.parked_next.is_none() && self.inner.consumed_record_separator {
+            // a record separator that cut a literal or an escape short
+            self.inner.parked_next = Some(RECORD_SEPARATOR);
+        }
+        loop {
+            match self.read_next_byte()? {
+                None => return Ok(false),
+                Some(RECORD_SEPARATOR) => {
+                    self.inner.parked_next = Some(RECORD_SEPARATOR);
+                    return Ok(true);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Discards all open objects and arrays, e.g. for a JSON Lines record that is cut short
+    pub(crate) fn reset_to_top_level(&mut self) {
+        self.inner.reset_to_top_level();
+    }
+
+    /// Restricts the tokens inside objects and arrays to a given line, see `ReaderInner::record_line`
+    pub(crate) fn set_record_line(&mut self, record_line: Option<usize>) {
+        self.inner.record_line = record_line;
+    }
+
+    /// Returns the location of the first byte of the next token after [JsonReader::skip_whitespace]
+    ///  returned `true`
+    pub(crate) fn token_start_location(&self) -> Location {
+        self.inner.token_start_location()
+    }
+
+    /// Returns the kind of the next token if it is a `{`, `}`, `[` or `]` (or the kind of a token
+    ///  that was already peeked), skipping comments. Unlike [JsonReader::peek_kind], this does not
+    ///  read other tokens ahead, so a string that does not fit into the buffer can still be read
+    ///  with [JsonReader::expect_string_chunked] afterwards.
+    pub(crate) fn peek_structural_kind(&mut self) -> JsonParseResult<Option<JsonReadTokenKind>, R::Error> {
+        if let Some((kind, _)) = self.inner.parked_token {
+            return Ok(Some(kind));
+        }
+        loop {
+            let next = self.read_token_start()?;
+            if next == Some(b'/') && self.inner.report_comments {
+                self.consume_comment(false)?;
+                continue;
+            }
+            self.inner.parked_next = next;
+            return Ok(match next {
+                Some(b'{') => Some(JsonReadTokenKind::StartObject),
+                Some(b'}') => Some(JsonReadTokenKind::EndObject),
+                Some(b'[') => Some(JsonReadTokenKind::StartArray),
+                Some(b']') => Some(JsonReadTokenKind::EndArray),
+                _ => None,
+            });
+        }
+    }
+
+    /// Returns the nesting depth before the next token, which is lower than [JsonReader::depth]
+    ///  if [JsonReader::peek_kind] read ahead an opening `{` or `[`
+    #[cfg(feature = "serde")]
+    pub(crate) fn depth_before_next_token(&self) -> usize {
+        match self.inner.parked_token {
+            Some((JsonReadTokenKind::StartObject | JsonReadTokenKind::StartArray, _)) => self.inner.depth - 1,
+            _ => self.inner.depth,
+        }
+    }
+
+    /// Skips the rest of the current line unless the reader is at the start of a line, and
+    ///  discards all open objects and arrays. Returns `false` if the stream ends before the end
+    ///  of the line.
+    pub(crate) fn skip_to_next_line(&mut self) -> JsonParseResult<bool, R::Error> {
+        let is_at_line_start = self.inner.parked_next.is_none() && self.inner.cur_location.column == 1;
+        if !is_at_line_start {
+            loop {
+                match self.read_next_byte()? {
+                    None => return Ok(false),
+                    Some(b'\n') => break,
+                    Some(_) => {}
+                }
+            }
+        }
+        self.inner.reset_to_top_level();
+        Ok(true)
+    }
+
+    /// Skips input after an error up to the next structural boundary so that reading can
+    ///  continue there: For JSON Lines, this is the start of the next line, and all open objects
+    ///  and arrays are discarded. Otherwise it is the current container's next `,`, `}` or `]`,
+    ///  skipping strings and nested objects or arrays. Returns `false` if the stream ends before
+    ///  such a boundary.
+    pub(crate) fn resync(&mut self, error: &JsonParseError<R::Error>, json_lines: bool) -> JsonParseResult<bool, R::Error> {
+        self.inner.recover_from(error);
+        let mut quote = self.inner.in_string.take();
+
+        if json_lines {
+            return self.skip_to_next_line();
+        }
+
+        let mut nesting = 0usize;
+        let mut is_escaped = false;
+        while let Some(b) = self.read_next_byte()? {
+            if let Some(q) = quote {
+                if is_escaped {
+                    is_escaped = false;
+                }
+                else if b == b'\\' {
+                    is_escaped = true;
+                }
+                else if b == q {
+                    quote = None;
+                }
+                continue;
+            }
+            match b {
+                b'"' => quote = Some(b),
+                b'{' | b'[' => nesting += 1,
+                b'}' | b']' if nesting > 0 => nesting -= 1,
+                b'}' | b']' if self.inner.depth > 0 => {
+                    self.inner.parked_next = Some(b);
+                    self.inner.state = ReaderState::AfterValue;
+                    return Ok(true);
+                }
+                b',' if nesting == 0 && self.inner.depth > 0 => {
+                    self.inner.state = ReaderState::BeforeEntry;
+                    return Ok(true);
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::unit_cmp)]
 mod tests {
     use std::io;
     use super::*;
+    use crate::blocking::BufferedRead;
     use rstest::*;
     use std::io::{Cursor, Read};
 
@@ -756,8 +1225,9 @@ mod tests {
                     return;
                 }
             }
-            JsonParseError::Parse(_, _) => {
-                if let JsonParseError::Parse(_, _) = expected {
+            JsonParseError::Parse(kind, _, _) => {
+                if let JsonParseError::Parse(expected_kind, _, _) = expected {
+                    assert_eq!(kind, expected_kind);
                     return;
                 }
             }
@@ -766,6 +1236,11 @@ mod tests {
                     return;
                 }
             }
+            JsonParseError::MaxNestingDepthExceeded(_) => {
+                if let JsonParseError::MaxNestingDepthExceeded(_) = expected {
+                    return;
+                }
+            }
         }
 
         panic!("{:?} != {:?}", actual, expected);
@@ -783,10 +1258,10 @@ mod tests {
     #[case::start_array("[", vec![JsonReadToken::StartArray], None)]
     #[case::end_array("[]", vec![JsonReadToken::StartArray, JsonReadToken::EndArray], None)]
 
-    #[case::key("\"xyz\":", vec![JsonReadToken::Key("xyz")], None)]
-    #[case::key_with_escapes("\"x\\ry\\nz\":", vec![JsonReadToken::Key("x\ry\nz")], None)]
-    #[case::key_ws("\"xyz\" \n:", vec![JsonReadToken::Key("xyz")], None)]
-    #[case::key_value("\"xyz\" \n:\r\tfalse", vec![JsonReadToken::Key("xyz"), JsonReadToken::BooleanLiteral(false)], None)]
+    #[case::key("{\"xyz\":", vec![JsonReadToken::StartObject, JsonReadToken::Key("xyz")], None)]
+    #[case::key_with_escapes("{\"x\\ry\\nz\":", vec![JsonReadToken::StartObject, JsonReadToken::Key("x\ry\nz")], None)]
+    #[case::key_ws("{\"xyz\" \n:", vec![JsonReadToken::StartObject, JsonReadToken::Key("xyz")], None)]
+    #[case::key_value("{\"xyz\" \n:\r\tfalse", vec![JsonReadToken::StartObject, JsonReadToken::Key("xyz"), JsonReadToken::BooleanLiteral(false)], None)]
 
     #[case::string_literal(r#""abc""#, vec![JsonReadToken::StringLiteral("abc")], None)]
     #[case::string_literal_empty(r#""""#, vec![JsonReadToken::StringLiteral("")], None)]
@@ -817,76 +1292,131 @@ mod tests {
     #[case::number_literal_neg_exp_lower("0.92e-4", vec![JsonReadToken::NumberLiteral(JsonNumber("0.92e-4"))], None)]
     #[case::number_literal_neg_exp_upper("0.92E-6", vec![JsonReadToken::NumberLiteral(JsonNumber("0.92E-6"))], None)]
 
-    #[case::number_literal_no_leading_zero(".1", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-    #[case::no_matching_literal("x", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-    #[case::invalid_number_continuation("1x", vec![JsonReadToken::NumberLiteral(JsonNumber("1"))], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::invalid_number_continuation_quote("x\"", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-
-    #[case::string_literal_unterminated_short(r#""abc "#, vec![], Some(JsonParseError::Parse("unterminated string literal", Location::start())))]
+    #[case::number_literal_neg_zero("-0", vec![JsonReadToken::NumberLiteral(JsonNumber("-0"))], None)]
+    #[case::number_literal_zero_fraction("0.5", vec![JsonReadToken::NumberLiteral(JsonNumber("0.5"))], None)]
+    #[case::number_literal_zero_exp("0e5", vec![JsonReadToken::NumberLiteral(JsonNumber("0e5"))], None)]
+    #[case::number_literal_int_exp("-12E-05", vec![JsonReadToken::NumberLiteral(JsonNumber("-12E-05"))], None)]
+    #[case::number_literal_in_array("[1,-2.5e3]", vec![JsonReadToken::StartArray, JsonReadToken::NumberLiteral(JsonNumber("1")), JsonReadToken::NumberLiteral(JsonNumber("-2.5e3")), JsonReadToken::EndArray], None)]
+    #[case::number_literal_minus_in_int("1-2", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_leading_zero("01", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_neg_leading_zero("-01", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_double_minus("--3", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_plus_sign("+3", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+    #[case::number_literal_only_minus("-", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_minus_dot("-.5", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_trailing_dot("1.", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_dot_exp("1.e5", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_two_dots("1.2.3", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_exp_without_digits("1e", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_exp_sign_without_digits("1e+", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_exp_sign_without_digits_in_array("[1e-]", vec![JsonReadToken::StartArray], Some(JsonParseError::Parse(ParseErrorKind::IncompleteNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_two_exps("1e2e3", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_fraction_in_exp("1e2.5", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+    #[case::number_literal_double_exp_sign("1e+-2", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidNumberLiteral, Location::start(), None)))]
+
+    #[case::number_literal_no_leading_zero(".1", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+    #[case::no_matching_literal("x", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+    #[case::invalid_number_continuation("1x", vec![JsonReadToken::NumberLiteral(JsonNumber("1"))], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::invalid_number_continuation_quote("x\"", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+
+    #[case::string_literal_unterminated_short(r#""abc "#, vec![], Some(JsonParseError::Parse(ParseErrorKind::UnterminatedString, Location::start(), None)))]
     #[case::string_literal_unterminated_long(r#""abc                                                                         "#, vec![], Some(JsonParseError::BufferOverflow(Location::start())))]
-    #[case::string_literal_invalid_escape(r#""\q""#, vec![], Some(JsonParseError::Parse("invalid escape in string literal", Location::start())))]
-    #[case::string_literal_unicode_string_ends(r#""\u004""#, vec![], Some(JsonParseError::Parse("not a four-digit hex number after \\u", Location::start())))]
-    #[case::string_literal_unicode_invalid_character_1(r#""\ux041""#, vec![], Some(JsonParseError::Parse("not a four-digit hex number after \\u", Location::start())))]
-    #[case::string_literal_unicode_invalid_character_2(r#""\u0x41""#, vec![], Some(JsonParseError::Parse("not a four-digit hex number after \\u", Location::start())))]
-    #[case::string_literal_unicode_invalid_character_3(r#""\u00x1""#, vec![], Some(JsonParseError::Parse("not a four-digit hex number after \\u", Location::start())))]
-    #[case::string_literal_unicode_invalid_character_4(r#""\u004x""#, vec![], Some(JsonParseError::Parse("not a four-digit hex number after \\u", Location::start())))]
-    #[case::string_literal_unicode_uppercase_u(r#""\U0041""#, vec![], Some(JsonParseError::Parse("invalid escape in string literal", Location::start())))]
+    #[case::string_literal_invalid_escape(r#""\q""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_string_ends(r#""\u004""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_invalid_character_1(r#""\ux041""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_invalid_character_2(r#""\u0x41""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_invalid_character_3(r#""\u00x1""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_invalid_character_4(r#""\u004x""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_uppercase_u(r#""\U0041""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidEscape, Location::start(), None)))]
     #[case::string_literal_unicode_uppercase(r#""\uABCD""#, vec![JsonReadToken::StringLiteral("\u{abcd}")], None)]
     #[case::string_literal_unicode_mixed_case_1(r#""\uaBcD""#, vec![JsonReadToken::StringLiteral("\u{abcd}")], None)]
     #[case::string_literal_unicode_mixed_case_2(r#""\uAbCd""#, vec![JsonReadToken::StringLiteral("\u{abcd}")], None)]
 
-    #[case::null_wrong_continuation_1("nul", vec![], Some(JsonParseError::Parse("incomplete null literal", Location::start())))]
-    #[case::null_wrong_continuation_2("nxll", vec![], Some(JsonParseError::Parse("incomplete null literal", Location::start())))]
-    #[case::null_wrong_continuation_3("nUll", vec![], Some(JsonParseError::Parse("incomplete null literal", Location::start())))]
-    #[case::null_wrong_continuation_4("nuxl", vec![], Some(JsonParseError::Parse("incomplete null literal", Location::start())))]
-    #[case::null_wrong_continuation_5("nuLl", vec![], Some(JsonParseError::Parse("incomplete null literal", Location::start())))]
-    #[case::null_wrong_continuation_6("nulx", vec![], Some(JsonParseError::Parse("incomplete null literal", Location::start())))]
-    #[case::null_wrong_continuation_7("nulL", vec![], Some(JsonParseError::Parse("incomplete null literal", Location::start())))]
-    #[case::null_uppercase("Null", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-    #[case::null_uppercase_2("NULL", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-
-    #[case::true_wrong_continuation_1("tru", vec![], Some(JsonParseError::Parse("incomplete true literal", Location::start())))]
-    #[case::true_wrong_continuation_2("txue", vec![], Some(JsonParseError::Parse("incomplete true literal", Location::start())))]
-    #[case::true_wrong_continuation_3("tRue", vec![], Some(JsonParseError::Parse("incomplete true literal", Location::start())))]
-    #[case::true_wrong_continuation_4("trxe", vec![], Some(JsonParseError::Parse("incomplete true literal", Location::start())))]
-    #[case::true_wrong_continuation_5("trUe", vec![], Some(JsonParseError::Parse("incomplete true literal", Location::start())))]
-    #[case::true_wrong_continuation_6("trux", vec![], Some(JsonParseError::Parse("incomplete true literal", Location::start())))]
-    #[case::true_wrong_continuation_7("truE", vec![], Some(JsonParseError::Parse("incomplete true literal", Location::start())))]
-    #[case::true_uppercase_1("True", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-    #[case::true_uppercase_2("TRUE", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-
-    #[case::false_wrong_continuation_1("fals", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_2("fxlse", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_3("fAlse", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_4("faxse", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_5("faLse", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_6("falxe", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_7("falSe", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_8("falsx", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_wrong_continuation_9("falsE", vec![], Some(JsonParseError::Parse("incomplete false literal", Location::start())))]
-    #[case::false_uppercase_1("False", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-    #[case::false_uppercase_2("FALSE", vec![], Some(JsonParseError::Parse("invalid JSON literal", Location::start())))]
-
-    #[case::object_end_just_comma(r#"{, }"#, vec![JsonReadToken::StartObject], Some(JsonParseError::Parse("unexpected comma", Location::start())))]
-    #[case::object_end_trailing_comma(r#"{"a": null, }"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NullLiteral], Some(JsonParseError::Parse("trailing comma", Location::start())))]
-    #[case::object_end_after_key(r#"{"a": }"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a")], Some(JsonParseError::Parse("key without a value", Location::start())))]
-    #[case::array_end_just_comma(r#"[, ]"#, vec![JsonReadToken::StartArray], Some(JsonParseError::Parse("unexpected comma", Location::start())))]
-    #[case::array_end_trailing_comma(r#"[null, ]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("trailing comma", Location::start())))]
-    #[case::array_end_after_key(r#"["a": ]"#, vec![JsonReadToken::StartArray, JsonReadToken::Key("a")], Some(JsonParseError::Parse("key without a value", Location::start())))]
-
-    #[case::missing_comma_null(r#"[null null]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::missing_comma_true(r#"[null true]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::missing_comma_false(r#"[null false]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::missing_comma_number(r#"[null 123]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::missing_comma_string(r#"[null "abc"]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::missing_comma_object(r#"[null {}]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::missing_comma_array(r#"[null []]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::missing_comma_key(r#"{"a": null "b": 1}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NullLiteral], Some(JsonParseError::Parse("missing comma", Location::start())))]
-    #[case::key_after_key(r#"{"a": "b": 1}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a")], Some(JsonParseError::Parse("two keys without value", Location::start())))]
-    #[case::comma_after_key(r#"{"a": , "b": 1}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a")], Some(JsonParseError::Parse("unexpected comma", Location::start())))]
-
-    #[case::object_comma_after_comma(r#"{"a": null, ,}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NullLiteral], Some(JsonParseError::Parse("unexpected comma", Location::start())))]
-    #[case::array_comma_after_comma(r#"[ null, ,]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse("unexpected comma", Location::start())))]
+    #[case::string_literal_unicode_surrogate_pair(r#""\ud83d\ude00""#, vec![JsonReadToken::StringLiteral("😀")], None)]
+    #[case::string_literal_unicode_surrogate_pair_uppercase(r#""\uD834\uDD1E""#, vec![JsonReadToken::StringLiteral("𝄞")], None)]
+    #[case::string_literal_unicode_surrogate_pair_max(r#""\udbff\udfff""#, vec![JsonReadToken::StringLiteral("\u{10ffff}")], None)]
+    #[case::string_literal_unicode_surrogate_pair_combined(r#""a\ud83d\ude00b\u00e4😀""#, vec![JsonReadToken::StringLiteral("a😀bä😀")], None)]
+    #[case::string_literal_unicode_lone_high_surrogate(r#""\ud83d""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::UnpairedSurrogate, Location::start(), None)))]
+    #[case::string_literal_unicode_lone_low_surrogate(r#""\ude00""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::UnpairedSurrogate, Location::start(), None)))]
+    #[case::string_literal_unicode_high_surrogate_then_char(r#""\ud83dx""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::UnpairedSurrogate, Location::start(), None)))]
+    #[case::string_literal_unicode_high_surrogate_then_escape(r#""\ud83d\n""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::UnpairedSurrogate, Location::start(), None)))]
+    #[case::string_literal_unicode_two_high_surrogates(r#""\ud83d\ud83d""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::UnpairedSurrogate, Location::start(), None)))]
+    #[case::string_literal_unicode_high_surrogate_then_bmp(r#""\ud83dA""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::UnpairedSurrogate, Location::start(), None)))]
+    #[case::key_unicode_surrogate_pair(r#"{"\ud83d\ude00":"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("😀")], None)]
+    #[case::string_literal_unicode_surrogate_invalid_hex(r#""\uDZ00""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_low_surrogate_invalid_hex(r#""\ud83d\uDZ00""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_invalid_uppercase_hex(r#""\uFFFZ""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+    #[case::string_literal_unicode_invalid_uppercase_hex_g(r#""\u00G0""#, vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidUnicodeEscape, Location::start(), None)))]
+
+    #[case::null_wrong_continuation_1("nul", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("null"), Location::start(), None)))]
+    #[case::null_wrong_continuation_2("nxll", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("null"), Location::start(), None)))]
+    #[case::null_wrong_continuation_3("nUll", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("null"), Location::start(), None)))]
+    #[case::null_wrong_continuation_4("nuxl", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("null"), Location::start(), None)))]
+    #[case::null_wrong_continuation_5("nuLl", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("null"), Location::start(), None)))]
+    #[case::null_wrong_continuation_6("nulx", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("null"), Location::start(), None)))]
+    #[case::null_wrong_continuation_7("nulL", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("null"), Location::start(), None)))]
+    #[case::null_uppercase("Null", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+    #[case::null_uppercase_2("NULL", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+
+    #[case::true_wrong_continuation_1("tru", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("true"), Location::start(), None)))]
+    #[case::true_wrong_continuation_2("txue", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("true"), Location::start(), None)))]
+    #[case::true_wrong_continuation_3("tRue", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("true"), Location::start(), None)))]
+    #[case::true_wrong_continuation_4("trxe", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("true"), Location::start(), None)))]
+    #[case::true_wrong_continuation_5("trUe", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("true"), Location::start(), None)))]
+    #[case::true_wrong_continuation_6("trux", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("true"), Location::start(), None)))]
+    #[case::true_wrong_continuation_7("truE", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("true"), Location::start(), None)))]
+    #[case::true_uppercase_1("True", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+    #[case::true_uppercase_2("TRUE", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+
+    #[case::false_wrong_continuation_1("fals", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_2("fxlse", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_3("fAlse", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_4("faxse", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_5("faLse", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_6("falxe", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_7("falSe", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_8("falsx", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_wrong_continuation_9("falsE", vec![], Some(JsonParseError::Parse(ParseErrorKind::IncompleteLiteral("false"), Location::start(), None)))]
+    #[case::false_uppercase_1("False", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+    #[case::false_uppercase_2("FALSE", vec![], Some(JsonParseError::Parse(ParseErrorKind::InvalidLiteral, Location::start(), None)))]
+
+    #[case::object_end_just_comma(r#"{, }"#, vec![JsonReadToken::StartObject], Some(JsonParseError::Parse(ParseErrorKind::UnexpectedComma, Location::start(), None)))]
+    #[case::object_end_trailing_comma(r#"{"a": null, }"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::TrailingComma, Location::start(), None)))]
+    #[case::object_end_after_key(r#"{"a": }"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a")], Some(JsonParseError::Parse(ParseErrorKind::KeyWithoutValue, Location::start(), None)))]
+    #[case::array_end_just_comma(r#"[, ]"#, vec![JsonReadToken::StartArray], Some(JsonParseError::Parse(ParseErrorKind::UnexpectedComma, Location::start(), None)))]
+    #[case::array_end_trailing_comma(r#"[null, ]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::TrailingComma, Location::start(), None)))]
+    #[case::array_end_after_key(r#"["a": ]"#, vec![JsonReadToken::StartArray], Some(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+
+    #[case::object_closed_as_array(r#"{"a":1]"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NumberLiteral(JsonNumber("1"))], Some(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::empty_object_closed_as_array(r#"{]"#, vec![JsonReadToken::StartObject], Some(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::array_closed_as_object(r#"[1}"#, vec![JsonReadToken::StartArray, JsonReadToken::NumberLiteral(JsonNumber("1"))], Some(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::nested_mismatch(r#"[{"a":[]]]"#, vec![JsonReadToken::StartArray, JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::StartArray, JsonReadToken::EndArray], Some(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::nested_match(r#"[{"a":[{}]},[]]"#, vec![JsonReadToken::StartArray, JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::StartArray, JsonReadToken::StartObject, JsonReadToken::EndObject, JsonReadToken::EndArray, JsonReadToken::EndObject, JsonReadToken::StartArray, JsonReadToken::EndArray, JsonReadToken::EndArray], None)]
+    #[case::unopened_end_object(r#"}"#, vec![], Some(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::unopened_end_array(r#"]"#, vec![], Some(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array_after_closed_array(r#"[]]"#, vec![JsonReadToken::StartArray, JsonReadToken::EndArray], Some(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::top_level_key(r#""a": 1"#, vec![], Some(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::key_in_array(r#"["k": 1]"#, vec![JsonReadToken::StartArray], Some(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::key_in_array_after_value(r#"[1, "k": 1]"#, vec![JsonReadToken::StartArray, JsonReadToken::NumberLiteral(JsonNumber("1"))], Some(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::key_in_array_in_object(r#"{"a": ["k": 1]}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::StartArray], Some(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::value_without_key(r#"{1}"#, vec![JsonReadToken::StartObject], Some(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::string_without_key(r#"{"a"}"#, vec![JsonReadToken::StartObject], Some(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::object_without_key(r#"{"a": 1, {}}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NumberLiteral(JsonNumber("1"))], Some(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::array_without_key(r#"{[]}"#, vec![JsonReadToken::StartObject], Some(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+
+    #[case::missing_comma_null(r#"[null null]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::missing_comma_true(r#"[null true]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::missing_comma_false(r#"[null false]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::missing_comma_number(r#"[null 123]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::missing_comma_string(r#"[null "abc"]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::missing_comma_object(r#"[null {}]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::missing_comma_array(r#"[null []]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::missing_comma_key(r#"{"a": null "b": 1}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::MissingComma, Location::start(), None)))]
+    #[case::key_after_key(r#"{"a": "b": 1}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a")], Some(JsonParseError::Parse(ParseErrorKind::TwoKeysWithoutValue, Location::start(), None)))]
+    #[case::comma_after_key(r#"{"a": , "b": 1}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a")], Some(JsonParseError::Parse(ParseErrorKind::UnexpectedComma, Location::start(), None)))]
+
+    #[case::object_comma_after_comma(r#"{"a": null, ,}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::UnexpectedComma, Location::start(), None)))]
+    #[case::array_comma_after_comma(r#"[ null, ,]"#, vec![JsonReadToken::StartArray, JsonReadToken::NullLiteral], Some(JsonParseError::Parse(ParseErrorKind::UnexpectedComma, Location::start(), None)))]
 
     #[case::object(r#"{ "a": 1, "b": true, "c": "xyz" }"#, vec![
         JsonReadToken::StartObject,
@@ -943,42 +1473,489 @@ mod tests {
         JsonReadToken::EndObject,
     ], None)]
 
-    fn test_parse(#[case] input: &str, #[case] expected: Vec<JsonReadToken>, #[case] expected_error: Option<JsonParseError<std::io::Error>>) {
-        let input_with_whitespace = format!(" \r\n\t{} \r\n\t", input);
+    fn test_parse(#[case] input: &str, #[case] expected: Vec<JsonReadToken>, #[case] expected_error: Option<JsonParseError<std::io::Error>>) {
+        let input_with_whitespace = format!(" \r\n\t{} \r\n\t", input);
+
+        {
+            let mut r = Cursor::new(input.as_bytes());
+            let mut json_reader = JsonReader::new(64, &mut r);
+            for evt in &expected {
+                let parsed_evt = json_reader.next();
+                assert_eq!(&parsed_evt.unwrap(), evt);
+            }
+            if let Some(expected_error) = &expected_error {
+                match json_reader.next() {
+                    Ok(_) => panic!("expected error but was ok: {}", expected_error),
+                    Err(e) => assert_is_similar_error(&e, expected_error),
+                }
+            }
+            else {
+                assert_eq!(json_reader.next().unwrap(), JsonReadToken::EndOfStream);
+            }
+        }
+        let mut expected_error_text = None;
+        {
+            let mut r = Cursor::new(input_with_whitespace.as_bytes());
+            let mut json_reader = JsonReader::new(64, &mut r);
+            for evt in &expected {
+                assert_eq!(&json_reader.next().unwrap(), evt);
+            }
+            if let Some(expected_error) = &expected_error {
+                match json_reader.next() {
+                    Ok(_) => panic!("expected error but was ok: {}", expected_error),
+                    Err(e) => {
+                        assert_is_similar_error(&e, expected_error);
+                        expected_error_text = Some(e.to_string());
+                    }
+                }
+            }
+            else {
+                assert_eq!(json_reader.next().unwrap(), JsonReadToken::EndOfStream);
+            }
+        }
+        for read_buf_size in [1, 3, 64] {
+            // slice-oriented reading must produce the same tokens and error locations
+            let mut r = BufferedRead::new(Cursor::new(input_with_whitespace.as_bytes()), vec![0u8; read_buf_size]);
+            let mut json_reader = JsonReader::new(64, &mut r);
+            for evt in &expected {
+                assert_eq!(&json_reader.next().unwrap(), evt);
+            }
+            match &expected_error_text {
+                Some(expected_error_text) => {
+                    assert_eq!(&json_reader.next().unwrap_err().to_string(), expected_error_text);
+                }
+                None => {
+                    assert_eq!(json_reader.next().unwrap(), JsonReadToken::EndOfStream);
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::lone_high(r#""\ud83d""#, "\u{fffd}")]
+    #[case::lone_low(r#""\ude00""#, "\u{fffd}")]
+    #[case::high_then_char(r#""a\ud83db""#, "a\u{fffd}b")]
+    #[case::high_then_escape(r#""\ud83d\n""#, "\u{fffd}\n")]
+    #[case::two_high(r#""\ud83d\ud83d\ude00""#, "\u{fffd}😀")]
+    #[case::reversed(r#""\ude00\ud83d""#, "\u{fffd}\u{fffd}")]
+    fn test_lossy_surrogate_handling(#[case] input: &str, #[case] expected: &str) {
+        let mut r = Cursor::new(input.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r).with_lossy_surrogate_handling();
+        assert_eq!(json_reader.expect_string().unwrap(), expected);
+
+        let mut r = Cursor::new(input.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        assert!(json_reader.expect_string().is_err());
+    }
+
+    #[test]
+    fn test_depth_and_current_container() -> JsonParseResult<(), io::Error> {
+        let json = r#"{"a": [1, {"b": []}], "c": 2}"#;
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (0, None));
+        json_reader.expect_start_object()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (1, Some(ContainerKind::Object)));
+        assert_eq!(json_reader.expect_key()?, Some("a"));
+        json_reader.expect_start_array()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (2, Some(ContainerKind::Array)));
+        assert_eq!(json_reader.expect_number::<u32>()?, 1);
+        json_reader.expect_start_object()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (3, Some(ContainerKind::Object)));
+        assert_eq!(json_reader.expect_key()?, Some("b"));
+        json_reader.expect_start_array()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (4, Some(ContainerKind::Array)));
+        json_reader.expect_end_array()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (3, Some(ContainerKind::Object)));
+        json_reader.expect_end_object()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (2, Some(ContainerKind::Array)));
+        json_reader.expect_end_array()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (1, Some(ContainerKind::Object)));
+        assert_eq!(json_reader.expect_key()?, Some("c"));
+        assert_eq!(json_reader.expect_number::<u32>()?, 2);
+        json_reader.expect_end_object()?;
+        assert_eq!((json_reader.depth(), json_reader.current_container()), (0, None));
+        json_reader.expect_end_of_stream()?;
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::flat("[]", 1, true)]
+    #[case::within_limit("[[[[[[[{}]]]]]]]", 1, true)]
+    #[case::exceeding_limit("[[[[[[[[[]]]]]]]]]", 1, false)]
+    #[case::within_bigger_limit("[[[[[[[[[]]]]]]]]]", 2, true)]
+    #[case::deep_alternating("[{\"a\":[{\"a\":[{\"a\":[{\"a\":[{\"a\":[]}]}]}]}]}]", 2, true)]
+    fn test_provided_nesting_buffer(#[case] json: &str, #[case] nesting_buf_len: usize, #[case] should_succeed: bool) {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new_with_provided_buffers(vec![0u8; 64], vec![0u8; nesting_buf_len], &mut r, false);
+        let result = json_reader.skip_value();
+        if should_succeed {
+            result.unwrap();
+            json_reader.expect_end_of_stream().unwrap();
+        }
+        else {
+            assert_is_similar_error(&result.unwrap_err(), &JsonParseError::MaxNestingDepthExceeded(Location::start()));
+        }
+    }
+
+    #[rstest]
+    #[case::scalar("1", 0, None)]
+    #[case::flat_at_zero("[]", 0, Some(1))]
+    #[case::within_limit("[[[1]]]", 3, None)]
+    #[case::objects_within_limit(r#"{"a": {"b": {}}}"#, 3, None)]
+    #[case::exceeding_limit("[[[[1]]]]", 3, Some(4))]
+    #[case::objects_exceeding_limit(r#"{"a": {"b": {"c": {}}}}"#, 3, Some(19))]
+    #[case::mixed_exceeding_limit(r#" [{"a": [[]]}]"#, 3, Some(10))]
+    fn test_max_nesting_depth(#[case] json: &str, #[case] max_nesting_depth: usize, #[case] expected_error_offset: Option<usize>) {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r).with_max_nesting_depth(max_nesting_depth);
+        match (json_reader.skip_value(), expected_error_offset) {
+            (Ok(()), None) => {}
+            (Err(JsonParseError::MaxNestingDepthExceeded(location)), Some(offset)) => assert_eq!(location.offset, offset),
+            (other, _) => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_max_nesting_depth() {
+        let within_limit = format!("{}{}", "[".repeat(DEFAULT_MAX_NESTING_DEPTH), "]".repeat(DEFAULT_MAX_NESTING_DEPTH));
+        let mut r = Cursor::new(within_limit.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.skip_value().unwrap();
+        json_reader.expect_end_of_stream().unwrap();
+
+        let huge = "[".repeat(1_000_000);
+        let mut r = Cursor::new(huge.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        match json_reader.skip_value() {
+            Err(JsonParseError::MaxNestingDepthExceeded(location)) => assert_eq!(location.offset, DEFAULT_MAX_NESTING_DEPTH + 1),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[rstest]
+    #[case::empty(r#""""#, 8, "")]
+    #[case::short(r#""abc""#, 8, "abc")]
+    #[case::exactly_one_chunk(r#""abcde""#, 8, "abcde")]
+    #[case::several_chunks(r#""abcdefghijklmnopqrstuvwxyz""#, 8, "abcdefghijklmnopqrstuvwxyz")]
+    #[case::multi_byte(r#""äöüÄÖÜß€€€😀😀😀""#, 8, "äöüÄÖÜß€€€😀😀😀")]
+    #[case::multi_byte_bigger_buffer(r#""äöüÄÖÜß€€€😀😀😀""#, 11, "äöüÄÖÜß€€€😀😀😀")]
+    #[case::escapes(r#""a\nb\tc\"d\\e\u00e4f\u20acg\ud83d\ude00h""#, 8, "a\nb\tc\"d\\eäf€g😀h")]
+    #[case::long(&format!("\"{}\"", "0123456789".repeat(1000)), 64, &"0123456789".repeat(1000))]
+    fn test_expect_string_chunked(#[case] json: &str, #[case] buf_size: usize, #[case] expected: &str) {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(buf_size, &mut r);
+        let mut chunks = Vec::new();
+        json_reader.expect_string_chunked(|chunk| chunks.push(chunk.to_string())).unwrap();
+        json_reader.expect_end_of_stream().unwrap();
+
+        assert_eq!(chunks.concat(), expected);
+        for chunk in &chunks {
+            assert!(!chunk.is_empty());
+            assert!(chunk.len() <= buf_size);
+        }
+    }
+
+    #[test]
+    fn test_expect_string_chunked_buffered() {
+        let json = r#""äbcdefghijklmnöpqrstuvwxyz\n😀 äbcdefghijklmnöpqrstuvwxyz""#;
+        let expected = "äbcdefghijklmnöpqrstuvwxyz\n😀 äbcdefghijklmnöpqrstuvwxyz";
+        for read_buf_size in [1, 5, 64] {
+            let mut r = BufferedRead::new(Cursor::new(json.as_bytes()), vec![0u8; read_buf_size]);
+            let mut json_reader = JsonReader::new(8, &mut r);
+            let mut chunks = Vec::new();
+            json_reader.expect_string_chunked(|chunk| chunks.push(chunk.to_string())).unwrap();
+            json_reader.expect_end_of_stream().unwrap();
+            assert_eq!(chunks.concat(), expected);
+        }
+    }
+
+    /// a plain [Read] implementation that records the size of the buffer passed to each call
+    struct ReadSizeRecorder<'a> {
+        inner: Cursor<&'a [u8]>,
+        read_sizes: Vec<usize>,
+    }
+    impl Read for ReadSizeRecorder<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.read_sizes.push(buf.len());
+            Read::read(&mut self.inner, buf)
+        }
+    }
 
-        {
-            let mut r = Cursor::new(input.as_bytes());
-            let mut json_reader = JsonReader::new(64, &mut r);
-            for evt in &expected {
-                let parsed_evt = json_reader.next();
-                assert_eq!(&parsed_evt.unwrap(), evt);
+    const READ_AHEAD_JSON: &str = r#"{"a": [1, 2.5, true, null],  "bc": "xyz"}  "#;
+
+    fn read_all_tokens<R: BlockingRead>(json_reader: &mut JsonReader<Vec<u8>, R>) -> Vec<String> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = json_reader.next().unwrap();
+            tokens.push(format!("{:?}", token));
+            if token == JsonReadToken::EndOfStream {
+                return tokens;
             }
-            if let Some(expected_error) = &expected_error {
-                match json_reader.next() {
-                    Ok(_) => panic!("expected error but was ok: {}", expected_error),
-                    Err(e) => assert_is_similar_error(&e, expected_error),
+        }
+    }
+
+    #[test]
+    fn test_read_ahead_allocated() {
+        let mut r = ReadSizeRecorder { inner: Cursor::new(READ_AHEAD_JSON.as_bytes()), read_sizes: Vec::new() };
+        let mut json_reader = JsonReader::new(64, &mut r);
+        let tokens = read_all_tokens(&mut json_reader);
+        assert_eq!(tokens.len(), 12);
+        // the first read returns the entire document, the others signal the end of the stream
+        assert!(r.read_sizes.len() < 5, "{:?}", r.read_sizes);
+        assert!(r.read_sizes.iter().all(|&size| size == DEFAULT_READ_AHEAD_SIZE));
+    }
+
+    #[rstest]
+    #[case::without_read_buffer(None, 1)]
+    #[case::tiny_read_buffer(Some(1), 1)]
+    #[case::small_read_buffer(Some(4), 4)]
+    #[case::large_read_buffer(Some(100), 100)]
+    fn test_read_ahead_provided(#[case] read_buf_size: Option<usize>, #[case] expected_read_size: usize) {
+        let mut r = Cursor::new(READ_AHEAD_JSON.as_bytes());
+        let expected = read_all_tokens(&mut JsonReader::new(64, &mut r));
+
+        let mut r = ReadSizeRecorder { inner: Cursor::new(READ_AHEAD_JSON.as_bytes()), read_sizes: Vec::new() };
+        let mut json_reader = JsonReader::new_with_provided_buffer(vec![0u8; 64], &mut r, false);
+        if let Some(read_buf_size) = read_buf_size {
+            json_reader = json_reader.with_provided_read_buffer(vec![0u8; read_buf_size]);
+        }
+        assert_eq!(read_all_tokens(&mut json_reader), expected);
+        assert!(r.read_sizes.len() > READ_AHEAD_JSON.len() / expected_read_size);
+        assert!(r.read_sizes.iter().all(|&size| size == expected_read_size));
+    }
+
+    #[test]
+    fn test_read_ahead_with_slice_reader() {
+        // a reader with slice-oriented access is used directly, without a second buffer
+        let mut r = BufferedRead::new(
+            ReadSizeRecorder { inner: Cursor::new(READ_AHEAD_JSON.as_bytes()), read_sizes: Vec::new() },
+            vec![0u8; 16],
+        );
+        let mut json_reader = JsonReader::new(64, &mut r);
+        assert_eq!(read_all_tokens(&mut json_reader).len(), 12);
+        assert!(r.into_inner().read_sizes.iter().all(|&size| size == 16));
+    }
+
+    #[test]
+    fn test_expect_string_chunked_in_container() -> JsonParseResult<(), io::Error> {
+        let json = r#"{"a": ["xyz", "0123456789abcdef", 5], "b": "uvw"}"#;
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(8, &mut r);
+
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.expect_key()?, Some("a"));
+        json_reader.expect_start_array()?;
+        let mut s = String::new();
+        json_reader.expect_string_chunked(|chunk| s.push_str(chunk))?;
+        assert_eq!(s, "xyz");
+        let mut s = String::new();
+        json_reader.expect_string_chunked(|chunk| s.push_str(chunk))?;
+        assert_eq!(s, "0123456789abcdef");
+        assert!(json_reader.expect_string_chunked(|_| {}).is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::number("1", 8, false)]
+    #[case::null("null", 8, false)]
+    #[case::start_array("[", 8, false)]
+    #[case::end_of_stream("", 8, false)]
+    #[case::key(r#"{"abc": 1}"#, 8, true)]
+    #[case::unterminated(r#""abcdefghijkl"#, 8, false)]
+    #[case::buffer_too_small(r#""abc""#, 7, false)]
+    #[case::invalid_escape(r#""abc\qdef""#, 8, false)]
+    fn test_expect_string_chunked_fails(#[case] json: &str, #[case] buf_size: usize, #[case] in_object: bool) {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(buf_size, &mut r);
+        if in_object {
+            json_reader.expect_start_object().unwrap();
+        }
+        assert!(json_reader.expect_string_chunked(|_| {}).is_err());
+    }
+
+    #[test]
+    fn test_peek_kind() -> JsonParseResult<(), io::Error> {
+        let json = r#"[{"name": "a", "size": 1}, "b", 2, true, false, null, [], 3.5]"#;
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+
+        // 'peek_kind()' is idempotent, and it does not consume the token
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::StartArray);
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::StartArray);
+        json_reader.expect_start_array()?;
+
+        let mut names = Vec::new();
+        loop {
+            match json_reader.peek_kind()? {
+                JsonReadTokenKind::StartObject => {
+                    json_reader.expect_start_object()?;
+                    assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::Key);
+                    assert_eq!(json_reader.expect_key()?, Some("name"));
+                    names.push(json_reader.expect_string()?.to_string());
+                    assert_eq!(json_reader.expect_key()?, Some("size"));
+                    assert_eq!(json_reader.expect_number::<u32>()?, 1);
+                    assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::EndObject);
+                    json_reader.expect_end_object()?;
                 }
-            }
-            else {
-                assert_eq!(json_reader.next().unwrap(), JsonReadToken::EndOfStream);
+                JsonReadTokenKind::StringLiteral => names.push(json_reader.expect_string()?.to_string()),
+                JsonReadTokenKind::NumberLiteral => names.push(json_reader.expect_raw_number()?.0.to_string()),
+                JsonReadTokenKind::BooleanLiteral => names.push(json_reader.expect_bool()?.to_string()),
+                JsonReadTokenKind::NullLiteral => {
+                    assert_eq!(json_reader.expect_opt_string()?, None);
+                    names.push("null".to_string());
+                }
+                JsonReadTokenKind::StartArray => {
+                    assert_eq!(json_reader.expect_start_array_or_end_array()?, Some(()));
+                    json_reader.skip_to_end_of_current_scope()?;
+                    names.push("[]".to_string());
+                }
+                JsonReadTokenKind::EndArray => {
+                    json_reader.expect_end_array()?;
+                    break;
+                }
+                other => panic!("unexpected token kind {}", other),
             }
         }
-        {
-            let mut r = Cursor::new(input_with_whitespace.as_bytes());
-            let mut json_reader = JsonReader::new(64, &mut r);
-            for evt in &expected {
-                assert_eq!(&json_reader.next().unwrap(), evt);
+        assert_eq!(names, vec!["a", "b", "2", "true", "false", "null", "[]", "3.5"]);
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::EndOfStream);
+        json_reader.expect_end_of_stream()
+    }
+
+    #[test]
+    fn test_peek_kind_then_expect_string_chunked() -> JsonParseResult<(), io::Error> {
+        let json = r#"["abcdefghijklmnopqrstuvwxyz", 1]"#;
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(32, &mut r);
+        json_reader.expect_start_array()?;
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::StringLiteral);
+        let mut chunks = Vec::new();
+        json_reader.expect_string_chunked(|chunk| chunks.push(chunk.to_string()))?;
+        assert_eq!(chunks.concat(), "abcdefghijklmnopqrstuvwxyz");
+
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::NumberLiteral);
+        assert!(json_reader.expect_string_chunked(|_| {}).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_peek_kind_fails() {
+        let json = r#"[1 2]"#;
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(32, &mut r);
+        json_reader.expect_start_array().unwrap();
+        assert_eq!(json_reader.peek_kind().unwrap(), JsonReadTokenKind::NumberLiteral);
+        assert_eq!(json_reader.expect_number::<u8>().unwrap(), 1);
+        assert!(json_reader.peek_kind().is_err());
+    }
+
+    #[rstest]
+    #[case::complete(64, vec!["", "/orders", "/orders", "/orders/0", "/orders/0/id", "/orders/0/id", "/orders/0/items", "/orders/0/items", "/orders/0/items/0", "/orders/0/items/1", "/orders/0/items/1/price", "/orders/0/items/1/price", "/orders/0/items/1", "/orders/0/items", "/orders/0", "/orders/1", "/orders/1", "/orders", "/a~1b~0c", "/a~1b~0c", "", ""])]
+    #[case::truncated(10, vec!["", "/orders", "/orders", "/orders/0", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0/...", "/orders/0", "/orders/1", "/orders/1", "/orders", "/a~1b~0c", "/a~1b~0c", "", ""])]
+    #[case::empty_buffer(0, vec!["", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "/...", "", ""])]
+    fn test_current_path(#[case] max_path_len: usize, #[case] expected: Vec<&str>) -> JsonParseResult<(), io::Error> {
+        let json = r#"{"orders": [{"id": 1, "items": [5, {"price": 3}]}, []], "a/b~c": null}"#;
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r)
+            .with_path_tracking(max_path_len);
+
+        let mut actual = Vec::new();
+        loop {
+            let is_end = json_reader.next()? == JsonReadToken::EndOfStream;
+            actual.push(json_reader.current_path().unwrap().to_string());
+            if is_end {
+                break;
             }
-            if let Some(expected_error) = &expected_error {
-                match json_reader.next() {
-                    Ok(_) => panic!("expected error but was ok: {}", expected_error),
-                    Err(e) => assert_is_similar_error(&e, expected_error),
-                }
+        }
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_current_path_with_provided_buffer() -> JsonParseResult<(), io::Error> {
+        let mut buf = [0u8; 16];
+        let mut path_buf = [0u8; 8];
+        let mut r = Cursor::new(br#"[[1, 2], {"abc": 3}]"#);
+        let mut json_reader = JsonReader::new_with_provided_buffer(&mut buf[..], &mut r, false)
+            .with_provided_path_buffer(&mut path_buf[..]);
+
+        json_reader.expect_start_array()?;
+        json_reader.expect_start_array()?;
+        json_reader.expect_number::<u32>()?;
+        json_reader.expect_number::<u32>()?;
+        assert_eq!(json_reader.current_path(), Some(JsonPath::new("/0/1", false)));
+        json_reader.expect_end_array()?;
+        json_reader.expect_start_object()?;
+        json_reader.expect_key()?;
+        assert_eq!(json_reader.current_path(), Some(JsonPath::new("/1/abc", false)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_current_path_without_tracking() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(br#"{"a": 1}"#);
+        let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_object()?;
+        json_reader.expect_key()?;
+        assert_eq!(json_reader.current_path(), None);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::invalid_literal(r#"{"a": [1, 2, x]}"#, "/a/2")]
+    #[case::missing_comma(r#"{"a": [1, 2], "b" 3}"#, "/a")]
+    #[case::mismatched_bracket(r#"[{"a": []]"#, "/0/a")]
+    #[case::invalid_key(r#"{"a": {"b": 1, "c": {"d" 1}}}"#, "/a/c")]
+    fn test_path_in_parse_error(#[case] json: &str, #[case] expected_path: &str) {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r)
+            .with_path_tracking(64);
+        let err = loop {
+            match json_reader.next() {
+                Ok(JsonReadToken::EndOfStream) => panic!("expected an error"),
+                Ok(_) => {}
+                Err(e) => break e,
             }
-            else {
-                assert_eq!(json_reader.next().unwrap(), JsonReadToken::EndOfStream);
+        };
+        match &err {
+            JsonParseError::Parse(_, _, Some(path)) => assert_eq!(path.as_str(), expected_path),
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert!(err.to_string().ends_with(&format!("(path {})", expected_path)));
+    }
+
+    #[rstest]
+    #[case::one_expected(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StringLiteral }, "expected number, found string")]
+    #[case::two_expected(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::Key, JsonReadTokenKind::EndObject], found: JsonReadTokenKind::NumberLiteral }, "expected key or }, found number")]
+    #[case::three_expected(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::Key }, "expected null, number or ], found key")]
+    #[case::end_of_input(ParseErrorKind::UnexpectedEndOfInput, "unexpected end of input")]
+    #[case::incomplete_literal(ParseErrorKind::IncompleteLiteral("true"), "incomplete true literal")]
+    #[case::missing_comma(ParseErrorKind::MissingComma, "missing comma")]
+    #[case::custom(ParseErrorKind::Custom("unexpected key"), "unexpected key")]
+    fn test_parse_error_kind_display(#[case] kind: ParseErrorKind, #[case] expected: &str) {
+        assert_eq!(kind.to_string(), expected);
+    }
+
+    #[test]
+    fn test_path_in_expect_error() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(br#"{"a": [{"price": "high"}]}"#);
+        let mut json_reader = JsonReader::new(64, &mut r)
+            .with_path_tracking(64);
+        json_reader.expect_start_object()?;
+        json_reader.expect_key()?;
+        json_reader.expect_start_array()?;
+        json_reader.expect_start_object()?;
+        json_reader.expect_key()?;
+        match json_reader.expect_number::<f64>() {
+            Err(JsonParseError::Parse(kind, location, Some(path))) => {
+                assert_eq!(kind, ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StringLiteral });
+                assert_eq!(location.offset, 16);
+                assert_eq!(path.as_str(), "/a/0/price");
             }
+            other => panic!("unexpected result {:?}", other),
         }
+        Ok(())
     }
 
     #[test]
@@ -1041,6 +2018,516 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case::zero("0", true)]
+    #[case::int("123", true)]
+    #[case::negative("-123", true)]
+    #[case::fract("1.0", false)]
+    #[case::exp_lower("1e3", false)]
+    #[case::exp_upper("1E3", false)]
+    #[case::fract_exp("-1.5e-3", false)]
+    #[case::infinity("Infinity", false)]
+    #[case::negative_infinity("-Infinity", false)]
+    #[case::nan("NaN", false)]
+    fn test_json_number_is_integer(#[case] s: &str, #[case] expected: bool) {
+        assert_eq!(JsonNumber(s).is_integer(), expected);
+    }
+
+    fn read_token_strings<R: BlockingRead>(json_reader: &mut JsonReader<Vec<u8>, R>) -> JsonParseResult<Vec<String>, R::Error> {
+        let mut result = Vec::new();
+        loop {
+            let token = json_reader.next()?;
+            result.push(format!("{:?}", token));
+            if token == JsonReadToken::EndOfStream {
+                return Ok(result);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::plain_json(r#"{"a": [true, false, null, "x", 1.5]}"#, r#"{"a": [true, false, null, "x", 1.5]}"#)]
+    #[case::line_comments("// leading\n[1, // one\n2 // two\n]// trailing", "[1, 2]")]
+    #[case::block_comments(r#"/* leading */{/**/"a"/* x */: /* y */1 /* z * / */, "b": [/***/]}/* trailing */"#, r#"{"a": 1, "b": []}"#)]
+    #[case::trailing_commas(r#"{"a": [1, 2,], "b": {"c": 3,},}"#, r#"{"a": [1, 2], "b": {"c": 3}}"#)]
+    #[case::single_quotes(r#"['abc', 'a"b', 'it\'s', "it's", '']"#, r#"["abc", "a\"b", "it's", "it's", ""]"#)]
+    #[case::single_quoted_key(r#"{'a': 'b'}"#, r#"{"a": "b"}"#)]
+    #[case::escapes(r#"['\x41\xe4\v\0\a\/ä']"#, r#"["Aä\u000b\u0000a/ä"]"#)]
+    #[case::line_continuation("['a\\\nb', 'c\\\r\nd', 'e\\\rf']", r#"["ab", "cd", "ef"]"#)]
+    #[case::unquoted_keys(r#"{a: 1, $b_2: 2, true: 3, null: 4, Infinity: 5, äö: 6}"#, r#"{"a": 1, "$b_2": 2, "true": 3, "null": 4, "Infinity": 5, "äö": 6}"#)]
+    #[case::unquoted_key_whitespace("{ a : 1 }", r#"{"a": 1}"#)]
+    #[case::hex_numbers("[0x1F, -0XfF, 0x0, +0x10]", "[31, -255, 0, 16]")]
+    #[case::signs_and_points("[+1, .5, 5., -.5e3, 5.e3, +0.0, -0]", "[1, 0.5, 5.0, -0.5e3, 5.0e3, 0.0, -0]")]
+    #[case::special_floats("[Infinity, -Infinity, +Infinity, NaN]", "[Infinity, -Infinity, Infinity, NaN]")]
+    #[case::whitespace("[1,\x0b2\x0c]", "[1, 2]")]
+    fn test_json5(#[case] json5: &str, #[case] expected_json: &str) {
+        // the expected tokens, including 'Infinity' and 'NaN' which a strict reader rejects
+        let expected = match read_token_strings(&mut JsonReader::new(64, &mut Cursor::new(expected_json.as_bytes()))) {
+            Ok(tokens) => tokens,
+            Err(_) => read_token_strings(&mut JsonReader::new(64, &mut Cursor::new(expected_json.as_bytes())).with_json5()).unwrap(),
+        };
+
+        let actual = read_token_strings(&mut JsonReader::new(64, &mut Cursor::new(json5.as_bytes())).with_json5()).unwrap();
+        assert_eq!(actual, expected);
+
+        for read_buf_size in [1, 3, 64] {
+            let mut r = BufferedRead::new(Cursor::new(json5.as_bytes()), vec![0u8; read_buf_size]);
+            let actual = read_token_strings(&mut JsonReader::new(64, &mut r).with_json5()).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[rstest]
+    #[case::unterminated_block_comment("[1 /* abc *")]
+    #[case::invalid_comment("[1 / 2]")]
+    #[case::empty_hex("[0x]")]
+    #[case::invalid_hex("[0x1G]")]
+    #[case::hex_too_big("[0x1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF]")]
+    #[case::only_point("[.]")]
+    #[case::only_sign("[+]")]
+    #[case::point_exponent("[.e3]")]
+    #[case::leading_zero("[01]")]
+    #[case::two_points("[1.2.3]")]
+    #[case::infinity_prefix("[Inf]")]
+    #[case::nan_lowercase("[nan]")]
+    #[case::octal_escape(r#"['\1']"#)]
+    #[case::short_hex_escape(r#"['\x4']"#)]
+    #[case::unterminated_single_quote("['abc")]
+    #[case::key_without_colon("{a b: 1}")]
+    #[case::unquoted_value("{a: b}")]
+    #[case::leading_comma("[, 1]")]
+    #[case::double_comma("[1,, 2]")]
+    fn test_json5_fails(#[case] json5: &str) {
+        let mut r = Cursor::new(json5.as_bytes());
+        assert!(read_token_strings(&mut JsonReader::new(64, &mut r).with_json5()).is_err());
+    }
+
+    #[rstest]
+    #[case::comment("[1 // one\n]")]
+    #[case::trailing_comma("[1,]")]
+    #[case::single_quotes("['a']")]
+    #[case::unquoted_key("{a: 1}")]
+    #[case::hex("[0x1]")]
+    #[case::plus("[+1]")]
+    #[case::leading_point("[.5]")]
+    #[case::infinity("[Infinity]")]
+    fn test_json5_rejected_without_json5(#[case] json5: &str) {
+        let mut r = Cursor::new(json5.as_bytes());
+        assert!(read_token_strings(&mut JsonReader::new(64, &mut r)).is_err());
+    }
+
+    #[test]
+    fn test_json5_expect_number() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(b"{n: [0xff, .5, Infinity, -Infinity, NaN], s: 'x',}".to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r).with_json5();
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.expect_key()?, Some("n"));
+        json_reader.expect_start_array()?;
+        assert_eq!(json_reader.expect_number::<u8>()?, 255);
+        assert_eq!(json_reader.expect_number::<f64>()?, 0.5);
+        assert_eq!(json_reader.expect_number::<f64>()?, f64::INFINITY);
+        assert_eq!(json_reader.expect_number::<f64>()?, f64::NEG_INFINITY);
+        assert!(json_reader.expect_number::<f64>()?.is_nan());
+        json_reader.expect_end_array()?;
+        assert_eq!(json_reader.expect_key()?, Some("s"));
+        let mut chunks = String::new();
+        json_reader.expect_string_chunked(|chunk| chunks.push_str(chunk))?;
+        assert_eq!(chunks, "x");
+        assert_eq!(json_reader.expect_key()?, None);
+        json_reader.expect_end_of_stream()
+    }
+
+    #[rstest]
+    #[case::line_comments("// leading\n[1, // one\r\n2 // two\n]// trailing", "[1, 2]")]
+    #[case::block_comments(r#"/* leading */{/**/"a"/* x */: /* y */1 /* z * / */, "b": [/***/]}/* trailing */"#, r#"{"a": 1, "b": []}"#)]
+    #[case::comment_markers_in_string(r#"["// x", "/* y */"] // z"#, r#"["// x", "/* y */"]"#)]
+    #[case::multi_line_block_comment("[\n/*\n * a\n */\ntrue]", "[true]")]
+    fn test_comments(#[case] json: &str, #[case] expected_json: &str) {
+        let expected = read_token_strings(&mut JsonReader::new(64, &mut Cursor::new(expected_json.as_bytes()))).unwrap();
+
+        for read_buf_size in [1, 3, 64] {
+            let mut r = BufferedRead::new(Cursor::new(json.as_bytes()), vec![0u8; read_buf_size]);
+            let actual = read_token_strings(&mut JsonReader::new(64, &mut r).with_comments()).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[rstest]
+    #[case::line_comments("// leading\n[1, // one\r\n2]// trailing", vec![
+        JsonReadToken::Comment("// leading"),
+        JsonReadToken::StartArray,
+        JsonReadToken::NumberLiteral(JsonNumber("1")),
+        JsonReadToken::Comment("// one"),
+        JsonReadToken::NumberLiteral(JsonNumber("2")),
+        JsonReadToken::EndArray,
+        JsonReadToken::Comment("// trailing"),
+    ])]
+    #[case::block_comments("{/* a */\"a\": /* b\n c */ 1}", vec![
+        JsonReadToken::StartObject,
+        JsonReadToken::Comment("/* a */"),
+        JsonReadToken::Key("a"),
+        JsonReadToken::Comment("/* b\n c */"),
+        JsonReadToken::NumberLiteral(JsonNumber("1")),
+        JsonReadToken::EndObject,
+    ])]
+    #[case::comments_between_key_and_colon("{\"a\" /* x */ // y\r\n: 1}", vec![
+        JsonReadToken::StartObject,
+        JsonReadToken::Key("a"),
+        JsonReadToken::Comment("/* x */"),
+        JsonReadToken::Comment("// y"),
+        JsonReadToken::NumberLiteral(JsonNumber("1")),
+        JsonReadToken::EndObject,
+    ])]
+    #[case::comments_after_values("[\"a\" /*c1*/, 1 /*c2*/, \"b\" // c3\n]", vec![
+        JsonReadToken::StartArray,
+        JsonReadToken::StringLiteral("a"),
+        JsonReadToken::Comment("/*c1*/"),
+        JsonReadToken::NumberLiteral(JsonNumber("1")),
+        JsonReadToken::Comment("/*c2*/"),
+        JsonReadToken::StringLiteral("b"),
+        JsonReadToken::Comment("// c3"),
+        JsonReadToken::EndArray,
+    ])]
+    #[case::empty_comments("[//\n/**/]", vec![
+        JsonReadToken::StartArray,
+        JsonReadToken::Comment("//"),
+        JsonReadToken::Comment("/**/"),
+        JsonReadToken::EndArray,
+    ])]
+    fn test_comment_tokens(#[case] json: &str, #[case] expected: Vec<JsonReadToken<'static>>) {
+        let mut expected = expected.iter()
+            .map(|t| format!("{:?}", t))
+            .collect::<Vec<_>>();
+        expected.push(format!("{:?}", JsonReadToken::EndOfStream));
+
+        for read_buf_size in [1, 3, 64] {
+            let mut r = BufferedRead::new(Cursor::new(json.as_bytes()), vec![0u8; read_buf_size]);
+            let actual = read_token_strings(&mut JsonReader::new(64, &mut r).with_comment_tokens()).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_comment_tokens_expect() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(b"/* a */{/* b */\"a\": /* c */[1, // d\n\"x\" /* e */], \"b\": /* f */ {\"c\": [/* g */]}, \"d\": /* h */ \"y\"} // i".to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r).with_comment_tokens();
+        assert_eq!(json_reader.next()?, JsonReadToken::Comment("/* a */"));
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.expect_key()?, Some("a"));
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::StartArray);
+        json_reader.expect_start_array()?;
+        assert_eq!(json_reader.expect_number::<u32>()?, 1);
+        assert_eq!(json_reader.next()?, JsonReadToken::Comment("// d"));
+        let mut chunks = String::new();
+        json_reader.expect_string_chunked(|chunk| chunks.push_str(chunk))?;
+        assert_eq!(chunks, "x");
+        json_reader.expect_end_array()?;
+        assert_eq!(json_reader.expect_key()?, Some("b"));
+        json_reader.skip_value()?;
+        assert_eq!(json_reader.expect_key()?, Some("d"));
+        let mut chunks = String::new();
+        json_reader.expect_string_chunked(|chunk| chunks.push_str(chunk))?;
+        assert_eq!(chunks, "y");
+        assert_eq!(json_reader.expect_key()?, None);
+        json_reader.expect_end_of_stream()
+    }
+
+    #[rstest]
+    #[case::unterminated("[1 /* abc *", Location { offset: 11, line: 1, column: 12 })]
+    #[case::unterminated_multi_line("[1,\n/* a\nb", Location { offset: 10, line: 3, column: 2 })]
+    #[case::invalid_comment("[1 / 2]", Location { offset: 5, line: 1, column: 6 })]
+    fn test_comments_fail(#[case] json: &str, #[case] expected_location: Location) {
+        for comment_tokens in [false, true] {
+            let mut r = Cursor::new(json.as_bytes());
+            let json_reader = JsonReader::new(64, &mut r);
+            let mut json_reader = if comment_tokens { json_reader.with_comment_tokens() } else { json_reader.with_comments() };
+            match read_token_strings(&mut json_reader) {
+                Err(JsonParseError::Parse(_, location, _)) => assert_eq!(location, expected_location),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::line_comment("[1 // one\n]")]
+    #[case::block_comment("[1 /* one */]")]
+    fn test_comments_rejected_without_option(#[case] json: &str) {
+        let mut r = Cursor::new(json.as_bytes());
+        assert!(read_token_strings(&mut JsonReader::new(64, &mut r)).is_err());
+    }
+
+    #[rstest]
+    #[case::trailing_comma("[1,]")]
+    #[case::single_quotes("['a']")]
+    #[case::unquoted_key("{a: 1}")]
+    fn test_comments_without_json5(#[case] json: &str) {
+        let mut r = Cursor::new(json.as_bytes());
+        assert!(read_token_strings(&mut JsonReader::new(64, &mut r).with_comments()).is_err());
+    }
+
+    #[rstest]
+    #[case::no_duplicates(r#"{"a": 1, "b": {"a": 2, "b": [{"a": 3}, {"a": 4}]}, "c": {"a": 5}}"#, None)]
+    #[case::flat(r#"{"a": 1, "b": 2, "a": 3}"#, Some(Location { offset: 17, line: 1, column: 18 }))]
+    #[case::nested(r#"{"a": {"b": 1, "b": 2}}"#, Some(Location { offset: 15, line: 1, column: 16 }))]
+    #[case::after_nested(r#"{"a": {"b": 1}, "a": 2}"#, Some(Location { offset: 16, line: 1, column: 17 }))]
+    #[case::in_array(r#"[{"a": 1}, {"a": 1, "a": 2}]"#, Some(Location { offset: 20, line: 1, column: 21 }))]
+    #[case::escaped(r#"{"a": 1, "\u0061": 2}"#, Some(Location { offset: 9, line: 1, column: 10 }))]
+    #[case::multi_line("{\n\"a\": 1,\n\"a\": 2}", Some(Location { offset: 10, line: 3, column: 1 }))]
+    #[case::json5_unquoted("{a: 1, a: 2}", Some(Location { offset: 7, line: 1, column: 8 }))]
+    #[case::json5_single_quotes("{a: 1, 'a': 2}", Some(Location { offset: 7, line: 1, column: 8 }))]
+    fn test_duplicate_keys(#[case] json: &str, #[case] expected: Option<Location>) {
+        for use_key_table in [false, true] {
+            let mut r = Cursor::new(json.as_bytes());
+            let json_reader = JsonReader::new(64, &mut r).with_json5();
+            let mut json_reader = if use_key_table {
+                json_reader.with_provided_key_table(vec![0u8; 256], 17)
+            }
+            else {
+                json_reader.with_duplicate_key_detection()
+            };
+            match (read_token_strings(&mut json_reader), expected) {
+                (Ok(_), None) => {}
+                (Err(JsonParseError::Parse(ParseErrorKind::DuplicateKey, location, _)), Some(expected)) => assert_eq!(location, expected),
+                (other, _) => panic!("unexpected result: {:?}", other),
+            }
+        }
+
+        let mut r = Cursor::new(json.as_bytes());
+        assert!(read_token_strings(&mut JsonReader::new(64, &mut r).with_json5()).is_ok());
+    }
+
+    #[rstest]
+    #[case::enough_slots(r#"{"a": {"b": 1}, "c": 2}"#, 96, true)]
+    #[case::too_few_slots(r#"{"a": {"b": 1}, "c": 2}"#, 95, false)]
+    #[case::closed_objects_are_released(r#"[{"a": 1, "b": 2}, {"c": 3, "d": 4}]"#, 72, true)]
+    #[case::empty(r#"{}"#, 0, false)]
+    fn test_key_table_size(#[case] json: &str, #[case] key_table_size: usize, #[case] expected_ok: bool) {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r).with_provided_key_table(vec![0u8; key_table_size], 17);
+        match read_token_strings(&mut json_reader) {
+            Ok(_) => assert!(expected_ok),
+            Err(JsonParseError::BufferOverflow(_)) => assert!(!expected_ok),
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[rstest]
+    #[case::no_duplicates(None)]
+    #[case::duplicate(Some("k17"))]
+    fn test_key_table_many_keys(#[case] duplicate: Option<&str>) {
+        let keys = (0..200).map(|i| format!(r#""k{}": {{"k{}": 1, "x": 2}}"#, i, i)).collect::<Vec<_>>();
+        let mut json = format!("{{{}", keys.join(", "));
+        if let Some(key) = duplicate {
+            json.push_str(&format!(r#", "{}": 3"#, key));
+        }
+        json.push('}');
+
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r).with_provided_key_table(vec![0u8; 24 * 204], 17);
+        match (read_token_strings(&mut json_reader), duplicate) {
+            (Ok(_), None) => {}
+            (Err(JsonParseError::Parse(ParseErrorKind::DuplicateKey, location, _)), Some(_)) => assert_eq!(location.offset, json.len() - 9),
+            (other, _) => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_key_path() {
+        let mut r = Cursor::new(br#"{"a": [{"b": 1, "b": 2}]}"#.to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r).with_path_tracking(64).with_duplicate_key_detection();
+        match read_token_strings(&mut json_reader) {
+            Err(JsonParseError::Parse(_, _, Some(path))) => assert_eq!(path.to_string(), "/a/0/b"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[rstest]
+    #[case::single_line("[1, 2, 3 4]", 64, false, "parse error: missing comma @ line 1, column 11 (offset 10)\n[1, 2, 3 4\n          ^")]
+    #[case::multi_line("{\n  \"a\": tru\n}", 64, false, "parse error: incomplete true literal @ line 3, column 1 (offset 13)\n  \"a\": tru\n          ^")]
+    #[case::with_path("{\n  \"a\": tru\n}", 64, true, "parse error: incomplete true literal @ line 3, column 1 (offset 13) (path /a)\n  \"a\": tru\n          ^")]
+    #[case::cut_off("[100, 200, 300, 400 500]", 8, false, "parse error: missing comma @ line 1, column 22 (offset 21)\n...0, 400 5\n           ^")]
+    #[case::tab("[1,\t2\t3]", 64, false, "parse error: missing comma @ line 1, column 8 (offset 7)\n[1,\t2\t3\n   \t \t ^")]
+    #[case::non_ascii(r#"["äöü" 1]"#, 64, false, "parse error: missing comma @ line 1, column 12 (offset 11)\n[\"äöü\" 1\n        ^")]
+    #[case::no_context("[1, 2, 3 4]", 0, false, "parse error: missing comma @ line 1, column 11 (offset 10)")]
+    #[case::string_cut_off(r#"{"a": 1, "abcdefghijklmnop" 2}"#, 4, false, "parse error: object member without a key @ line 1, column 30 (offset 29)\n...p\" 2\n       ^")]
+    fn test_error_report(#[case] json: &str, #[case] context_size: usize, #[case] track_path: bool, #[case] expected: &str) {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        if context_size > 0 {
+            json_reader = json_reader.with_error_context(context_size);
+        }
+        if track_path {
+            json_reader = json_reader.with_path_tracking(64);
+        }
+        let error = read_token_strings(&mut json_reader).unwrap_err();
+        assert_eq!(json_reader.error_report(&error).to_string(), expected);
+    }
+
+    #[test]
+    fn test_error_report_expect() {
+        let mut r = Cursor::new(br#"{"a": "x"}"#.to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r).with_error_context(64);
+        json_reader.expect_start_object().unwrap();
+        json_reader.expect_key().unwrap();
+        let error = json_reader.expect_number::<u32>().unwrap_err();
+        assert_eq!(json_reader.error_report(&error).to_string(), "parse error: expected number, found string @ line 1, column 6 (offset 5)\n{\"a\": \"x\"}\n     ^");
+    }
+
+
+    #[rstest]
+    #[case::tab("\"a\tb\"", false)]
+    #[case::newline("\"a\nb\"", false)]
+    #[case::nul("\"a\u{0}b\"", false)]
+    #[case::escaped(r#""a\tb""#, true)]
+    #[case::delete("\"a\u{7f}b\"", true)]
+    fn test_control_character_check(#[case] json: &str, #[case] expected_ok: bool) {
+        for read_buf_size in [1, 64] {
+            let mut r = BufferedRead::new(Cursor::new(json.as_bytes()), vec![0u8; read_buf_size]);
+            let mut json_reader = JsonReader::new(64, &mut r).with_control_character_check();
+            match json_reader.expect_string() {
+                Ok(_) => assert!(expected_ok),
+                Err(JsonParseError::Parse(ParseErrorKind::ControlCharacterInString, location, _)) => {
+                    assert!(!expected_ok);
+                    assert_eq!(location.offset, 3);
+                }
+                Err(e) => panic!("unexpected error {:?}", e),
+            }
+        }
+
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        assert!(json_reader.expect_string().is_ok());
+    }
+
+    #[rstest]
+    #[case::object("{\"a\": [1, 2.5, {\"b\": null}], \"c\": \"x\"}", false)]
+    #[case::array("[1, [true, [false]], -3e2, \"y\"]", false)]
+    #[case::multi_line("{\n  \"a\": 1,\n  \"b\": [\n    2\n  ]\n}\n", false)]
+    #[case::lenient("{\"a\": 1}\n{\"a\": 2}\n[3]\n", true)]
+    fn test_checkpoint(#[case] json: &str, #[case] lenient_comma_handling: bool) -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let expected = read_token_strings(&mut JsonReader::new_with_provided_buffer(vec![0u8; 64], &mut r, lenient_comma_handling))?;
+
+        for num_tokens in 0..expected.len() {
+            let mut r = Cursor::new(json.as_bytes().to_vec());
+            let mut json_reader = JsonReader::new_with_provided_buffer(vec![0u8; 64], &mut r, lenient_comma_handling);
+            let mut tokens = Vec::new();
+            for _ in 0..num_tokens {
+                tokens.push(format!("{:?}", json_reader.next()?));
+            }
+
+            let mut checkpoint = json_reader.checkpoint().unwrap();
+            let mut encoded = vec![0u8; checkpoint.encoded_len()];
+            checkpoint.encode(&mut encoded).unwrap();
+            let checkpoint = ReaderCheckpoint::decode(&encoded, default_nesting_buffer()).unwrap();
+            assert_eq!(checkpoint.location, json_reader.location());
+            assert_eq!(checkpoint.depth(), json_reader.depth());
+
+            let mut r = Cursor::new(json.as_bytes()[checkpoint.location.offset..].to_vec());
+            let mut json_reader = JsonReader::new_from_checkpoint(64, checkpoint, &mut r);
+            tokens.extend(read_token_strings(&mut json_reader)?);
+            assert_eq!(tokens, expected);
+            assert_eq!(json_reader.location().offset, json.len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_checkpoint_after_peek() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(b"[1, 2]".to_vec());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array()?;
+        json_reader.peek_kind()?;
+        assert!(json_reader.checkpoint().is_none());
+        json_reader.expect_number::<u8>()?;
+        assert!(json_reader.checkpoint().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_checkpoint_resumed_object() -> JsonParseResult<(), io::Error> {
+        let json = r#"{"a": 1, "a": {"b": 2, "b": 3}} [4]"#;
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let mut json_reader = JsonReader::new_with_lenient_comma_handling(64, &mut r);
+        json_reader.expect_start_object()?;
+        let checkpoint = json_reader.checkpoint().unwrap();
+
+        let mut r = Cursor::new(json.as_bytes()[checkpoint.location.offset..].to_vec());
+        let mut json_reader = JsonReader::new_from_checkpoint(64, checkpoint, &mut r)
+            .with_path_tracking(64)
+            .with_duplicate_key_detection();
+        assert_eq!(json_reader.current_container(), Some(ContainerKind::Object));
+
+        // keys of objects that were open at the checkpoint are unknown
+        assert_eq!(json_reader.expect_key()?, Some("a"));
+        assert!(json_reader.current_path().is_none());
+        json_reader.expect_number::<u8>()?;
+        assert_eq!(json_reader.expect_key()?, Some("a"));
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.expect_key()?, Some("b"));
+        json_reader.expect_number::<u8>()?;
+        match json_reader.expect_key() {
+            Err(JsonParseError::Parse(ParseErrorKind::DuplicateKey, location, None)) => assert_eq!(location.offset, 23),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        json_reader.expect_number::<u8>()?;
+        json_reader.expect_end_object()?;
+        json_reader.expect_end_object()?;
+
+        // the path is tracked again at the top level
+        json_reader.expect_start_array()?;
+        json_reader.expect_number::<u8>()?;
+        assert_eq!(json_reader.current_path().unwrap().to_string(), "/0");
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::empty("[]", vec![])]
+    #[case::scalars("[1,true, null ,\"a\"]", vec![1, 3, 9, 15])]
+    #[case::nested(r#"[{"a": [1, 2]}, [[]], {}]"#, vec![1, 16, 22])]
+    #[case::multi_line("[\n  {\"a\": 1},\n  {\"a\": 2}\n]\n", vec![4, 16])]
+    fn test_index_array_elements(#[case] json: &str, #[case] expected_offsets: Vec<usize>) -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let index = JsonReader::new(64, &mut r).index_array_elements()?;
+        assert_eq!(index.locations().iter().map(|l| l.offset).collect::<Vec<_>>(), expected_offsets);
+
+        for n in 0..index.len() {
+            let mut json_reader = JsonReader::new_at_array_element(64, &index, n, &mut r)?;
+            assert_eq!(json_reader.location(), index.location(n).unwrap());
+            for _ in n..index.len() {
+                json_reader.skip_value()?;
+            }
+            json_reader.expect_end_array()?;
+            json_reader.expect_end_of_stream()?;
+        }
+
+        match JsonReader::new_at_array_element(64, &index, index.len(), &mut r) {
+            Err(e) => assert_eq!(e.kind(), io::ErrorKind::InvalidInput),
+            Ok(_) => panic!("expected an error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_array_elements_location() -> JsonParseResult<(), io::Error> {
+        let json = "[\n  \"a\",\n  \"b\"\n]";
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let index = JsonReader::new(64, &mut r).index_array_elements()?;
+        assert_eq!(index.location(1), Some(Location { offset: 11, line: 3, column: 3 }));
+
+        let mut json_reader = JsonReader::new_at_array_element(64, &index, 1, &mut r)?;
+        assert_eq!(json_reader.expect_string()?, "b");
+        assert_eq!(json_reader.expect_string_or_end_array()?, None);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::not_an_array("{}")]
+    #[case::unterminated("[1, 2")]
+    #[case::invalid_element("[1, x]")]
+    fn test_index_array_elements_fails(#[case] json: &str) {
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        assert!(JsonReader::new(64, &mut r).index_array_elements().is_err());
+    }
 
     #[rstest]
     #[case::simple(Location::start(), vec![b'a'], Location { offset: 1, line: 1, column: 2,})]
@@ -1056,6 +2543,22 @@ mod tests {
         assert_eq!(initial, expected);
     }
 
+    #[rstest]
+    #[case::empty(b"")]
+    #[case::simple(b"abc")]
+    #[case::nl(b"\n")]
+    #[case::in_line(b"\r\n\nxy")]
+    #[case::trailing_nl(b"ab\nx\n")]
+    fn test_location_after_bytes(#[case] bytes: &[u8]) {
+        let mut initial = Location { offset: 3, line: 2, column: 4 };
+        let mut expected = initial;
+        for &byte in bytes {
+            expected.after_byte(byte);
+        }
+        initial.after_bytes(bytes);
+        assert_eq!(initial, expected);
+    }
+
 
     #[rstest]
     #[case::key(r#""abc": null"#, Some(Some("abc")))]
@@ -1069,28 +2572,30 @@ mod tests {
     #[case::start_array(r#"["#, None)]
     #[case::end_array(r#"]"#, None)]
     fn test_expect_key(#[case] json: &str, #[case] expected: Option<Option<&str>>) {
+        let json = format!("{{{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_object().unwrap();
         match json_reader.expect_key() {
             Ok(actual) => assert_eq!(actual, expected.unwrap()),
-            Err(JsonParseError::Parse(_, _)) => assert!(expected.is_none()),
+            Err(JsonParseError::Parse(_, _, _)) => assert!(expected.is_none()),
             Err(e) => panic!("unexpected error: {}", e)
         }
     }
 
     #[rstest]
     #[case::simple("1", Ok(1))]
-    #[case::other_number("500", Err(JsonParseError::Parse("invalid number", Location::start())))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"abc\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::other_number("500", Err(JsonParseError::Parse(ParseErrorKind::InvalidNumber, Location::start(), None)))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::string("\"abc\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_number(#[case] json: &str, #[case] expected_num: JsonParseResult<u8, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1105,20 +2610,22 @@ mod tests {
 
     #[rstest]
     #[case::simple("1", Ok(Some(1)))]
-    #[case::other_number("500", Err(JsonParseError::Parse("invalid number", Location::start())))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"abc\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::other_number("500", Err(JsonParseError::Parse(ParseErrorKind::InvalidNumber, Location::start(), None)))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::string("\"abc\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_number_or_end_array(#[case] json: &str, #[case] expected_num: JsonParseResult<Option<u8>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_number_or_end_array::<u8>() {
             Ok(n) => assert_eq!(n, expected_num.unwrap()),
             Err(act_e) => match expected_num {
@@ -1130,17 +2637,17 @@ mod tests {
 
     #[rstest]
     #[case::simple("1", Ok(Some(1)))]
-    #[case::other_number("500", Err(JsonParseError::Parse("invalid number", Location::start())))]
+    #[case::other_number("500", Err(JsonParseError::Parse(ParseErrorKind::InvalidNumber, Location::start(), None)))]
     #[case::null("null", Ok(None))]
-    #[case::string("\"abc\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"abc\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_number(#[case] json: &str, #[case] expected_num: JsonParseResult<Option<u8>, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1155,20 +2662,22 @@ mod tests {
 
     #[rstest]
     #[case::simple("1", Ok(Some(Some(1))))]
-    #[case::other_number("500", Err(JsonParseError::Parse("invalid number", Location::start())))]
+    #[case::other_number("500", Err(JsonParseError::Parse(ParseErrorKind::InvalidNumber, Location::start(), None)))]
     #[case::null("null", Ok(Some(None)))]
-    #[case::string("\"abc\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"abc\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_number_or_end_array(#[case] json: &str, #[case] expected_num: JsonParseResult<Option<Option<u8>>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_opt_number_or_end_array::<u8>() {
             Ok(n) => assert_eq!(n, expected_num.unwrap()),
             Err(act_e) => match expected_num {
@@ -1180,16 +2689,16 @@ mod tests {
 
     #[rstest]
     #[case::simple(" 123.45 ", Ok(JsonNumber("123.45")))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"abc\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::string("\"abc\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_raw_number(#[case] json: &str, #[case] expected_num: JsonParseResult<JsonNumber, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1204,19 +2713,21 @@ mod tests {
 
     #[rstest]
     #[case::simple(" 123.45 ", Ok(Some(JsonNumber("123.45"))))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"abc\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::string("\"abc\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_raw_number_or_end_array(#[case] json: &str, #[case] expected_num: JsonParseResult<Option<JsonNumber>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_raw_number_or_end_array() {
             Ok(n) => assert_eq!(n, expected_num.unwrap()),
             Err(act_e) => match expected_num {
@@ -1229,10 +2740,10 @@ mod tests {
     #[rstest]
     #[case::number(" 5 ", Ok(Some(JsonNumber("5"))))]
     #[case::null(" null ", Ok(None))]
-    #[case::boolean(" ] ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::boolean(" true ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::boolean(" ] ", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::boolean(" true ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_raw_number(#[case] json: &str, #[case] expected_num: JsonParseResult<Option<JsonNumber>, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1249,12 +2760,14 @@ mod tests {
     #[case::number(" 5 ", Ok(Some(Some(JsonNumber("5")))))]
     #[case::null(" null ", Ok(Some(None)))]
     #[case::null(" ] ", Ok(None))]
-    #[case::boolean(" true ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::boolean(" true ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::NumberLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_raw_number_or_end_array(#[case] json: &str, #[case] expected_num: JsonParseResult<Option<Option<JsonNumber>>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_opt_raw_number_or_end_array() {
             Ok(o) => assert_eq!(o, expected_num.unwrap()),
             Err(act_e) => match expected_num {
@@ -1266,16 +2779,16 @@ mod tests {
 
     #[rstest]
     #[case::simple("\"qrs\"", Ok("qrs"))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_string(#[case] json: &str, #[case] expected: JsonParseResult<&str, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1290,19 +2803,21 @@ mod tests {
 
     #[rstest]
     #[case::simple("\"qrs\"", Ok(Some("qrs")))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_string_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<&str>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_string_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1315,15 +2830,15 @@ mod tests {
     #[rstest]
     #[case::simple("\"rst\"", Ok(Some("rst")))]
     #[case::null("null", Ok(None))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_string(#[case] json: &str, #[case] expected: JsonParseResult<Option<&str>, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1339,18 +2854,20 @@ mod tests {
     #[rstest]
     #[case::simple("\"rst\"", Ok(Some(Some("rst"))))]
     #[case::null("null", Ok(Some(None)))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StringLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_string_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<Option<&str>>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_opt_string_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1363,16 +2880,16 @@ mod tests {
     #[rstest]
     #[case::bool_true("true", Ok(true))]
     #[case::bool_false("false", Ok(false))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_bool(#[case] json: &str, #[case] expected: JsonParseResult<bool, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1388,19 +2905,21 @@ mod tests {
     #[rstest]
     #[case::bool_true("true", Ok(Some(true)))]
     #[case::bool_false("false", Ok(Some(false)))]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_bool_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<bool>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_bool_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1414,15 +2933,15 @@ mod tests {
     #[case::bool_true("true", Ok(Some(true)))]
     #[case::bool_false("false", Ok(Some(false)))]
     #[case::null("null", Ok(None))]
-    #[case::string("\"x\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"x\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_bool(#[case] json: &str, #[case] expected: JsonParseResult<Option<bool>, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1439,18 +2958,20 @@ mod tests {
     #[case::bool_true("true", Ok(Some(Some(true))))]
     #[case::bool_false("false", Ok(Some(Some(false))))]
     #[case::null("null", Ok(Some(None)))]
-    #[case::string("\"x\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"x\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::BooleanLiteral, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_bool_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<Option<bool>>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_opt_bool_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1461,17 +2982,17 @@ mod tests {
     }
 
     #[rstest]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
     #[case::start_object("{", Ok(()))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_start_object(#[case] json: &str, #[case] expected: JsonParseResult<(), io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1485,20 +3006,22 @@ mod tests {
     }
 
     #[rstest]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
     #[case::start_object("{", Ok(Some(())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_start_object_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<()>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_start_object_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1509,20 +3032,22 @@ mod tests {
     }
 
     #[rstest]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndObject], found: JsonReadTokenKind::Key }, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
     #[case::end_object("}", Ok(()))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::MemberWithoutKey, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_end_object(#[case] json: &str, #[case] expected: JsonParseResult<(), io::Error>) {
+        let json = format!("{{{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_object().unwrap();
         match json_reader.expect_end_object() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1533,17 +3058,17 @@ mod tests {
     }
 
     #[rstest]
-    #[case::bool("false", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::bool("false", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
     #[case::null("null", Ok(None))]
-    #[case::string("\"x\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"x\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
     #[case::start_object("{", Ok(Some(())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_start_object(#[case] json: &str, #[case] expected: JsonParseResult<Option<()>, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1557,20 +3082,22 @@ mod tests {
     }
 
     #[rstest]
-    #[case::bool("false", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::bool("false", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
     #[case::null("null", Ok(Some(None)))]
-    #[case::string("\"x\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"x\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
     #[case::start_object("{", Ok(Some(Some(()))))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartObject, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_start_object_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<Option<()>>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_opt_start_object_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1581,17 +3108,17 @@ mod tests {
     }
 
     #[rstest]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
     #[case::start_array("[", Ok(()))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_start_array(#[case] json: &str, #[case] expected: JsonParseResult<(), io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1605,20 +3132,22 @@ mod tests {
     }
 
     #[rstest]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
     #[case::start_array("[", Ok(Some(())))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_start_array_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<()>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_start_array_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1629,20 +3158,22 @@ mod tests {
     }
 
     #[rstest]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
     #[case::end_array("]", Ok(()))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_end_array(#[case] json: &str, #[case] expected: JsonParseResult<(), io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1653,17 +3184,17 @@ mod tests {
     }
 
     #[rstest]
-    #[case::bool("false", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::bool("false", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
     #[case::null("null", Ok(None))]
-    #[case::string("\"x\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"x\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
     #[case::start_array("[", Ok(Some(())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_start_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<()>, io::Error>) {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
@@ -1677,20 +3208,22 @@ mod tests {
     }
 
     #[rstest]
-    #[case::bool("false", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::bool("false", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
     #[case::null("null", Ok(Some(None)))]
-    #[case::string("\"x\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::string("\"x\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::NullLiteral, JsonReadTokenKind::StartArray, JsonReadTokenKind::EndArray], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
     #[case::start_array("[", Ok(Some(Some(()))))]
     #[case::end_array("]", Ok(None))]
-    #[case::end_array("", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array(" ", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::end_array("", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
+    #[case::end_array(" ", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedEndOfInput, Location::start(), None)))]
     fn test_expect_opt_start_array_or_end_array(#[case] json: &str, #[case] expected: JsonParseResult<Option<Option<()>>, io::Error>) {
+        let json = format!("[{}", json);
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        json_reader.expect_start_array().unwrap();
         match json_reader.expect_opt_start_array_or_end_array() {
             Ok(n) => assert_eq!(n, expected.unwrap()),
             Err(act_e) => match expected {
@@ -1701,15 +3234,15 @@ mod tests {
     }
 
     #[rstest]
-    #[case::null("null", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::bool("true", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::string("\"a\"", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::number("12", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::key("\"abc\": ", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_object("{", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_object("}", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::start_array("[", Err(JsonParseError::Parse("", Location::start())))]
-    #[case::end_array("]", Err(JsonParseError::Parse("", Location::start())))]
+    #[case::null("null", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndOfStream], found: JsonReadTokenKind::NullLiteral }, Location::start(), None)))]
+    #[case::bool("true", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndOfStream], found: JsonReadTokenKind::BooleanLiteral }, Location::start(), None)))]
+    #[case::string("\"a\"", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndOfStream], found: JsonReadTokenKind::StringLiteral }, Location::start(), None)))]
+    #[case::number("12", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndOfStream], found: JsonReadTokenKind::NumberLiteral }, Location::start(), None)))]
+    #[case::key("\"abc\": ", Err(JsonParseError::Parse(ParseErrorKind::KeyOutsideOfObject, Location::start(), None)))]
+    #[case::start_object("{", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndOfStream], found: JsonReadTokenKind::StartObject }, Location::start(), None)))]
+    #[case::end_object("}", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndObject, Location::start(), None)))]
+    #[case::start_array("[", Err(JsonParseError::Parse(ParseErrorKind::UnexpectedToken { expected: &[JsonReadTokenKind::EndOfStream], found: JsonReadTokenKind::StartArray }, Location::start(), None)))]
+    #[case::end_array("]", Err(JsonParseError::Parse(ParseErrorKind::MismatchedEndArray, Location::start(), None)))]
     #[case::end_array("", Ok(()))]
     #[case::end_array(" ", Ok(()))]
     fn test_expect_end_of_stream(#[case] json: &str, #[case] expected: JsonParseResult<(), io::Error>) {
@@ -1759,18 +3292,22 @@ mod tests {
     }
 
     #[rstest]
-    #[case::end_object_empty("}, 77", false)]
-    #[case::end_object_simple(r#" "a": 10, "b": null }, 77"#, false)]
-    #[case::end_object_nested(r#" "a": 10, "x": { "q": 99, "r": [1, 2, 3] }, "b": null }, 77"#, false)]
-    #[case::end_array_empty("], 77", false)]
-    #[case::end_array_simple(r#"99, "abc", null, true], 77"#, false)]
-    #[case::end_array_nested(r#"99, [1, [], true, {"abc": { "xyz": [1, 2, 3]}}], "abc", null, true], 77"#, false)]
+    #[case::end_object_empty("{}, 77", false)]
+    #[case::end_object_simple(r#"{ "a": 10, "b": null }, 77"#, false)]
+    #[case::end_object_nested(r#"{ "a": 10, "x": { "q": 99, "r": [1, 2, 3] }, "b": null }, 77"#, false)]
+    #[case::end_array_empty("[], 77", false)]
+    #[case::end_array_simple(r#"[99, "abc", null, true], 77"#, false)]
+    #[case::end_array_nested(r#"[99, [1, [], true, {"abc": { "xyz": [1, 2, 3]}}], "abc", null, true], 77"#, false)]
     #[case::number("1, 77", true)]
     #[case::boolean("true, 77", true)]
     #[case::null("null, 77", true)]
     fn test_skip_to_end_of_current_scope(#[case] json: &str, #[case] should_fail: bool) -> JsonParseResult<(), io::Error> {
         let mut r = Cursor::new(json.as_bytes());
         let mut json_reader = JsonReader::new(64, &mut r);
+        // position the reader inside the object or array that is skipped
+        if json.starts_with(['{', '[']) {
+            json_reader.next()?;
+        }
         match json_reader.skip_to_end_of_current_scope() {
             Ok(_) => {
                 assert!(!should_fail);
@@ -0,0 +1,227 @@
+use crate::blocking::io::BlockingWrite;
+use crate::blocking::json_writer::JsonWriter;
+use crate::shared::*;
+use core::fmt::Display;
+
+/// The blocking [JsonWriter] is written to directly during serialization
+impl<'a, W: BlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonSink for JsonWriter<'a, W, F, FF> {
+    type Error = W::Error;
+
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), W::Error> {
+        JsonWriter::write_bytes(self, data)
+    }
+
+    fn write_escaped_string(&mut self, s: &str) -> Result<(), W::Error> {
+        JsonWriter::write_escaped_string(self, s)
+    }
+
+    fn write_f64(&mut self, value: f64) -> Result<(), W::Error> {
+        JsonWriter::write_f64(self, value)
+    }
+
+    fn write_f32(&mut self, value: f32) -> Result<(), W::Error> {
+        JsonWriter::write_f32(self, value)
+    }
+
+    fn write_raw_num(&mut self, value: impl Display) -> Result<(), W::Error> {
+        JsonWriter::write_raw_num(self, value)
+    }
+
+    fn write_format_after_key(&mut self) -> Result<(), W::Error> {
+        JsonWriter::write_format_after_key(self)
+    }
+
+    fn write_format_after_start_nested(&mut self) -> Result<(), W::Error> {
+        JsonWriter::write_format_after_start_nested(self)
+    }
+
+    fn write_format_after_element(&mut self) -> Result<(), W::Error> {
+        JsonWriter::write_format_after_element(self)
+    }
+
+    fn write_format_before_end_nested(&mut self, is_empty: bool) -> Result<(), W::Error> {
+        JsonWriter::write_format_before_end_nested(self, is_empty)
+    }
+
+    fn write_format_indent(&mut self) -> Result<(), W::Error> {
+        JsonWriter::write_format_indent(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blocking::array::JsonArray;
+    use crate::blocking::json_writer::JsonWriter;
+    use crate::blocking::object::JsonObject;
+    use crate::blocking::read::JsonReader;
+    use crate::shared::*;
+    use rstest::*;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+    use std::fmt::Write;
+    use std::io;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Serialize)]
+    struct Wrapper(u8);
+
+    #[derive(Serialize)]
+    struct Pair(u8, bool);
+
+    #[derive(Serialize)]
+    struct Empty {}
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Line(Point, Point),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[rstest]
+    #[case::bool(true, "true")]
+    #[case::i8(-3i8, "-3")]
+    #[case::u128(u128::MAX, "340282366920938463463374607431768211455")]
+    #[case::f64(1.5f64, "1.5")]
+    #[case::f64_exp(1e10f64, "1e10")]
+    #[case::f32_nan(f32::NAN, "null")]
+    #[case::char('"', "\"\\\"\"")]
+    #[case::str("a\nb\u{1}", "\"a\\nb\\u0001\"")]
+    #[case::none(None::<u8>, "null")]
+    #[case::some(Some(2u8), "2")]
+    #[case::unit((), "null")]
+    #[case::vec(vec![1, 2], "[1,2]")]
+    #[case::empty_vec(Vec::<u8>::new(), "[]")]
+    #[case::bytes(serde_bytes_like(), "[1,2]")]
+    #[case::tuple((1, "a"), "[1,\"a\"]")]
+    #[case::newtype_struct(Wrapper(5), "5")]
+    #[case::tuple_struct(Pair(1, false), "[1,false]")]
+    #[case::struct_(Point { x: 1, y: -2 }, "{\"x\":1,\"y\":-2}")]
+    #[case::empty_struct(Empty {}, "{}")]
+    #[case::map(BTreeMap::from([("b", 2), ("a", 1)]), "{\"a\":1,\"b\":2}")]
+    #[case::int_keys(BTreeMap::from([(2, "x"), (1, "y")]), "{\"1\":\"y\",\"2\":\"x\"}")]
+    #[case::unit_variant(Shape::Empty, "\"Empty\"")]
+    #[case::newtype_variant(Shape::Circle(1.5), "{\"Circle\":1.5}")]
+    #[case::tuple_variant(Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 }), "{\"Line\":[{\"x\":0,\"y\":0},{\"x\":1,\"y\":1}]}")]
+    #[case::struct_variant(Shape::Rect { width: 3, height: 4 }, "{\"Rect\":{\"width\":3,\"height\":4}}")]
+    fn test_write_serialize<T: Serialize>(#[case] value: T, #[case] expected: &str) -> Result<(), JsonSerializeError<io::Error>> {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_compact(&mut buf);
+        {
+            let mut o = JsonObject::new(&mut writer)?;
+            o.write_serialize("k", &value)?;
+            let mut a = o.start_array("a")?;
+            a.write_serialize(&value)?;
+            a.write_serialize(&value)?;
+        }
+        writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), format!("{{\"k\":{},\"a\":[{},{}]}}", expected, expected, expected));
+        Ok(())
+    }
+
+    fn serde_bytes_like() -> impl Serialize {
+        struct Bytes;
+        impl Serialize for Bytes {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&[1, 2])
+            }
+        }
+        Bytes
+    }
+
+    #[test]
+    fn test_write_serialize_pretty() -> Result<(), JsonSerializeError<io::Error>> {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_pretty(&mut buf);
+        {
+            let mut o = JsonObject::new(&mut writer)?;
+            o.write_serialize("shapes", &vec![Shape::Empty, Shape::Rect { width: 3, height: 4 }])?;
+            o.write_serialize("empty", &Vec::<u8>::new())?;
+            o.write_u8_value("after", 1)?;
+        }
+        writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), r#"{
+  "shapes": [
+    "Empty",
+    {
+      "Rect": {
+        "width": 3,
+        "height": 4
+      }
+    }
+  ],
+  "empty": [],
+  "after": 1
+}"#);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_serialize_float_format() -> Result<(), JsonSerializeError<io::Error>> {
+        struct OtherFf;
+        impl FloatFormat for OtherFf {
+            fn write_f64(f: &mut impl Write, value: f64) -> std::fmt::Result {
+                write!(f, "_{}_64", value)
+            }
+
+            fn write_f32(f: &mut impl Write, value: f32) -> std::fmt::Result {
+                write!(f, "_{}_32", value)
+            }
+        }
+
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new(&mut buf, CompactFormatter, OtherFf);
+        JsonArray::new(&mut writer)?.write_serialize(&(1.2f64, 3.4f32))?;
+        writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), "[[_1.2_64,_3.4_32]]");
+        Ok(())
+    }
+
+    /// a map with keys that are not strings or integers
+    struct Entries<K>(Vec<(K, u8)>);
+    impl<K: Serialize> Serialize for Entries<K> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+        }
+    }
+
+    #[rstest]
+    #[case::float_key(Entries(vec![(1.5f32, 1)]))]
+    #[case::tuple_key(Entries(vec![((1u8, 2u8), 1)]))]
+    #[case::unit_key(Entries(vec![((), 1)]))]
+    fn test_write_serialize_invalid_key<T: Serialize>(#[case] value: T) {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_compact(&mut buf);
+        let mut a = JsonArray::new(&mut writer).unwrap();
+        match a.write_serialize(&value) {
+            Err(JsonSerializeError::Data(msg)) => assert_eq!(msg, "key must be a string"),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), JsonSerializeError<io::Error>> {
+        let shapes = vec![Shape::Empty, Shape::Circle(2.5), Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 }), Shape::Rect { width: 3, height: 4 }];
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_pretty(&mut buf);
+        {
+            let mut a = JsonArray::new(&mut writer)?;
+            for shape in &shapes {
+                a.write_serialize(shape)?;
+            }
+        }
+        writer.into_inner()?;
+
+        let mut r = io::Cursor::new(buf);
+        let mut json_reader = JsonReader::new(64, &mut r);
+        let actual: Vec<Shape> = json_reader.deserialize_value().unwrap();
+        assert_eq!(actual, shapes);
+        Ok(())
+    }
+}
@@ -0,0 +1,563 @@
+use crate::blocking::expect::expect_functions;
+use crate::shared::*;
+use core::convert::Infallible;
+use core::str::FromStr;
+
+/// A [JsonSliceReader] is the counterpart to [crate::blocking::JsonReader] for documents that are
+///  in memory as a whole, e.g. memory mapped files or HTTP bodies. It has the same `expect_*` API,
+///  but its tokens borrow from the input rather than from an internal buffer, so they live as long
+///  as the input and not just until the next call to [JsonSliceReader::next].
+///
+/// Strings without escapes, keys and numbers are returned as slices of the input without copying
+///  them. Only strings containing escapes need to be unescaped, and they are written to a scratch
+///  buffer provided by the application. Each escaped string permanently uses a part of the scratch
+///  buffer (that is what allows it to outlive subsequent calls), so the scratch buffer must be big
+///  enough for all escaped strings in the document - a scratch buffer of the input's size is
+///  always sufficient, and an empty scratch buffer works for documents without escapes.
+///
+/// Like [crate::blocking::JsonReader], [JsonSliceReader] tracks the nesting of objects and arrays
+///  in a fixed-size bit buffer, and it does not allocate memory on the heap.
+///
+/// ```
+/// use json_streaming::blocking::*;
+/// use json_streaming::shared::*;
+///
+/// # fn read_names() -> JsonParseResult<(), core::convert::Infallible> {
+/// let json = r#"["Alice", "Bob \"the builder\"", "Carol"]"#;
+/// let mut scratch = [0u8; 64];
+/// let mut json_reader = JsonSliceReader::new(json, &mut scratch);
+///
+/// let mut names = Vec::new();
+/// json_reader.expect_start_array()?;
+/// while let Some(name) = json_reader.expect_string_or_end_array()? {
+///     names.push(name);
+/// }
+/// assert_eq!(names, vec!["Alice", "Bob \"the builder\"", "Carol"]);
+/// # Ok(()) }
+/// ```
+pub struct JsonSliceReader<'i, S: AsMut<[u8]> = DefaultNestingBuffer> {
+    inner: ReaderInner<&'i mut [u8], S, Infallible>,
+    input: &'i [u8],
+    parked_token: Option<JsonReadToken<'i>>,
+}
+
+impl<'i> JsonSliceReader<'i> {
+    /// Create a [JsonSliceReader] for a `&str` or `&[u8]`, using `scratch` for unescaping strings.
+    ///
+    /// The [JsonSliceReader] supports up to [DEFAULT_MAX_NESTING_DEPTH] levels of nested objects and
+    ///  arrays - see [JsonSliceReader::new_with_provided_buffers] for more control.
+    pub fn new<I: AsRef<[u8]> + ?Sized>(input: &'i I, scratch: &'i mut [u8]) -> Self {
+        Self::new_with_provided_buffers(input, scratch, default_nesting_buffer(), false)
+    }
+}
+
+impl<'i, S: AsMut<[u8]>> JsonSliceReader<'i, S> {
+    /// Create a [JsonSliceReader] that uses an externally provided buffer for tracking the nesting
+    ///  of objects and arrays, and optionally does not require commas between top-level values (for
+    ///  reading [https://jsonlines.org] documents).
+    pub fn new_with_provided_buffers<I: AsRef<[u8]> + ?Sized>(input: &'i I, scratch: &'i mut [u8], nesting_buf: S, lenient_comma_handling: bool) -> Self {
+        Self {
+            inner: ReaderInner::new(scratch, nesting_buf, lenient_comma_handling),
+            input: input.as_ref(),
+            parked_token: None,
+        }
+    }
+
+    /// Limit the nesting depth of objects and arrays - see
+    ///  [crate::blocking::JsonReader::with_max_nesting_depth].
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: usize) -> Self {
+        self.inner.max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    /// Replace unpaired UTF-16 surrogates in `\u` escapes with U+FFFD instead of failing - see
+    ///  [crate::blocking::JsonReader::with_lossy_surrogate_handling].
+    pub fn with_lossy_surrogate_handling(mut self) -> Self {
+        self.inner.lossy_surrogate_handling = true;
+        self
+    }
+
+    /// Return the next JSON token. It does the same grammar checks as
+    ///  [crate::blocking::JsonReader::next], but the returned token borrows from the input and
+    ///  the scratch buffer rather than from the reader.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> JsonParseResult<JsonReadToken<'i>, Infallible> {
+        if let Some(token) = self.parked_token.take() {
+            return Ok(token);
+        }
+
+        match self.read_token_start()? {
+            None => {
+                Ok(JsonReadToken::EndOfStream)
+            },
+            Some(b'{') => {
+                self.inner.on_start_nested(ContainerKind::Object)?;
+                Ok(JsonReadToken::StartObject)
+            },
+            Some(b'}') => {
+                self.inner.on_end_nested(ContainerKind::Object)?;
+                Ok(JsonReadToken::EndObject)
+            },
+            Some(b'[') => {
+                self.inner.on_start_nested(ContainerKind::Array)?;
+                Ok(JsonReadToken::StartArray)
+            },
+            Some(b']') => {
+                self.inner.on_end_nested(ContainerKind::Array)?;
+                Ok(JsonReadToken::EndArray)
+            },
+
+            Some(b'n') => {
+                self.inner.state_change_for_value()?;
+                self.consume_literal(b"ull", "incomplete null literal")?;
+                Ok(JsonReadToken::NullLiteral)
+            },
+            Some(b't') => {
+                self.inner.state_change_for_value()?;
+                self.consume_literal(b"rue", "incomplete true literal")?;
+                Ok(JsonReadToken::BooleanLiteral(true))
+            },
+            Some(b'f') => {
+                self.inner.state_change_for_value()?;
+                self.consume_literal(b"alse", "incomplete false literal")?;
+                Ok(JsonReadToken::BooleanLiteral(false))
+            },
+
+            Some(b'"') => self.parse_after_quote(), // key or string value based on following ':'
+            Some(b) => {
+                self.inner.state_change_for_value()?;
+                match b {
+                    b'-' | b'0'..=b'9' => self.parse_number_literal(b),
+                    _ => self.inner.parse_err(ParseErrorKind::InvalidLiteral)
+                }
+            },
+        }
+    }
+
+    /// Returns the kind of the next token without consuming it: The following call to `next()`
+    ///  or an `expect_*` function returns the token as usual.
+    pub fn peek_kind(&mut self) -> JsonParseResult<JsonReadTokenKind, Infallible> {
+        if let Some(token) = &self.parked_token {
+            return Ok(token.token_kind());
+        }
+        let token = self.next()?;
+        let kind = token.token_kind();
+        self.parked_token = Some(token);
+        Ok(kind)
+    }
+
+    expect_functions!(JsonSliceReader, 'i, Infallible;);
+
+    /// There is no path tracking, so the `expect_*` functions check the next token themselves
+    fn check_next_kind(&mut self, _expected: &'static [JsonReadTokenKind], _location: Location) -> JsonParseResult<(), Infallible> {
+        Ok(())
+    }
+
+    /// There are no comments in the input, so this is the same as [JsonSliceReader::next]
+    fn next_non_comment(&mut self) -> JsonParseResult<JsonReadToken<'i>, Infallible> {
+        self.next()
+    }
+
+    /// The part of the input that was not consumed yet
+    fn remaining(&self) -> &'i [u8] {
+        &self.input[self.inner.cur_location.offset..]
+    }
+
+    /// Skips whitespace and commas between tokens, returning the first byte of the next token.
+    fn read_token_start(&mut self) -> JsonParseResult<Option<u8>, Infallible> {
+        loop {
+            self.consume_whitespace();
+            match self.read_next_byte() {
+                Some(b',') => self.inner.on_comma()?,
+                other => return Ok(other),
+            }
+        }
+    }
+
+    fn consume_whitespace(&mut self) {
+        self.inner.scan_whitespace(self.remaining());
+    }
+
+    fn peek_next_byte(&self) -> Option<u8> {
+        self.remaining().first().cloned()
+    }
+
+    fn read_next_byte(&mut self) -> Option<u8> {
+        let next = self.peek_next_byte();
+        if let Some(byte) = next {
+            self.inner.cur_location.after_byte(byte);
+        }
+        next
+    }
+
+    fn consume_literal(&mut self, rest: &[u8], msg: &'static str) -> JsonParseResult<(), Infallible> {
+        for &expected in rest {
+            if self.read_next_byte() != Some(expected) {
+                return self.parse_err(msg);
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_after_quote(&mut self) -> JsonParseResult<JsonReadToken<'i>, Infallible> {
+        let s = self.read_string_literal()?;
+
+        self.consume_whitespace();
+        if self.peek_next_byte() == Some(b':') {
+            self.read_next_byte();
+            self.inner.on_key()?;
+            Ok(JsonReadToken::Key(s))
+        }
+        else {
+            self.inner.state_change_for_value()?;
+            Ok(JsonReadToken::StringLiteral(s))
+        }
+    }
+
+    /// Reads a string literal, consuming the closing quote. The result borrows from the input if
+    ///  there are no escapes, and from the scratch buffer otherwise.
+    fn read_string_literal(&mut self) -> JsonParseResult<&'i str, Infallible> {
+        let remaining = self.remaining();
+        let len = remaining.iter()
+            .position(|&b| b == b'"' || b == b'\\')
+            .unwrap_or(remaining.len());
+
+        if remaining.get(len) == Some(&b'"') {
+            let s = &remaining[..len];
+            self.inner.cur_location.after_bytes(s);
+            self.read_next_byte();
+            return core::str::from_utf8(s).map_err(JsonParseError::Utf8);
+        }
+
+        // the string contains escapes (or is unterminated), so it is assembled in the scratch buffer
+        self.inner.ind_end_buf = 0;
+        self.inner.pending_high_surrogate = None;
+        loop {
+            let len = self.inner.scan_string_run(self.remaining(), b'"');
+            if len > 0 {
+                continue;
+            }
+            match self.read_next_byte() {
+                Some(b'"') => {
+                    self.inner.end_utf16_sequence()?;
+                    break;
+                }
+                Some(b'\\') => {
+                    let escaped = self.read_next_byte();
+                    if escaped != Some(b'u') {
+                        self.inner.end_utf16_sequence()?;
+                    }
+                    match escaped {
+                        Some(b'"') => self.inner.append_to_buf(b'"')?,
+                        Some(b'\\') => self.inner.append_to_buf(b'\\')?,
+                        Some(b'/') => self.inner.append_to_buf(b'/')?,
+                        Some(b'b') => self.inner.append_to_buf(0x08)?,
+                        Some(b'f') => self.inner.append_to_buf(0x0c)?,
+                        Some(b'n') => self.inner.append_to_buf(b'\n')?,
+                        Some(b'r') => self.inner.append_to_buf(b'\r')?,
+                        Some(b't') => self.inner.append_to_buf(b'\t')?,
+                        Some(b'u') => {
+                            let unit = self.parse_unicode_codepoint()?;
+                            self.inner.append_utf16_code_unit(unit)?;
+                        },
+                        _ => return self.inner.parse_err(ParseErrorKind::InvalidEscape),
+                    }
+                }
+                Some(ch) => {
+                    self.inner.end_utf16_sequence()?;
+                    self.inner.append_to_buf(ch)?;
+                }
+                None => {
+                    return self.inner.parse_err(ParseErrorKind::UnterminatedString);
+                }
+            }
+        }
+
+        // split off the used part of the scratch buffer so that the string can outlive this call
+        let scratch = core::mem::take(&mut self.inner.buf);
+        let (s, rest) = scratch.split_at_mut(self.inner.ind_end_buf);
+        self.inner.buf = rest;
+        self.inner.ind_end_buf = 0;
+        let s: &'i [u8] = s;
+        core::str::from_utf8(s).map_err(JsonParseError::Utf8)
+    }
+
+    fn parse_unicode_codepoint(&mut self) -> JsonParseResult<u16, Infallible> {
+        // exactly four hex digits specifying a code point
+        let mut cp: u16 = 0;
+        for _ in 0..4 {
+            if let Some(b) = self.read_next_byte() {
+                cp <<= 4;
+                match b {
+                    b'0'..=b'9' => cp += (b - b'0') as u16,
+                    b'a'..=b'f' => cp += (b - b'a' + 10) as u16,
+                    b'A'..=b'F' => cp += (b - b'A' + 10) as u16,
+                    _ => {
+                        return self.inner.parse_err(ParseErrorKind::InvalidUnicodeEscape);
+                    }
+                }
+            }
+            else {
+                return self.inner.parse_err(ParseErrorKind::IncompleteUtf8Sequence);
+            }
+        }
+        Ok(cp)
+    }
+
+    fn parse_number_literal(&mut self, b: u8) -> JsonParseResult<JsonReadToken<'i>, Infallible> {
+        let start = self.inner.cur_location.offset - 1;
+        let mut state = match NumberState::start(b) {
+            Some(state) => state,
+            None => return self.inner.parse_err(ParseErrorKind::InvalidLiteral),
+        };
+
+        while let Some(next) = self.peek_next_byte() {
+            if !NumberState::is_number_byte(next) {
+                break;
+            }
+            self.read_next_byte();
+            match state.next(next) {
+                Some(s) => state = s,
+                None => return self.inner.parse_err(ParseErrorKind::InvalidNumberLiteral),
+            }
+        }
+        if !state.is_complete() {
+            return self.inner.parse_err(ParseErrorKind::IncompleteNumberLiteral);
+        }
+        let s = &self.input[start..self.inner.cur_location.offset];
+        Ok(JsonReadToken::NumberLiteral(JsonNumber(core::str::from_utf8(s).map_err(JsonParseError::Utf8)?)))
+    }
+
+    /// convenience function for creating a parse error with the current location and an
+    ///  application specific message, see [ParseErrorKind::Custom]
+    pub fn parse_err<T>(&mut self, msg: &'static str) -> JsonParseResult<T, Infallible> {
+        self.inner.parse_err(ParseErrorKind::Custom(msg))
+    }
+
+    /// Returns the current parse location in the input - offset, row and column.
+    #[inline]
+    pub fn location(&self) -> Location {
+        self.inner.cur_location
+    }
+
+    /// Returns the number of objects and arrays that were started but not ended yet, i.e. `0` at
+    ///  the top level of the input.
+    pub fn depth(&self) -> usize {
+        self.inner.depth
+    }
+
+    /// Returns the kind of the innermost object or array that was started but not ended yet, or
+    ///  `None` at the top level of the input.
+    pub fn current_container(&self) -> Option<ContainerKind> {
+        self.inner.current_container
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case::empty("", vec![])]
+    #[case::null("null", vec![JsonReadToken::NullLiteral])]
+    #[case::bools("[true, false]", vec![JsonReadToken::StartArray, JsonReadToken::BooleanLiteral(true), JsonReadToken::BooleanLiteral(false), JsonReadToken::EndArray])]
+    #[case::number("-12.5e3", vec![JsonReadToken::NumberLiteral(JsonNumber("-12.5e3"))])]
+    #[case::numbers_in_array("[1,2 , 3]", vec![JsonReadToken::StartArray, JsonReadToken::NumberLiteral(JsonNumber("1")), JsonReadToken::NumberLiteral(JsonNumber("2")), JsonReadToken::NumberLiteral(JsonNumber("3")), JsonReadToken::EndArray])]
+    #[case::string(r#""abc""#, vec![JsonReadToken::StringLiteral("abc")])]
+    #[case::empty_string(r#""""#, vec![JsonReadToken::StringLiteral("")])]
+    #[case::escaped_string(r#""a\"b\\c\/d\nä""#, vec![JsonReadToken::StringLiteral("a\"b\\c/d\nä")])]
+    #[case::surrogate_pair(r#""\ud83d\ude00""#, vec![JsonReadToken::StringLiteral("\u{1f600}")])]
+    #[case::object(r#"{"a": 1, "b\n": "x"}"#, vec![JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::NumberLiteral(JsonNumber("1")), JsonReadToken::Key("b\n"), JsonReadToken::StringLiteral("x"), JsonReadToken::EndObject])]
+    #[case::nested(r#"[{"a":[]},{}]"#, vec![JsonReadToken::StartArray, JsonReadToken::StartObject, JsonReadToken::Key("a"), JsonReadToken::StartArray, JsonReadToken::EndArray, JsonReadToken::EndObject, JsonReadToken::StartObject, JsonReadToken::EndObject, JsonReadToken::EndArray])]
+    #[case::whitespace(" \r\n\t[ \n1 \t]\n", vec![JsonReadToken::StartArray, JsonReadToken::NumberLiteral(JsonNumber("1")), JsonReadToken::EndArray])]
+    fn test_parse(#[case] input: &str, #[case] expected: Vec<JsonReadToken>) -> JsonParseResult<(), Infallible> {
+        let mut scratch = vec![0u8; input.len()];
+        let mut json_reader = JsonSliceReader::new(input, &mut scratch);
+
+        // collect all tokens before comparing them, verifying that they outlive subsequent calls
+        let mut tokens = Vec::new();
+        loop {
+            match json_reader.next()? {
+                JsonReadToken::EndOfStream => break,
+                token => tokens.push(token),
+            }
+        }
+        assert_eq!(tokens, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::invalid_literal(b"x")]
+    #[case::incomplete_null(b"nul")]
+    #[case::incomplete_true(b"[tru]")]
+    #[case::incomplete_false(b"fals")]
+    #[case::unterminated_string(br#""abc"#)]
+    #[case::unterminated_escaped_string(br#""a\nbc"#)]
+    #[case::invalid_escape(br#""\x""#)]
+    #[case::invalid_unicode_escape(br#""\u12x4""#)]
+    #[case::invalid_uppercase_unicode_escape(br#""\uDZ00""#)]
+    #[case::unpaired_surrogate(br#""\ud83d""#)]
+    #[case::invalid_number(b"01")]
+    #[case::incomplete_number(b"[1.]")]
+    #[case::missing_comma(b"[1 2]")]
+    #[case::mismatched_bracket(b"[1}")]
+    #[case::key_outside_object(br#"["a": 1]"#)]
+    #[case::invalid_utf8(b"\"\xc3\"")]
+    #[case::invalid_utf8_escaped(b"\"\\n\xc3\"")]
+    fn test_parse_fails(#[case] input: &[u8]) {
+        let mut scratch = [0u8; 64];
+        let mut json_reader = JsonSliceReader::new(input, &mut scratch);
+        loop {
+            match json_reader.next() {
+                Ok(JsonReadToken::EndOfStream) => panic!("expected an error"),
+                Ok(_) => continue,
+                Err(_) => break,
+            }
+        }
+    }
+
+    #[test]
+    fn test_unescaped_strings_do_not_use_scratch() -> JsonParseResult<(), Infallible> {
+        let json = r#"{"name": "Alice", "tags": ["a", "b"], "n": 12}"#;
+        let mut json_reader = JsonSliceReader::new(json, &mut []);
+
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.expect_key()?, Some("name"));
+        let name = json_reader.expect_string()?;
+        assert_eq!(json_reader.expect_key()?, Some("tags"));
+        json_reader.expect_start_array()?;
+        let tag_a = json_reader.expect_string()?;
+        let tag_b = json_reader.expect_string()?;
+        json_reader.expect_end_array()?;
+        assert_eq!(json_reader.expect_key()?, Some("n"));
+        let n = json_reader.expect_raw_number()?;
+        json_reader.expect_end_object()?;
+        json_reader.expect_end_of_stream()?;
+
+        // the tokens borrow from the input
+        assert_eq!((name, tag_a, tag_b, n), ("Alice", "a", "b", JsonNumber("12")));
+        assert!(json.as_bytes().as_ptr_range().contains(&name.as_ptr()));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::fits(r#"["a\n", "\tb"]"#, 4, true)]
+    #[case::too_small(r#"["a\n", "\tb"]"#, 3, false)]
+    #[case::empty(r#""\n""#, 0, false)]
+    fn test_scratch_size(#[case] json: &str, #[case] scratch_size: usize, #[case] should_succeed: bool) {
+        let mut scratch = vec![0u8; scratch_size];
+        let mut json_reader = JsonSliceReader::new(json, &mut scratch);
+        let mut result = Ok(());
+        loop {
+            match json_reader.next() {
+                Ok(JsonReadToken::EndOfStream) => break,
+                Ok(_) => continue,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        match result {
+            Ok(()) => assert!(should_succeed),
+            Err(JsonParseError::BufferOverflow(_)) => assert!(!should_succeed),
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expect_functions() -> JsonParseResult<(), Infallible> {
+        let json = r#"[1, null, "x", null, true, null, {}, null, [], null]"#;
+        let mut json_reader = JsonSliceReader::new(json, &mut []);
+
+        json_reader.expect_start_array()?;
+        assert_eq!(json_reader.expect_opt_number::<u32>()?, Some(1));
+        assert_eq!(json_reader.expect_opt_number::<u32>()?, None);
+        assert_eq!(json_reader.expect_opt_string()?, Some("x"));
+        assert_eq!(json_reader.expect_opt_string()?, None);
+        assert_eq!(json_reader.expect_opt_bool()?, Some(true));
+        assert_eq!(json_reader.expect_opt_bool()?, None);
+        assert_eq!(json_reader.expect_opt_start_object()?, Some(()));
+        json_reader.expect_end_object()?;
+        assert_eq!(json_reader.expect_opt_start_object()?, None);
+        assert_eq!(json_reader.expect_opt_start_array()?, Some(()));
+        json_reader.expect_end_array()?;
+        assert_eq!(json_reader.expect_opt_start_array_or_end_array()?, Some(None));
+        assert_eq!(json_reader.expect_opt_start_array_or_end_array()?, None);
+        json_reader.expect_end_of_stream()?;
+
+        let mut json_reader = JsonSliceReader::new("[true]", &mut []);
+        json_reader.expect_start_array()?;
+        assert!(json_reader.expect_string().is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::simple(r#"{"a": 1, "b": 2}"#)]
+    #[case::nested(r#"{"a": {"x": [1, {"y": "\n"}]}, "b": 2}"#)]
+    fn test_skip_value(#[case] json: &str) -> JsonParseResult<(), Infallible> {
+        let mut scratch = [0u8; 8];
+        let mut json_reader = JsonSliceReader::new(json, &mut scratch);
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.expect_key()?, Some("a"));
+        json_reader.skip_value()?;
+        assert_eq!(json_reader.expect_key()?, Some("b"));
+        assert_eq!(json_reader.expect_number::<u8>()?, 2);
+        assert_eq!(json_reader.depth(), 1);
+        assert_eq!(json_reader.current_container(), Some(ContainerKind::Object));
+        json_reader.expect_end_object()?;
+        json_reader.expect_end_of_stream()
+    }
+
+    #[test]
+    fn test_peek_kind() -> JsonParseResult<(), Infallible> {
+        let mut json_reader = JsonSliceReader::new(r#"{"a": "x", "b": true}"#, &mut []);
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::StartObject);
+        json_reader.expect_start_object()?;
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::Key);
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::Key);
+        assert_eq!(json_reader.expect_key()?, Some("a"));
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::StringLiteral);
+        assert_eq!(json_reader.expect_string()?, "x");
+        assert_eq!(json_reader.expect_key()?, Some("b"));
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::BooleanLiteral);
+        assert!(json_reader.expect_bool()?);
+        json_reader.expect_end_object()?;
+        assert_eq!(json_reader.peek_kind()?, JsonReadTokenKind::EndOfStream);
+        json_reader.expect_end_of_stream()
+    }
+
+    #[test]
+    fn test_location() -> JsonParseResult<(), Infallible> {
+        let mut scratch = [0u8; 4];
+        let mut json_reader = JsonSliceReader::new("[\n  \"a\\n\",\n  12 ]", &mut scratch);
+        json_reader.expect_start_array()?;
+        json_reader.expect_string()?;
+        assert_eq!(json_reader.location(), Location { offset: 9, line: 2, column: 8 });
+        json_reader.expect_number::<u8>()?;
+        assert_eq!(json_reader.location(), Location { offset: 15, line: 3, column: 5 });
+        Ok(())
+    }
+
+    #[test]
+    fn test_lenient_comma_handling() -> JsonParseResult<(), Infallible> {
+        let json = "{\"a\": 1}\n{\"a\": 2}\n";
+        let mut json_reader = JsonSliceReader::new_with_provided_buffers(json, &mut [], default_nesting_buffer(), true);
+        for expected in [1, 2] {
+            json_reader.expect_start_object()?;
+            assert_eq!(json_reader.expect_key()?, Some("a"));
+            assert_eq!(json_reader.expect_number::<u8>()?, expected);
+            json_reader.expect_end_object()?;
+        }
+        json_reader.expect_end_of_stream()
+    }
+
+    #[test]
+    fn test_max_nesting_depth() {
+        let mut json_reader = JsonSliceReader::new("[[[1]]]", &mut []).with_max_nesting_depth(2);
+        json_reader.expect_start_array().unwrap();
+        json_reader.expect_start_array().unwrap();
+        assert!(matches!(json_reader.expect_start_array(), Err(JsonParseError::MaxNestingDepthExceeded(_))));
+    }
+}
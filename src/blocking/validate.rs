@@ -0,0 +1,233 @@
+use crate::blocking::io::BlockingRead;
+use crate::blocking::read::JsonReader;
+use crate::shared::*;
+
+/// A [JsonValidator] reads an entire stream with a [JsonReader] to check that it is valid JSON,
+///  e.g. for checking an uploaded file before importing it. Like all reading, this happens in a
+///  streaming fashion without holding the document in memory, so the reader's buffer must only
+///  be large enough for the longest key, string or number.
+///
+/// On top of the checks that [JsonReader] does anyway (matching brackets, strict number syntax
+///  etc.), the validator checks that the stream contains exactly one top-level value, or one
+///  value per line for [JSON Lines](https://jsonlines.org), and it rejects unescaped control
+///  characters in strings.
+///
+/// A valid stream results in [JsonValidationStats]. Otherwise, the validator collects errors up
+///  to a configurable maximum: After an error, it continues at the next structural boundary,
+///  i.e. the next `,`, `}` or `]` of the current object or array, or the next line for JSON
+///  Lines. An I/O error ends validation.
+///
+/// ```
+/// use json_streaming::blocking::*;
+/// use json_streaming::shared::*;
+///
+/// let mut r = std::io::Cursor::new(r#"{"a": [1, 2, 3], "b": {"c": true}}"#);
+/// let stats = JsonValidator::new().validate(&mut JsonReader::new(64, &mut r)).unwrap();
+/// assert_eq!(stats.max_depth, 2);
+/// assert_eq!(stats.numbers, 3);
+///
+/// let mut r = std::io::Cursor::new(r#"{"a": [1, 2 3], "b": x}"#);
+/// let errors = JsonValidator::new().validate(&mut JsonReader::new(64, &mut r)).unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub struct JsonValidator {
+    json_lines: bool,
+    max_errors: usize,
+}
+
+impl Default for JsonValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonValidator {
+    /// Create a [JsonValidator] for a stream with a single top-level value that stops after
+    ///  [DEFAULT_MAX_VALIDATION_ERRORS] errors.
+    pub fn new() -> Self {
+        Self {
+            json_lines: false,
+            max_errors: DEFAULT_MAX_VALIDATION_ERRORS,
+        }
+    }
+
+    /// Validate [JSON Lines](https://jsonlines.org), i.e. one top-level value per line. Lines
+    ///  containing only whitespace are accepted, and so is an empty stream.
+    pub fn with_json_lines(mut self) -> Self {
+        self.json_lines = true;
+        self
+    }
+
+    /// Stop validation after a given number of errors.
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+
+    /// Reads the rest of the stream, returning statistics if it is valid, and the errors
+    ///  otherwise. This enables [JsonReader::with_control_character_check] for the reader.
+    pub fn validate<B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>>(&self, json_reader: &mut JsonReader<'_, B, R, S>) -> Result<JsonValidationStats, Vec<JsonParseError<R::Error>>> {
+        json_reader.enable_control_character_check();
+        let mut inner = ValidatorInner::new(self.json_lines);
+        let mut errors = Vec::new();
+
+        loop {
+            let error = match Self::read_token(json_reader, &mut inner) {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(error) => error,
+            };
+
+            let resync_result = match error {
+                JsonParseError::Io(_) => Ok(false),
+                _ if errors.len() + 1 >= self.max_errors => Ok(false),
+                // anything after the top-level value is considered garbage
+                JsonParseError::Parse(ParseErrorKind::MultipleTopLevelValues, _, _) if !self.json_lines => Ok(false),
+                JsonParseError::Parse(ParseErrorKind::MultiLineRecord, _, _) => {
+                    // the token in the next line is the start of the next record
+                    json_reader.reset_to_top_level();
+                    Ok(true)
+                }
+                _ => json_reader.resync(&error, self.json_lines),
+            };
+            errors.push(error);
+
+            match resync_result {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(error) => {
+                    errors.push(error);
+                    break;
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            inner.stats.bytes = json_reader.location().offset;
+            Ok(inner.stats)
+        }
+        else {
+            Err(errors)
+        }
+    }
+
+    /// Reads and checks the next token, returning `false` at the end of the stream
+    fn read_token<B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>>(json_reader: &mut JsonReader<'_, B, R, S>, inner: &mut ValidatorInner) -> JsonParseResult<bool, R::Error> {
+        let depth = json_reader.depth();
+        if !json_reader.skip_whitespace()? {
+            inner.check_end_of_stream(depth)
+                .or_else(|kind| Self::validation_err(json_reader, kind))?;
+            return Ok(false);
+        }
+
+        let line = json_reader.location().line;
+        inner.check_line(depth, line)
+            .or_else(|kind| Self::validation_err(json_reader, kind))?;
+
+        let kind = json_reader.peek_kind()?;
+        inner.check_top_level_value(kind, depth, line)
+            .or_else(|kind| Self::validation_err(json_reader, kind))?;
+        json_reader.next()?;
+        inner.on_token(kind, depth, line);
+        json_reader.expect_next_top_level_value();
+        Ok(true)
+    }
+
+    fn validation_err<B: AsMut<[u8]>, R: BlockingRead, S: AsMut<[u8]>>(json_reader: &mut JsonReader<'_, B, R, S>, kind: ParseErrorKind) -> JsonParseResult<(), R::Error> {
+        let location = json_reader.location();
+        Err(JsonParseError::Parse(kind, location, json_reader.current_path().map(OwnedJsonPath::from)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::io::Cursor;
+
+    fn validate(json: &str, json_lines: bool) -> Result<JsonValidationStats, Vec<ParseErrorKind>> {
+        let mut r = Cursor::new(json.as_bytes());
+        let mut json_reader = JsonReader::new(64, &mut r);
+        let validator = if json_lines { JsonValidator::new().with_json_lines() } else { JsonValidator::new() };
+        validator.validate(&mut json_reader)
+            .map_err(|errors| errors.into_iter()
+                .map(|e| match e {
+                    JsonParseError::Parse(kind, _, _) => kind,
+                    other => panic!("unexpected error {:?}", other),
+                })
+                .collect())
+    }
+
+    fn stats(bytes: usize, max_depth: usize, values: usize, counts: [usize; 7]) -> JsonValidationStats {
+        let [objects, arrays, keys, strings, numbers, booleans, nulls] = counts;
+        JsonValidationStats { bytes, max_depth, values, objects, arrays, keys, strings, numbers, booleans, nulls }
+    }
+
+    #[rstest]
+    #[case::scalar("12", false, stats(2, 0, 1, [0, 0, 0, 0, 1, 0, 0]))]
+    #[case::whitespace(" \n[]\n ", false, stats(6, 1, 1, [0, 1, 0, 0, 0, 0, 0]))]
+    #[case::nested(r#"{"a": [1, "x", true, null, {"b": false}], "c": []}"#, false, stats(50, 3, 1, [2, 2, 3, 1, 1, 2, 1]))]
+    #[case::escaped_control_character(r#"["a\tb"]"#, false, stats(8, 1, 1, [0, 1, 0, 1, 0, 0, 0]))]
+    #[case::json_lines("{\"a\": 1}\n[2]\n\n\"x\"\n", true, stats(18, 1, 3, [1, 1, 1, 1, 2, 0, 0]))]
+    #[case::json_lines_crlf("1\r\n2", true, stats(4, 0, 2, [0, 0, 0, 0, 2, 0, 0]))]
+    #[case::json_lines_empty("", true, stats(0, 0, 0, [0; 7]))]
+    fn test_validate(#[case] json: &str, #[case] json_lines: bool, #[case] expected: JsonValidationStats) {
+        assert_eq!(validate(json, json_lines), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::empty("", false, vec![ParseErrorKind::UnexpectedEndOfInput])]
+    #[case::unclosed("[1, 2", false, vec![ParseErrorKind::UnexpectedEndOfInput])]
+    #[case::missing_comma("[1 2]", false, vec![ParseErrorKind::MissingComma])]
+    #[case::two_errors("[1, x, 3, y]", false, vec![ParseErrorKind::InvalidLiteral, ParseErrorKind::InvalidLiteral])]
+    #[case::numbers(r#"{"a": 01, "b": 1.}"#, false, vec![ParseErrorKind::InvalidNumberLiteral, ParseErrorKind::IncompleteNumberLiteral])]
+    #[case::control_character("[\"a\tb\", \"c\u{1}\", 3]", false, vec![ParseErrorKind::ControlCharacterInString, ParseErrorKind::ControlCharacterInString])]
+    #[case::trailing_comma("[1,]", false, vec![ParseErrorKind::TrailingComma])]
+    #[case::nested_trailing_comma(r#"{"a": [1,], "b": 2,}"#, false, vec![ParseErrorKind::TrailingComma, ParseErrorKind::TrailingComma])]
+    #[case::skipped_nested("[[1], x [2, 3], [4]]", false, vec![ParseErrorKind::InvalidLiteral])]
+    #[case::skipped_string(r#"["a\q", 1, x]"#, false, vec![ParseErrorKind::InvalidEscape, ParseErrorKind::InvalidLiteral])]
+    #[case::skipped_string_with_bracket(r#"[x "]", 1, y]"#, false, vec![ParseErrorKind::InvalidLiteral, ParseErrorKind::InvalidLiteral])]
+    #[case::mismatched_end("[1}", false, vec![ParseErrorKind::MismatchedEndObject])]
+    #[case::two_values("[1] [2]", false, vec![ParseErrorKind::MultipleTopLevelValues])]
+    #[case::json_lines_invalid_record("{\"a\": 1}\n{\"a\": x}\n{\"a\": y}\n", true, vec![ParseErrorKind::InvalidLiteral, ParseErrorKind::InvalidLiteral])]
+    #[case::json_lines_unclosed_record("{\"a\": 1\n{\"a\": 2}\n", true, vec![ParseErrorKind::MultiLineRecord])]
+    #[case::json_lines_unclosed_at_end("{\"a\": 1}\n{\"a\": 2", true, vec![ParseErrorKind::UnexpectedEndOfInput])]
+    #[case::json_lines_two_values("1 2\n3\n[4]]\n5", true, vec![ParseErrorKind::MultipleTopLevelValues, ParseErrorKind::MismatchedEndArray])]
+    #[case::json_lines_newline_in_string("\"a\n1\n", true, vec![ParseErrorKind::ControlCharacterInString])]
+    fn test_validate_fails(#[case] json: &str, #[case] json_lines: bool, #[case] expected: Vec<ParseErrorKind>) {
+        assert_eq!(validate(json, json_lines), Err(expected));
+    }
+
+    #[test]
+    fn test_validate_error_locations() {
+        let mut r = Cursor::new(r#"{"a": [1, x], "b": {"c" 2}}"#);
+        let mut json_reader = JsonReader::new(64, &mut r).with_path_tracking(64);
+        let errors = JsonValidator::new().validate(&mut json_reader).unwrap_err();
+        let errors = errors.iter()
+            .map(|e| match e {
+                JsonParseError::Parse(_, location, path) => (location.offset, path.as_ref().unwrap().to_string()),
+                other => panic!("unexpected error {:?}", other),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec![(11, "/a/1".to_string()), (25, "/b".to_string())]);
+    }
+
+    #[rstest]
+    #[case::limited(2, 2)]
+    #[case::unlimited(100, 4)]
+    fn test_validate_max_errors(#[case] max_errors: usize, #[case] expected_num_errors: usize) {
+        let mut r = Cursor::new("[x, x, x, x]");
+        let mut json_reader = JsonReader::new(64, &mut r);
+        let errors = JsonValidator::new().with_max_errors(max_errors).validate(&mut json_reader).unwrap_err();
+        assert_eq!(errors.len(), expected_num_errors);
+    }
+
+    #[test]
+    fn test_validate_buffer_overflow() {
+        let mut r = Cursor::new(r#"["abcdefghijklmnop", 1, x]"#);
+        let mut json_reader = JsonReader::new(8, &mut r);
+        let errors = JsonValidator::new().validate(&mut json_reader).unwrap_err();
+        assert!(matches!(errors[0], JsonParseError::BufferOverflow(_)));
+        assert_eq!(errors.len(), 2);
+    }
+}
@@ -17,6 +17,8 @@ pub struct JsonArray<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFor
     writer: &'a mut JsonWriter<'b, W, F, FF>,
     is_initial: bool,
     is_ended: bool,
+    /// a JSON Lines record, see `JsonLinesWriter`
+    is_record: bool,
 }
 
 impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'a, 'b, W, F, FF> {
@@ -30,9 +32,18 @@ impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'
             writer,
             is_initial: true,
             is_ended: false,
+            is_record: false,
         })
     }
 
+    /// Creates a top-level [JsonArray] that is a record of JSON Lines, writing a newline after the
+    ///  closing bracket
+    pub(crate) async fn new_record(writer: &'a mut JsonWriter<'b, W, F, FF>) -> Result<Self, W::Error> {
+        let mut result = Self::new(writer).await?;
+        result.is_record = true;
+        Ok(result)
+    }
+
     async fn handle_initial(&mut self) -> Result<(), W::Error> {
         if self.is_initial {
             self.is_initial = false;
@@ -81,6 +92,19 @@ impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'
         self.writer.write_f32(value).await
     }
 
+    /// Write an element that is serialized with serde (requires the `serde` feature), honoring
+    ///  this writer's [JsonFormatter] and [FloatFormat]. See [JsonObject::write_serialize] for
+    ///  the representation.
+    ///
+    /// Since serde's API is synchronous, the value is serialized into a buffer which is then
+    ///  written. Nothing is written if serialization fails with [JsonSerializeError::Data].
+    #[cfg(feature = "serde")]
+    pub async fn write_serialize<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonSerializeError<W::Error>> {
+        let buf = self.writer.serialize_to_buffer(value)?;
+        self.handle_initial().await?;
+        Ok(self.writer.write_bytes(&buf).await?)
+    }
+
     /// Start a nested object as an element. This function returns a new [JsonObject] instance
     ///  for writing elements to the nested object. When the returned [JsonObject] goes out of scope
     ///  (per syntactic scope or an explicit call to `end()`), the nested object is closed, and
@@ -113,6 +137,9 @@ impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonArray<'
         self.writer.write_format_before_end_nested(self.is_initial).await?;
         self.writer.write_bytes(b"]").await?;
         self.is_ended = true;
+        if self.is_record {
+            self.writer.write_bytes(b"\n").await?;
+        }
         Ok(())
     }
 }
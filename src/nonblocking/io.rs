@@ -1,5 +1,7 @@
 use async_trait::async_trait;
 use core::error::Error;
+use core::future::Future;
+use core::pin::Pin;
 
 /// [NonBlockingWrite] is the library's abstraction for non-blocking write I/O.
 ///
@@ -42,6 +44,9 @@ impl <W: tokio::io::AsyncWrite + Unpin + Send> NonBlockingWrite for W {
 }
 
 
+/// The future returned by [NonBlockingRead]'s functions, as generated by `async_trait`
+type ReadFuture<'a, T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'a>>;
+
 #[async_trait]
 /// [NonBlockingRead] is the library's abstraction for non-blocking read I/O.
 ///
@@ -50,13 +55,68 @@ impl <W: tokio::io::AsyncWrite + Unpin + Send> NonBlockingWrite for W {
 ///  [NonBlockingRead] is to decouple json-streaming from tokio and allow it to be used with
 ///  other async frameworks.
 ///
-/// Note that json-streaming reads data from a [NonBlockingRead] in many small chunks without any
-///  I/O buffering. It is the client's responsibility to add buffering for improved performance
-///  where desired.
+/// The only required method is [NonBlockingRead::read] which reads a single byte.
+///  Implementations that hold data in memory can additionally provide slice-oriented access
+///  through [NonBlockingRead::fill_buf] and [NonBlockingRead::consume] (modelled on
+///  `tokio::io::AsyncBufRead`), which allows [crate::nonblocking::JsonReader] to scan whitespace,
+///  strings and numbers a slice at a time rather than byte by byte.
+///
+/// The blanket implementation for `AsyncRead` does no buffering.
+///  [crate::nonblocking::JsonReader::new] compensates for that with a read-ahead buffer of its
+///  own; in other cases, wrap the reader in a [BufferedRead] for improved performance.
 pub trait NonBlockingRead {
     type Error: Error;
 
     async fn read(&mut self) -> Result<Option<u8>, Self::Error>;
+
+    // The default implementations below are written out as the functions that `async_trait`
+    //  generates, so that they do not capture `self` in their futures. Otherwise `async_trait`
+    //  would require `Self: Send` for calling them.
+
+    /// Reads some bytes into `buf`, returning the number of bytes read. `Ok(0)` signals the end
+    ///  of the stream (or an empty `buf`).
+    ///
+    /// The default implementation reads a single byte using [NonBlockingRead::read].
+    fn read_slice<'life0, 'life1, 'async_trait>(&'life0 mut self, buf: &'life1 mut [u8]) -> ReadFuture<'async_trait, usize, Self::Error>
+    where
+        'life0: 'async_trait,
+        'life1: 'async_trait,
+        Self: 'async_trait,
+    {
+        let read = (!buf.is_empty()).then(|| self.read());
+        Box::pin(async move {
+            let Some(read) = read else {
+                return Ok(0);
+            };
+            match read.await? {
+                Some(b) => {
+                    buf[0] = b;
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        })
+    }
+
+    /// Returns the bytes that are buffered by this reader, reading more data from the underlying
+    ///  source if the buffer is empty. Bytes in the returned slice are not consumed until
+    ///  [NonBlockingRead::consume] is called.
+    ///
+    /// An empty slice means that there is no more data, or that this reader does not support
+    ///  slice-oriented access. Either way, callers fall back to [NonBlockingRead::read]. That is
+    ///  what the default implementation does.
+    fn fill_buf<'life0, 'async_trait>(&'life0 mut self) -> ReadFuture<'async_trait, &'life0 [u8], Self::Error>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async { Ok(&[][..]) })
+    }
+
+    /// Marks `amount` bytes returned by [NonBlockingRead::fill_buf] as consumed.
+    fn consume(&mut self, amount: usize) {
+        let _ = amount;
+    }
 }
 
 #[cfg(all(test, not(feature = "tokio")))]
@@ -74,6 +134,10 @@ impl NonBlockingRead for std::io::Cursor<Vec<u8>> {
             Ok(None)
         }
     }
+
+    async fn read_slice(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        std::io::Read::read(self, buf)
+    }
 }
 
 #[cfg(feature = "tokio")]
@@ -93,4 +157,131 @@ impl <R: tokio::io::AsyncRead + Unpin + Send> NonBlockingRead for R {
             Ok(None)
         }
     }
+
+    async fn read_slice(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        tokio::io::AsyncReadExt::read(self, buf).await
+    }
+}
+
+/// [BufferedRead] adds an in-memory buffer to a [NonBlockingRead], similar to
+///  `tokio::io::BufReader`. It fills the buffer using [NonBlockingRead::read_slice] and provides
+///  slice-oriented access to it, allowing [crate::nonblocking::JsonReader] to process data a slice
+///  at a time.
+///
+/// The buffer is provided by the caller, so this does not depend on a particular async runtime
+///  or on allocation.
+pub struct BufferedRead<R: NonBlockingRead, B: AsMut<[u8]>> {
+    reader: R,
+    buf: B,
+    pos: usize,
+    end: usize,
+}
+impl<R: NonBlockingRead, B: AsMut<[u8]>> BufferedRead<R, B> {
+    /// Create a [BufferedRead] wrapping `reader`, using `buf` as its buffer. The buffer's size
+    ///  determines the maximum number of bytes read from `reader` at a time.
+    pub fn new(reader: R, buf: B) -> Self {
+        BufferedRead {
+            reader,
+            buf,
+            pos: 0,
+            end: 0,
+        }
+    }
+
+    /// Returns the wrapped reader, discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[async_trait]
+impl<R: NonBlockingRead + Send, B: AsMut<[u8]> + Send> NonBlockingRead for BufferedRead<R, B> {
+    type Error = R::Error;
+
+    async fn read(&mut self) -> Result<Option<u8>, Self::Error> {
+        let result = self.fill_buf().await?.first().cloned();
+        if result.is_some() {
+            self.pos += 1;
+        }
+        Ok(result)
+    }
+
+    async fn read_slice(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let available = self.fill_buf().await?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.pos += len;
+        Ok(len)
+    }
+
+    async fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if self.pos == self.end {
+            self.end = self.reader.read_slice(self.buf.as_mut()).await?;
+            self.pos = 0;
+        }
+        Ok(&self.buf.as_mut()[self.pos..self.end])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = self.end.min(self.pos + amount);
+    }
+}
+
+/// The reader that a [crate::nonblocking::JsonReader] consumes, with an optional read-ahead
+///  buffer. The read-ahead buffer is only used if the reader does not provide slice-oriented
+///  access itself, giving plain `AsyncRead` implementations a slice path without a
+///  [BufferedRead].
+pub(crate) struct ReadAhead<'a, R: NonBlockingRead, B: AsMut<[u8]>> {
+    reader: &'a mut R,
+    buf: Option<B>,
+    pos: usize,
+    end: usize,
+}
+impl<'a, R: NonBlockingRead, B: AsMut<[u8]>> ReadAhead<'a, R, B> {
+    pub fn new(reader: &'a mut R) -> Self {
+        ReadAhead {
+            reader,
+            buf: None,
+            pos: 0,
+            end: 0,
+        }
+    }
+
+    pub fn set_buf(&mut self, buf: B) {
+        self.buf = Some(buf);
+    }
+
+    pub async fn read(&mut self) -> Result<Option<u8>, R::Error> {
+        if self.buf.is_none() {
+            return self.reader.read().await;
+        }
+        let result = self.fill_buf().await?.first().cloned();
+        if result.is_some() {
+            self.consume(1);
+        }
+        Ok(result)
+    }
+
+    pub async fn fill_buf(&mut self) -> Result<&[u8], R::Error> {
+        let Some(buf) = self.buf.as_mut() else {
+            return self.reader.fill_buf().await;
+        };
+        if self.pos == self.end {
+            if !self.reader.fill_buf().await?.is_empty() {
+                return self.reader.fill_buf().await;
+            }
+            self.end = self.reader.read_slice(buf.as_mut()).await?;
+            self.pos = 0;
+        }
+        Ok(&buf.as_mut()[self.pos..self.end])
+    }
+
+    pub fn consume(&mut self, amount: usize) {
+        if self.pos < self.end {
+            self.pos = self.end.min(self.pos + amount);
+        }
+        else {
+            self.reader.consume(amount);
+        }
+    }
 }
@@ -0,0 +1,235 @@
+use crate::nonblocking::io::NonBlockingRead;
+use crate::nonblocking::read::JsonReader;
+use crate::shared::*;
+
+/// A [JsonLinesReader] reads a [JSON Lines](https://jsonlines.org) stream, i.e. a stream with one
+///  JSON value per line. It wraps a [JsonReader] which application code uses to read each record
+///  with the regular `expect_*` functions, and it takes care of the line structure:
+///
+/// * Each record must be a single value in a single line: A token inside a record's objects or
+///   arrays that starts in a later line fails with [ParseErrorKind::MultiLineRecord], and another
+///   value in a record's line fails with [ParseErrorKind::MultipleTopLevelValues].
+/// * Inside a record, the regular grammar checks apply - unlike with
+///   [JsonReader::new_with_lenient_comma_handling], `[1 2]` is rejected.
+/// * Blank lines are skipped.
+///
+/// Errors are reported as [JsonLinesError] with the number of the record in which they occurred,
+///  and after an error, [JsonLinesReader::skip_bad_record] continues with the next line.
+///
+/// ```
+/// use json_streaming::nonblocking::*;
+/// use json_streaming::shared::*;
+///
+/// async fn sum_a<R: NonBlockingRead>(r: &mut R) -> Result<u32, JsonLinesError<R::Error>> {
+///     let mut json_lines_reader = JsonLinesReader::new(JsonReader::new(64, r));
+///     let mut sum = 0;
+///     while json_lines_reader.next_record().await? {
+///         match json_lines_reader.json_reader().expect_number::<u32>().await {
+///             Ok(a) => sum += a,
+///             Err(e) => {
+///                 let error = json_lines_reader.record_error(e);
+///                 json_lines_reader.skip_bad_record(&error).await?;
+///             }
+///         }
+///     }
+///     Ok(sum)
+/// }
+/// ```
+pub struct JsonLinesReader<'a, B: AsMut<[u8]>, R: NonBlockingRead, S: AsMut<[u8]> = DefaultNestingBuffer> {
+    json_reader: JsonReader<'a, B, R, S>,
+    num_records: usize,
+    /// the location of the current record's first byte
+    record_start: Location,
+    is_in_record: bool,
+}
+
+impl<'a, B: AsMut<[u8]>, R: NonBlockingRead, S: AsMut<[u8]>> JsonLinesReader<'a, B, R, S> {
+    /// Creates a [JsonLinesReader] that reads records with a given [JsonReader], which should not
+    ///  have lenient comma handling.
+    pub fn new(json_reader: JsonReader<'a, B, R, S>) -> Self {
+        JsonLinesReader {
+            json_reader,
+            num_records: 0,
+            record_start: Location::start(),
+            is_in_record: false,
+        }
+    }
+
+    /// Moves to the next record, returning `false` at the end of the stream. If the current
+    ///  record was not read completely, the rest of its line is skipped.
+    ///
+    /// This fails with [ParseErrorKind::MultipleTopLevelValues] if the current record was read
+    ///  completely and is followed by another value in the same line. The rest of the line is
+    ///  skipped in that case, so calling this function again continues with the next line.
+    pub async fn next_record(&mut self) -> Result<bool, JsonLinesError<R::Error>> {
+        if self.is_in_record {
+            self.is_in_record = false;
+            if !self.json_reader.is_after_top_level_value() {
+                self.json_reader.skip_to_next_line().await
+                    .map_err(|e| self.record_error(e))?;
+            }
+            else if self.json_reader.skip_whitespace().await.map_err(|e| self.record_error(e))?
+                && self.json_reader.token_start_location().line == self.record_start.line {
+                let error = JsonParseError::Parse(ParseErrorKind::MultipleTopLevelValues, self.json_reader.token_start_location(), None);
+                self.json_reader.skip_to_next_line().await
+                    .map_err(|e| self.record_error(e))?;
+                return Err(self.record_error(error));
+            }
+        }
+
+        self.json_reader.set_record_line(None);
+        if !self.json_reader.skip_whitespace().await.map_err(|e| self.record_error(e))? {
+            return Ok(false);
+        }
+        self.record_start = self.json_reader.token_start_location();
+        self.num_records += 1;
+        self.json_reader.set_record_line(Some(self.record_start.line));
+        self.json_reader.expect_next_top_level_value();
+        self.is_in_record = true;
+        Ok(true)
+    }
+
+    /// Returns the [JsonReader] for reading the current record
+    pub fn json_reader(&mut self) -> &mut JsonReader<'a, B, R, S> {
+        &mut self.json_reader
+    }
+
+    /// Returns the 0-based number of the current record. Blank lines are not counted - the line
+    ///  in which the record starts is available from [JsonLinesReader::record_start].
+    pub fn record(&self) -> usize {
+        self.num_records.saturating_sub(1)
+    }
+
+    /// Returns the location at which the current record starts
+    pub fn record_start(&self) -> Location {
+        self.record_start
+    }
+
+    /// Wraps an error that occurred while reading the current record in a [JsonLinesError] with
+    ///  the record's number
+    pub fn record_error(&self, error: JsonParseError<R::Error>) -> JsonLinesError<R::Error> {
+        JsonLinesError {
+            record: self.record(),
+            offset: error_location(&error).unwrap_or(self.record_start).offset,
+            error,
+        }
+    }
+
+    /// Skips the rest of a record after an error, so that [JsonLinesReader::next_record] continues
+    ///  with the next line. If the error was a record spanning more than one line, reading
+    ///  continues in the line at which the error occurred.
+    pub async fn skip_bad_record(&mut self, error: &JsonLinesError<R::Error>) -> Result<(), JsonLinesError<R::Error>> {
+        self.is_in_record = false;
+        if let JsonParseError::Parse(ParseErrorKind::MultiLineRecord, _, _) = error.error {
+            self.json_reader.reset_to_top_level();
+            return Ok(());
+        }
+        self.json_reader.resync(&error.error, true).await
+            .map_err(|e| self.record_error(e))?;
+        Ok(())
+    }
+
+    /// Returns the wrapped [JsonReader]
+    pub fn into_inner(mut self) -> JsonReader<'a, B, R, S> {
+        self.json_reader.set_record_line(None);
+        self.json_reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::io;
+    use std::io::Cursor;
+
+    #[rstest]
+    #[case::valid("{\"a\": 1}\n[2, 3]\n\n4\n\"x\"", vec![(0, None), (1, None), (2, None), (3, None)])]
+    #[case::crlf("{\"a\": 1}\r\n[2, 3]\r\n", vec![(0, None), (1, None)])]
+    #[case::empty("", vec![])]
+    #[case::blank_lines("\n  \n\t\n", vec![])]
+    #[case::missing_comma("[1 2]\n[3]\n", vec![(0, Some(ParseErrorKind::MissingComma)), (1, None)])]
+    #[case::invalid_literal("{\"a\": x}\n[3]\n", vec![(0, Some(ParseErrorKind::InvalidLiteral)), (1, None)])]
+    #[case::two_values("1 2\n3\n", vec![(0, None), (0, Some(ParseErrorKind::MultipleTopLevelValues)), (1, None)])]
+    #[case::two_objects("{} {}\n[]\n", vec![(0, None), (0, Some(ParseErrorKind::MultipleTopLevelValues)), (1, None)])]
+    #[case::truncated("{\"a\": [1, 2\n{\"b\": 3}\n", vec![(0, Some(ParseErrorKind::MultiLineRecord)), (1, None)])]
+    #[case::multi_line("[1,\n2]\n[3]", vec![(0, Some(ParseErrorKind::MultiLineRecord)), (1, None), (1, Some(ParseErrorKind::MultipleTopLevelValues)), (2, None)])]
+    #[case::truncated_at_end("[1]\n[1, 2", vec![(0, None), (1, Some(ParseErrorKind::UnexpectedEndOfInput))])]
+    #[tokio::test]
+    async fn test_json_lines_reader(#[case] json: &str, #[case] expected: Vec<(usize, Option<ParseErrorKind>)>) -> Result<(), JsonLinesError<io::Error>> {
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let mut json_lines_reader = JsonLinesReader::new(JsonReader::new(64, &mut r));
+        let mut actual = Vec::new();
+        loop {
+            match json_lines_reader.next_record().await {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(JsonLinesError { record, error: JsonParseError::Parse(kind, _, _), .. }) => {
+                    actual.push((record, Some(kind)));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+            match json_lines_reader.json_reader().skip_value().await {
+                Ok(()) => actual.push((json_lines_reader.record(), None)),
+                Err(e) => {
+                    let error = json_lines_reader.record_error(e);
+                    let JsonParseError::Parse(kind, _, _) = &error.error else {
+                        return Err(error);
+                    };
+                    actual.push((error.record, Some(*kind)));
+                    json_lines_reader.skip_bad_record(&error).await?;
+                }
+            }
+        }
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_record_error() -> Result<(), JsonLinesError<io::Error>> {
+        let mut r = Cursor::new(b"[1]\n\n  [2, x]\n".to_vec());
+        let mut json_lines_reader = JsonLinesReader::new(JsonReader::new(64, &mut r));
+        assert!(json_lines_reader.next_record().await?);
+        json_lines_reader.json_reader().skip_value().await.unwrap();
+        assert!(json_lines_reader.next_record().await?);
+        assert_eq!(json_lines_reader.record(), 1);
+        assert_eq!(json_lines_reader.record_start(), Location { offset: 7, line: 3, column: 3 });
+
+        let error = json_lines_reader.json_reader().skip_value().await.unwrap_err();
+        let error = json_lines_reader.record_error(error);
+        assert_eq!(error.record, 1);
+        assert_eq!(error.offset, 12);
+        assert_eq!(error.to_string(), "record 1 (offset 12): parse error: invalid JSON literal @ line 3, column 8 (offset 12)");
+        json_lines_reader.skip_bad_record(&error).await?;
+        assert!(!json_lines_reader.next_record().await?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::unread("1\n2\n", 0, vec![(0, 1), (1, 2)])]
+    #[case::unread_with_second_value("1 2\n3\n", 0, vec![(0, 1), (1, 2)])]
+    #[case::unread_object("{\"a\": 1}\n\n[2]\n", 0, vec![(0, 1), (1, 3)])]
+    #[case::partly_read_nested("{\"a\": [1, 2]}\n{\"b\": 3}", 3, vec![(0, 1), (1, 2)])]
+    #[case::partly_read_object("{\"a\": 1} 5\n2\n", 2, vec![(0, 1), (1, 2)])]
+    #[case::read_completely("1\n2\n", 1, vec![(0, 1), (1, 2)])]
+    #[tokio::test]
+    async fn test_record_not_read_completely(#[case] json: &str, #[case] num_tokens: usize, #[case] expected: Vec<(usize, usize)>) -> Result<(), JsonLinesError<io::Error>> {
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let mut json_lines_reader = JsonLinesReader::new(JsonReader::new(64, &mut r));
+        let mut actual = Vec::new();
+        while json_lines_reader.next_record().await? {
+            actual.push((json_lines_reader.record(), json_lines_reader.record_start().line));
+            if json_lines_reader.record() == 0 {
+                for _ in 0..num_tokens {
+                    json_lines_reader.json_reader().next().await.unwrap();
+                }
+            }
+            else {
+                json_lines_reader.json_reader().skip_value().await.unwrap();
+            }
+        }
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use crate::nonblocking::array::JsonArray;
+use crate::nonblocking::io::NonBlockingWrite;
+use crate::nonblocking::json_writer::JsonWriter;
+use crate::nonblocking::object::JsonObject;
+use crate::shared::*;
+
+/// A [JsonLinesWriter] writes a [JSON Lines](https://jsonlines.org) stream, i.e. a stream with one
+///  JSON value per line. It always uses [CompactFormatter] so that each record fits into a single
+///  line.
+///
+/// Each record is a top-level [JsonObject] or [JsonArray] handed out by
+///  [JsonLinesWriter::start_object] or [JsonLinesWriter::start_array], which writes the newline
+///  terminating the record when its `end()` function is called, or a scalar written by one of the
+///  `write_*_value` functions.
+///
+/// ```
+/// use json_streaming::nonblocking::*;
+///
+/// async fn write_records<W: NonBlockingWrite>(w: &mut W) -> Result<(), W::Error> {
+///     let mut json_lines_writer = JsonLinesWriter::new(w);
+///     for i in 0..2 {
+///         let mut o = json_lines_writer.start_object().await?;
+///         o.write_u32_value("a", i).await?;
+///         o.end().await?;
+///     }
+///     json_lines_writer.write_string_value("done").await
+/// }
+/// ```
+pub struct JsonLinesWriter<'a, W: NonBlockingWrite, FF: FloatFormat = DefaultFloatFormat> {
+    json_writer: JsonWriter<'a, W, CompactFormatter, FF>,
+    num_records: usize,
+}
+
+impl<'a, W: NonBlockingWrite> JsonLinesWriter<'a, W, DefaultFloatFormat> {
+    /// Creates a [JsonLinesWriter] with [DefaultFloatFormat]
+    pub fn new(inner: &'a mut W) -> Self {
+        JsonLinesWriter::new_with_float_format(inner, DefaultFloatFormat)
+    }
+}
+
+impl<'a, W: NonBlockingWrite, FF: FloatFormat> JsonLinesWriter<'a, W, FF> {
+    /// Creates a [JsonLinesWriter] with an explicitly provided [FloatFormat]
+    pub fn new_with_float_format(inner: &'a mut W, float_format: FF) -> Self {
+        JsonLinesWriter {
+            json_writer: JsonWriter::new(inner, CompactFormatter, float_format),
+            num_records: 0,
+        }
+    }
+
+    /// Returns the number of records that were started so far
+    pub fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    /// Starts a record that is an object. The returned [JsonObject] writes the newline after the
+    ///  record when its `end()` function is called.
+    pub async fn start_object(&mut self) -> Result<JsonObject<'_, 'a, W, CompactFormatter, FF>, W::Error> {
+        self.num_records += 1;
+        JsonObject::new_record(&mut self.json_writer).await
+    }
+
+    /// Starts a record that is an array. The returned [JsonArray] writes the newline after the
+    ///  record when its `end()` function is called.
+    pub async fn start_array(&mut self) -> Result<JsonArray<'_, 'a, W, CompactFormatter, FF>, W::Error> {
+        self.num_records += 1;
+        JsonArray::new_record(&mut self.json_writer).await
+    }
+
+    /// Write a record of type 'string', escaping the provided string value.
+    pub async fn write_string_value(&mut self, value: &str) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_escaped_string(value).await?;
+        self.end_record().await
+    }
+
+    /// Write a record of type 'bool'.
+    pub async fn write_bool_value(&mut self, value: bool) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_bool(value).await?;
+        self.end_record().await
+    }
+
+    /// Write a null literal as a record.
+    pub async fn write_null_value(&mut self) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_bytes(b"null").await?;
+        self.end_record().await
+    }
+
+    /// Write an f64 value as a record. If the value is not finite (i.e. infinite or NaN), a null
+    ///  literal is written instead.
+    pub async fn write_f64_value(&mut self, value: f64) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_f64(value).await?;
+        self.end_record().await
+    }
+
+    /// Write an f32 value as a record. If the value is not finite (i.e. infinite or NaN), a null
+    ///  literal is written instead.
+    pub async fn write_f32_value(&mut self, value: f32) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_f32(value).await?;
+        self.end_record().await
+    }
+
+    async fn end_record(&mut self) -> Result<(), W::Error> {
+        self.json_writer.write_bytes(b"\n").await
+    }
+
+    /// End this [JsonLinesWriter]'s lifetime, returning the [NonBlockingWrite] instance it owned.
+    pub fn into_inner(self) -> Result<&'a mut W, W::Error> {
+        self.json_writer.into_inner()
+    }
+}
+
+macro_rules! write_lines_int {
+    ($t:ty ; $f:ident) => {
+impl<'a, W: NonBlockingWrite, FF: FloatFormat> JsonLinesWriter<'a, W, FF> {
+    /// Write a record with an int value of type $t.
+    pub async fn $f(&mut self, value: $t) -> Result<(), W::Error> {
+        self.num_records += 1;
+        self.json_writer.write_raw_num(value).await?;
+        self.end_record().await
+    }
+}
+    };
+}
+write_lines_int!(i8; write_i8_value);
+write_lines_int!(u8; write_u8_value);
+write_lines_int!(i16; write_i16_value);
+write_lines_int!(u16; write_u16_value);
+write_lines_int!(i32; write_i32_value);
+write_lines_int!(u32; write_u32_value);
+write_lines_int!(i64; write_i64_value);
+write_lines_int!(u64; write_u64_value);
+write_lines_int!(i128; write_i128_value);
+write_lines_int!(u128; write_u128_value);
+write_lines_int!(isize; write_isize_value);
+write_lines_int!(usize; write_usize_value);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[tokio::test]
+    async fn test_json_lines_writer() -> io::Result<()> {
+        let mut buf = Vec::new();
+        let mut json_lines_writer = JsonLinesWriter::new(&mut buf);
+        let mut o = json_lines_writer.start_object().await?;
+        o.write_u32_value("a", 1).await?;
+        let mut a = o.start_array("b").await?;
+        a.write_null_value().await?;
+        a.end().await?;
+        o.end().await?;
+        let mut a = json_lines_writer.start_array().await?;
+        a.start_object().await?.end().await?;
+        a.end().await?;
+        json_lines_writer.write_string_value("x\ny").await?;
+        json_lines_writer.write_bool_value(true).await?;
+        json_lines_writer.write_null_value().await?;
+        json_lines_writer.write_f64_value(1.5).await?;
+        json_lines_writer.write_f32_value(f32::NAN).await?;
+        json_lines_writer.write_i32_value(-3).await?;
+        assert_eq!(json_lines_writer.num_records(), 8);
+        json_lines_writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), "{\"a\":1,\"b\":[null]}\n[{}]\n\"x\\ny\"\ntrue\nnull\n1.5\nnull\n-3\n");
+        Ok(())
+    }
+}
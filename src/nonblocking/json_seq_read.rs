@@ -0,0 +1,224 @@
+use crate::nonblocking::io::NonBlockingRead;
+use crate::nonblocking::read::JsonReader;
+use crate::shared::*;
+
+/// A [JsonSeqReader] reads a JSON text sequence ([RFC 7464](https://www.rfc-editor.org/rfc/rfc7464),
+///  `application/json-seq`), i.e. a stream of records that each start with an ASCII record
+///  separator ([RECORD_SEPARATOR]) and end with a newline. It wraps a [JsonReader] which
+///  application code uses to read each record with the regular `expect_*` functions, and it
+///  takes care of the record structure:
+///
+/// * A record separator inside a record's objects, arrays or strings means that the record was
+///   cut short, and reading it fails with [ParseErrorKind::TruncatedRecord].
+/// * A top-level number must be followed by whitespace, since a number that is cut short looks
+///   like a valid number. Otherwise reading it fails with [ParseErrorKind::TruncatedRecord].
+/// * Another value after a record's value fails with [ParseErrorKind::MultipleTopLevelValues],
+///   and a value at the start of the stream without a record separator fails with
+///   [ParseErrorKind::MissingRecordSeparator].
+/// * Consecutive record separators are skipped, as are empty records.
+///
+/// After an error, [JsonSeqReader::skip_bad_record] skips to the next record separator, which is
+///  the RFC's rule for recovering from truncated records.
+///
+/// ```
+/// use json_streaming::nonblocking::*;
+/// use json_streaming::shared::*;
+///
+/// async fn sum_a<R: NonBlockingRead>(r: &mut R) -> JsonParseResult<u32, R::Error> {
+///     let mut json_seq_reader = JsonSeqReader::new(JsonReader::new(64, r));
+///     let mut sum = 0;
+///     while json_seq_reader.next_record().await? {
+///         match json_seq_reader.json_reader().expect_number::<u32>().await {
+///             Ok(a) => sum += a,
+///             Err(_) => json_seq_reader.skip_bad_record().await?,
+///         }
+///     }
+///     Ok(sum)
+/// }
+/// ```
+pub struct JsonSeqReader<'a, B: AsMut<[u8]>, R: NonBlockingRead, S: AsMut<[u8]> = DefaultNestingBuffer> {
+    json_reader: JsonReader<'a, B, R, S>,
+    num_records: usize,
+    /// the location of the current record's first byte after its record separator
+    record_start: Location,
+    is_in_record: bool,
+}
+
+impl<'a, B: AsMut<[u8]>, R: NonBlockingRead, S: AsMut<[u8]>> JsonSeqReader<'a, B, R, S> {
+    /// Creates a [JsonSeqReader] that reads records with a given [JsonReader], which should not
+    ///  have lenient comma handling.
+    pub fn new(mut json_reader: JsonReader<'a, B, R, S>) -> Self {
+        json_reader.set_record_separators(true);
+        JsonSeqReader {
+            json_reader,
+            num_records: 0,
+            record_start: Location::start(),
+            is_in_record: false,
+        }
+    }
+
+    /// Moves to the next record, returning `false` at the end of the stream. If the current
+    ///  record was not read completely, the rest of it is skipped.
+    ///
+    /// This fails with [ParseErrorKind::MultipleTopLevelValues] if the current record's value is
+    ///  followed by another value, and with [ParseErrorKind::MissingRecordSeparator] if the stream
+    ///  does not start with a record separator. The rest of the record is skipped in both cases,
+    ///  so calling this function again continues with the next record.
+    pub async fn next_record(&mut self) -> JsonParseResult<bool, R::Error> {
+        let mut has_separator = false;
+        if self.is_in_record {
+            self.is_in_record = false;
+            if !self.json_reader.is_after_top_level_value() {
+                self.json_reader.skip_to_record_separator().await?;
+            }
+            else {
+                has_separator = self.json_reader.skip_record_separator().await?;
+                if !has_separator && self.json_reader.skip_whitespace().await? {
+                    let location = self.json_reader.token_start_location();
+                    self.json_reader.skip_to_record_separator().await?;
+                    return Err(JsonParseError::Parse(ParseErrorKind::MultipleTopLevelValues, location, None));
+                }
+            }
+        }
+
+        while self.json_reader.skip_record_separator().await? {
+            has_separator = true;
+        }
+        if !self.json_reader.skip_whitespace().await? {
+            return Ok(false);
+        }
+        self.record_start = self.json_reader.token_start_location();
+        self.num_records += 1;
+        if !has_separator {
+            self.json_reader.skip_to_record_separator().await?;
+            return Err(JsonParseError::Parse(ParseErrorKind::MissingRecordSeparator, self.record_start, None));
+        }
+        self.json_reader.expect_next_top_level_value();
+        self.is_in_record = true;
+        Ok(true)
+    }
+
+    /// Returns the [JsonReader] for reading the current record
+    pub fn json_reader(&mut self) -> &mut JsonReader<'a, B, R, S> {
+        &mut self.json_reader
+    }
+
+    /// Returns the 0-based number of the current record. Empty records are not counted.
+    pub fn record(&self) -> usize {
+        self.num_records.saturating_sub(1)
+    }
+
+    /// Returns the location at which the current record's value starts
+    pub fn record_start(&self) -> Location {
+        self.record_start
+    }
+
+    /// Skips the rest of a record after an error, up to the next record separator, so that
+    ///  [JsonSeqReader::next_record] continues with the next record.
+    pub async fn skip_bad_record(&mut self) -> JsonParseResult<(), R::Error> {
+        self.is_in_record = false;
+        self.json_reader.skip_to_record_separator().await?;
+        Ok(())
+    }
+
+    /// Returns the wrapped [JsonReader]
+    pub fn into_inner(mut self) -> JsonReader<'a, B, R, S> {
+        self.json_reader.set_record_separators(false);
+        self.json_reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::io;
+    use std::io::Cursor;
+
+    #[rstest]
+    #[case::valid("\x1e{\"a\": 1}\n\x1e[2, 3]\n\x1e4\n\x1e\"x\"\n", vec![(0, None), (1, None), (2, None), (3, None)])]
+    #[case::crlf("\x1e{\"a\": 1}\r\n\x1e[2, 3]\r\n", vec![(0, None), (1, None)])]
+    #[case::empty("", vec![])]
+    #[case::empty_records("\x1e\x1e\n\x1e[1]\n\x1e \n", vec![(0, None)])]
+    #[case::without_newline("\x1e[1]\x1e{}", vec![(0, None), (1, None)])]
+    #[case::missing_separator("[1]\n\x1e[2]\n", vec![(0, Some(ParseErrorKind::MissingRecordSeparator)), (1, None)])]
+    #[case::missing_comma("\x1e[1 2]\n\x1e[3]\n", vec![(0, Some(ParseErrorKind::MissingComma)), (1, None)])]
+    #[case::two_values("\x1e1 2\n\x1e3\n", vec![(0, None), (0, Some(ParseErrorKind::MultipleTopLevelValues)), (1, None)])]
+    #[case::truncated_object("\x1e{\"a\": [1, 2\x1e{\"b\": 3}\n", vec![(0, Some(ParseErrorKind::TruncatedRecord)), (1, None)])]
+    #[case::truncated_string("\x1e[\"ab\x1e[3]\n", vec![(0, Some(ParseErrorKind::TruncatedRecord)), (1, None)])]
+    #[case::truncated_literal("\x1e[tr\x1e[3]\n", vec![(0, Some(ParseErrorKind::IncompleteLiteral("true"))), (1, None)])]
+    #[case::truncated_escape("\x1e[\"\\u00\x1e[3]\n", vec![(0, Some(ParseErrorKind::InvalidUnicodeEscape)), (1, None)])]
+    #[case::truncated_number("\x1e123\x1e[3]\n", vec![(0, Some(ParseErrorKind::TruncatedRecord)), (1, None)])]
+    #[case::truncated_number_at_end("\x1e[1]\n\x1e12", vec![(0, None), (1, Some(ParseErrorKind::TruncatedRecord))])]
+    #[case::truncated_at_end("\x1e[1]\n\x1e[1, 2", vec![(0, None), (1, Some(ParseErrorKind::UnexpectedEndOfInput))])]
+    #[case::number_in_array("\x1e[12\x1e[3]\n", vec![(0, Some(ParseErrorKind::TruncatedRecord)), (1, None)])]
+    #[tokio::test]
+    async fn test_json_seq_reader(#[case] json: &str, #[case] expected: Vec<(usize, Option<ParseErrorKind>)>) -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(json.as_bytes().to_vec());
+        let mut json_seq_reader = JsonSeqReader::new(JsonReader::new(64, &mut r));
+        let mut actual = Vec::new();
+        loop {
+            match json_seq_reader.next_record().await {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(JsonParseError::Parse(kind, _, _)) => {
+                    actual.push((json_seq_reader.record(), Some(kind)));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+            match json_seq_reader.json_reader().skip_value().await {
+                Ok(()) => actual.push((json_seq_reader.record(), None)),
+                Err(JsonParseError::Parse(kind, _, _)) => {
+                    actual.push((json_seq_reader.record(), Some(kind)));
+                    json_seq_reader.skip_bad_record().await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_truncated_record_location() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(b"\x1e[1]\n\x1e[1, \x1e2\n".to_vec());
+        let mut json_seq_reader = JsonSeqReader::new(JsonReader::new(64, &mut r));
+        assert!(json_seq_reader.next_record().await?);
+        json_seq_reader.json_reader().skip_value().await?;
+        assert!(json_seq_reader.next_record().await?);
+        assert_eq!(json_seq_reader.record_start(), Location { offset: 6, line: 2, column: 2 });
+
+        match json_seq_reader.json_reader().skip_value().await {
+            Err(JsonParseError::Parse(ParseErrorKind::TruncatedRecord, location, _)) => assert_eq!(location.offset, 10),
+            other => panic!("unexpected result {:?}", other),
+        }
+        json_seq_reader.skip_bad_record().await?;
+        assert!(json_seq_reader.next_record().await?);
+        assert_eq!(json_seq_reader.json_reader().expect_number::<u32>().await?, 2);
+        assert!(!json_seq_reader.next_record().await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_record_not_read_completely() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(b"\x1e{\"a\": [1, 2]}\n\x1e\"skipped\"\n\x1e{\"b\": 3}\n".to_vec());
+        let mut json_seq_reader = JsonSeqReader::new(JsonReader::new(64, &mut r));
+        assert!(json_seq_reader.next_record().await?);
+        json_seq_reader.json_reader().expect_start_object().await?;
+        assert!(json_seq_reader.next_record().await?);
+        assert!(json_seq_reader.next_record().await?);
+        assert_eq!(json_seq_reader.record(), 2);
+        json_seq_reader.json_reader().expect_start_object().await?;
+        assert_eq!(json_seq_reader.json_reader().expect_key().await?, Some("b"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_top_level_number_without_seq_reader() -> JsonParseResult<(), io::Error> {
+        let mut r = Cursor::new(b"123".to_vec());
+        let mut json_reader = JsonSeqReader::new(JsonReader::new(64, &mut r)).into_inner();
+        assert_eq!(json_reader.expect_number::<u32>().await?, 123);
+        Ok(())
+    }
+}
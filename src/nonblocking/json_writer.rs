@@ -36,29 +36,8 @@ impl <'a, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonWriter<'a,
     /// Internal API for writing a string as an escaped JSON string.
     pub async fn write_escaped_string(&mut self, s: &str) -> Result<(), W::Error> {
         self.write_bytes(b"\"").await?;
-        for b in s.bytes() {
-            match b {
-                b'"' => self.write_bytes(b"\\\"").await?,
-                b'\\' => self.write_bytes(b"\\\\").await?,
-                b'\x08' => self.write_bytes(b"\\b").await?,
-                b'\x0c' => self.write_bytes(b"\\f").await?,
-                b'\n' => self.write_bytes(b"\\n").await?,
-                b'\r' => self.write_bytes(b"\\r").await?,
-                b'\t' => self.write_bytes(b"\\t").await?,
-                b if b < 0x20 => {
-                    static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
-                    let bytes = &[
-                        b'\\',
-                        b'u',
-                        b'0',
-                        b'0',
-                        HEX_DIGITS[(b >> 4) as usize],
-                        HEX_DIGITS[(b & 0xF) as usize],
-                    ];
-                    self.write_bytes(bytes).await?
-                },
-                b => self.write_bytes(&[b]).await?,
-            }
+        for chunk in json_escaped_chunks(s) {
+            self.write_bytes(chunk).await?;
         }
         self.write_bytes(b"\"").await?;
         Ok(())
@@ -117,6 +96,25 @@ impl <'a, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonWriter<'a,
         self.inner.write_all(self.formatter.indent().as_bytes()).await
     }
 
+    /// Starts a record of a JSON text sequence ([RFC 7464](https://www.rfc-editor.org/rfc/rfc7464),
+    ///  `application/json-seq`) by writing a record separator. The record's value is written as
+    ///  usual, e.g. with a `JsonObject`, followed by [JsonWriter::end_seq_record].
+    pub async fn start_seq_record(&mut self) -> Result<(), W::Error> {
+        self.write_bytes(&[RECORD_SEPARATOR]).await
+    }
+
+    /// Ends a record of a JSON text sequence by writing a newline, which also marks a top-level
+    ///  number as complete for readers
+    pub async fn end_seq_record(&mut self) -> Result<(), W::Error> {
+        self.write_bytes(b"\n").await
+    }
+
+    /// Internal API for serializing with the writer's formatter outside of async code
+    #[cfg(feature = "serde")]
+    pub(crate) fn formatter_mut(&mut self) -> &mut F {
+        &mut self.formatter
+    }
+
     /// End this [JsonWriter]'s lifetime, returning the [Write] instance it owned. This function
     ///  returns any unreported errors.
     pub fn into_inner(self) -> Result<&'a mut W, W::Error> {
@@ -179,7 +177,7 @@ impl<'a, W: NonBlockingWrite, FF: FloatFormat> FormatWrapper<'a, W, FF> {
         Self {
             buf: &mut inner.number_write_buf,
             writer: &mut inner.inner,
-            pd: PhantomData::default(),
+            pd: PhantomData,
         }
     }
 
@@ -187,17 +185,17 @@ impl<'a, W: NonBlockingWrite, FF: FloatFormat> FormatWrapper<'a, W, FF> {
         use core::fmt::Write;
 
         let _ = write!(&mut self.buf, "{}", value);
-        self.writer.write_all(&self.buf.as_bytes()).await
+        self.writer.write_all(self.buf.as_bytes()).await
     }
 
     async fn write_f64(&mut self, value: f64) -> Result<(), W::Error> {
         let _ = FF::write_f64(&mut self.buf, value);
-        self.writer.write_all(&self.buf.as_bytes()).await
+        self.writer.write_all(self.buf.as_bytes()).await
     }
 
     async fn write_f32(&mut self, value: f32) -> Result<(), W::Error> {
         let _ = FF::write_f32(&mut self.buf, value);
-        self.writer.write_all(&self.buf.as_bytes()).await
+        self.writer.write_all(self.buf.as_bytes()).await
     }
 }
 
@@ -367,4 +365,20 @@ mod tests {
         let written = writer.into_inner().unwrap().to_vec();
         assert_eq!(&written, b"_1.2_64_3.4_32");
     }
+
+    #[tokio::test]
+    async fn test_seq_records() -> std::io::Result<()> {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_pretty(&mut buf);
+        writer.start_seq_record().await?;
+        let mut o = crate::nonblocking::object::JsonObject::new(&mut writer).await?;
+        o.write_u32_value("a", 1).await?;
+        o.end().await?;
+        writer.end_seq_record().await?;
+        writer.start_seq_record().await?;
+        writer.write_raw_num(2).await?;
+        writer.end_seq_record().await?;
+        assert_eq!(as_written_string(writer), "\x1e{\n  \"a\": 1\n}\n\x1e2\n");
+        Ok(())
+    }
 }
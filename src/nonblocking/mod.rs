@@ -1,8 +1,15 @@
 pub(crate) mod array;
 pub(crate) mod io;
+pub(crate) mod json_lines_read;
+pub(crate) mod json_lines_write;
+pub(crate) mod json_seq_read;
 pub(crate) mod json_writer;
 pub(crate) mod object;
+pub(crate) mod query;
 pub(crate) mod read;
+#[cfg(feature = "serde")]
+pub(crate) mod serialize;
+pub(crate) mod validate;
 
 #[cfg(not(test))]
 #[allow(unused_imports)]
@@ -10,13 +17,23 @@ pub use array::*;
 #[allow(unused_imports)]
 pub use io::*;
 #[allow(unused_imports)]
+pub use json_lines_read::*;
+#[allow(unused_imports)]
+pub use json_lines_write::*;
+#[allow(unused_imports)]
+pub use json_seq_read::*;
+#[allow(unused_imports)]
 pub use json_writer::*;
 #[cfg(not(test))]
 #[allow(unused_imports)]
 pub use object::*;
+#[allow(unused_imports)]
+pub use query::*;
 
 #[allow(unused_imports)]
 pub use read::*;
+#[allow(unused_imports)]
+pub use validate::*;
 
 
 #[cfg(test)]
@@ -17,6 +17,8 @@ pub struct JsonObject<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFo
     writer: &'a mut JsonWriter<'b, W, F, FF>,
     is_initial: bool,
     is_ended: bool,
+    /// a JSON Lines record, see `JsonLinesWriter`
+    is_record: bool,
 }
 impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<'a, 'b, W, F, FF> {
     /// Create a new [JsonObject] instance. Application code can do this explicitly only initially
@@ -28,9 +30,18 @@ impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<
             writer,
             is_initial: true,
             is_ended: false,
+            is_record: false,
         })
     }
 
+    /// Creates a top-level [JsonObject] that is a record of JSON Lines, writing a newline after the
+    ///  closing bracket
+    pub(crate) async fn new_record(writer: &'a mut JsonWriter<'b, W, F, FF>) -> Result<Self, W::Error> {
+        let mut result = Self::new(writer).await?;
+        result.is_record = true;
+        Ok(result)
+    }
+
     async fn write_key(&mut self, key: &str) -> Result<(), W::Error> {
         if !self.is_initial {
             self.writer.write_bytes(b",").await?;
@@ -79,6 +90,20 @@ impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<
         self.writer.write_f32(value).await
     }
 
+    /// Write a key/value pair with a value that is serialized with serde (requires the `serde`
+    ///  feature), honoring this writer's [JsonFormatter] and [FloatFormat]. The representation is
+    ///  the same as `serde_json`'s: structs and maps as objects, sequences and tuples as arrays, and
+    ///  enum variants as a string or an object with the variant's name as its single key.
+    ///
+    /// Since serde's API is synchronous, the value is serialized into a buffer which is then
+    ///  written. Nothing is written if serialization fails with [JsonSerializeError::Data].
+    #[cfg(feature = "serde")]
+    pub async fn write_serialize<T: serde::Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), JsonSerializeError<W::Error>> {
+        let buf = self.writer.serialize_to_buffer(value)?;
+        self.write_key(key).await?;
+        Ok(self.writer.write_bytes(&buf).await?)
+    }
+
     /// Start a nested object under a given key. This function returns a new [JsonObject] instance
     ///  for writing elements to the nested object. When the returned [JsonObject] goes out of scope
     ///  (per syntactic scope or an explicit call to `end()`), the nested object is closed, and
@@ -87,7 +112,7 @@ impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<
     where 'a: 'c, 'x: 'c
     {
         self.write_key(key).await?;
-        JsonObject::new(&mut self.writer).await
+        JsonObject::new(self.writer).await
     }
 
     /// Start a nested array under a given key. This function returns a new [JsonArray] instance
@@ -111,6 +136,9 @@ impl<'a, 'b, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonObject<
         self.writer.write_format_before_end_nested(self.is_initial).await?;
         self.writer.write_bytes(b"}").await?;
         self.is_ended = true;
+        if self.is_record {
+            self.writer.write_bytes(b"\n").await?;
+        }
         Ok(())
     }
 }
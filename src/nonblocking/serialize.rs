@@ -0,0 +1,220 @@
+use crate::nonblocking::io::NonBlockingWrite;
+use crate::nonblocking::json_writer::JsonWriter;
+use crate::shared::*;
+use core::convert::Infallible;
+use core::fmt::Display;
+use core::marker::PhantomData;
+
+impl<'a, W: NonBlockingWrite, F: JsonFormatter, FF: FloatFormat> JsonWriter<'a, W, F, FF> {
+    /// Internal API for serializing a value with serde into a buffer, using this writer's
+    ///  [JsonFormatter] and [FloatFormat]. The buffer is written asynchronously afterwards.
+    pub(crate) fn serialize_to_buffer<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<Vec<u8>, JsonSerializeError<W::Error>> {
+        let mut sink = BufferSink::<F, FF> {
+            buf: Vec::new(),
+            formatter: self.formatter_mut(),
+            pd: PhantomData,
+        };
+        match serialize_to_sink(&mut sink, value) {
+            Ok(()) => Ok(sink.buf),
+            Err(JsonSerializeError::Data(msg)) => Err(JsonSerializeError::Data(msg)),
+            Err(JsonSerializeError::Io(e)) => match e {},
+        }
+    }
+}
+
+/// Collects serialized JSON in memory, with a [JsonWriter]'s formatter
+struct BufferSink<'f, F: JsonFormatter, FF: FloatFormat> {
+    buf: Vec<u8>,
+    formatter: &'f mut F,
+    pd: PhantomData<FF>,
+}
+
+impl<'f, F: JsonFormatter, FF: FloatFormat> core::fmt::Write for BufferSink<'f, F, FF> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.buf.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<'f, F: JsonFormatter, FF: FloatFormat> JsonSink for BufferSink<'f, F, FF> {
+    type Error = Infallible;
+
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), Infallible> {
+        self.buf.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn write_escaped_string(&mut self, s: &str) -> Result<(), Infallible> {
+        self.buf.push(b'"');
+        for chunk in json_escaped_chunks(s) {
+            self.buf.extend_from_slice(chunk);
+        }
+        self.buf.push(b'"');
+        Ok(())
+    }
+
+    fn write_f64(&mut self, value: f64) -> Result<(), Infallible> {
+        let _ = FF::write_f64(self, value);
+        Ok(())
+    }
+
+    fn write_f32(&mut self, value: f32) -> Result<(), Infallible> {
+        let _ = FF::write_f32(self, value);
+        Ok(())
+    }
+
+    fn write_raw_num(&mut self, value: impl Display) -> Result<(), Infallible> {
+        use core::fmt::Write;
+        let _ = write!(self, "{}", value);
+        Ok(())
+    }
+
+    fn write_format_after_key(&mut self) -> Result<(), Infallible> {
+        self.buf.extend_from_slice(self.formatter.after_key().as_bytes());
+        Ok(())
+    }
+
+    fn write_format_after_start_nested(&mut self) -> Result<(), Infallible> {
+        self.buf.extend_from_slice(self.formatter.after_start_nested().as_bytes());
+        Ok(())
+    }
+
+    fn write_format_after_element(&mut self) -> Result<(), Infallible> {
+        self.buf.extend_from_slice(self.formatter.after_element().as_bytes());
+        Ok(())
+    }
+
+    fn write_format_before_end_nested(&mut self, is_empty: bool) -> Result<(), Infallible> {
+        self.buf.extend_from_slice(self.formatter.before_end_nested(is_empty).as_bytes());
+        Ok(())
+    }
+
+    fn write_format_indent(&mut self) -> Result<(), Infallible> {
+        self.buf.extend_from_slice(self.formatter.indent().as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nonblocking::array::JsonArray;
+    use crate::nonblocking::json_writer::JsonWriter;
+    use crate::nonblocking::object::JsonObject;
+    use crate::shared::*;
+    use rstest::*;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+    use std::fmt::Write;
+    use std::io;
+
+    #[derive(Serialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Line(Point, Point),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[rstest]
+    #[case::bool(true, "true")]
+    #[case::u128(u128::MAX, "340282366920938463463374607431768211455")]
+    #[case::f64(1.5f64, "1.5")]
+    #[case::f32_nan(f32::NAN, "null")]
+    #[case::str("a\nb\u{1}", "\"a\\nb\\u0001\"")]
+    #[case::none(None::<u8>, "null")]
+    #[case::vec(vec![1, 2], "[1,2]")]
+    #[case::empty_vec(Vec::<u8>::new(), "[]")]
+    #[case::tuple((1, "a"), "[1,\"a\"]")]
+    #[case::struct_(Point { x: 1, y: -2 }, "{\"x\":1,\"y\":-2}")]
+    #[case::map(BTreeMap::from([("b", 2), ("a", 1)]), "{\"a\":1,\"b\":2}")]
+    #[case::unit_variant(Shape::Empty, "\"Empty\"")]
+    #[case::newtype_variant(Shape::Circle(1.5), "{\"Circle\":1.5}")]
+    #[case::tuple_variant(Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 }), "{\"Line\":[{\"x\":0,\"y\":0},{\"x\":1,\"y\":1}]}")]
+    #[case::struct_variant(Shape::Rect { width: 3, height: 4 }, "{\"Rect\":{\"width\":3,\"height\":4}}")]
+    #[tokio::test]
+    async fn test_write_serialize<T: Serialize>(#[case] value: T, #[case] expected: &str) -> Result<(), JsonSerializeError<io::Error>> {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_compact(&mut buf);
+        let mut o = JsonObject::new(&mut writer).await?;
+        o.write_serialize("k", &value).await?;
+        let mut a = o.start_array("a").await?;
+        a.write_serialize(&value).await?;
+        a.write_serialize(&value).await?;
+        a.end().await?;
+        o.end().await?;
+        writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), format!("{{\"k\":{},\"a\":[{},{}]}}", expected, expected, expected));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_serialize_pretty() -> Result<(), JsonSerializeError<io::Error>> {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_pretty(&mut buf);
+        let mut o = JsonObject::new(&mut writer).await?;
+        o.write_serialize("shapes", &vec![Shape::Empty, Shape::Rect { width: 3, height: 4 }]).await?;
+        o.write_serialize("empty", &Vec::<u8>::new()).await?;
+        o.write_u8_value("after", 1).await?;
+        o.end().await?;
+        writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), r#"{
+  "shapes": [
+    "Empty",
+    {
+      "Rect": {
+        "width": 3,
+        "height": 4
+      }
+    }
+  ],
+  "empty": [],
+  "after": 1
+}"#);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_serialize_float_format() -> Result<(), JsonSerializeError<io::Error>> {
+        struct OtherFf;
+        impl FloatFormat for OtherFf {
+            fn write_f64(f: &mut impl Write, value: f64) -> std::fmt::Result {
+                write!(f, "_{}_64", value)
+            }
+
+            fn write_f32(f: &mut impl Write, value: f32) -> std::fmt::Result {
+                write!(f, "_{}_32", value)
+            }
+        }
+
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new(&mut buf, CompactFormatter, OtherFf);
+        let mut a = JsonArray::new(&mut writer).await?;
+        a.write_serialize(&(1.2f64, 3.4f32)).await?;
+        a.end().await?;
+        writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), "[[_1.2_64,_3.4_32]]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_serialize_invalid_key() -> Result<(), JsonSerializeError<io::Error>> {
+        let mut buf = Vec::new();
+        let mut writer = JsonWriter::new_compact(&mut buf);
+        let mut o = JsonObject::new(&mut writer).await?;
+        match o.write_serialize("k", &BTreeMap::from([((1u8, 2u8), 3u8)])).await {
+            Err(JsonSerializeError::Data(msg)) => assert_eq!(msg, "key must be a string"),
+            other => panic!("unexpected result {:?}", other),
+        }
+        o.write_u8_value("after", 1).await?;
+        o.end().await?;
+        writer.into_inner()?;
+        assert_eq!(String::from_utf8(buf).unwrap(), "{\"after\":1}");
+        Ok(())
+    }
+}
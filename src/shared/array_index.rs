@@ -0,0 +1,50 @@
+use crate::shared::*;
+
+/// A [JsonArrayIndex] holds the locations at which the elements of a stream's top-level array
+///  start, allowing random access to the elements of a large document, e.g. for paging through
+///  a file of records.
+///
+/// `JsonReader::index_array_elements()` creates the index by reading the entire array once, and
+///  `JsonReader::new_at_array_element()` creates a reader at an element, seeking the input to the
+///  element's offset. The reader is inside the top-level array, so `expect_*` functions can read
+///  the element right away, and subsequent elements after it.
+///
+/// The index is a plain list of locations, so an application can persist it and restore it with
+///  [JsonArrayIndex::from_locations].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonArrayIndex {
+    locations: Vec<Location>,
+}
+
+impl JsonArrayIndex {
+    /// Creates an index from the start locations of a top-level array's elements, e.g. after they
+    ///  were loaded from a file
+    pub fn from_locations(locations: Vec<Location>) -> JsonArrayIndex {
+        JsonArrayIndex { locations }
+    }
+
+    /// Returns the number of elements in the array
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Returns the location at which the element with a given index starts, or `None` if the
+    ///  array has no such element
+    pub fn location(&self, index: usize) -> Option<Location> {
+        self.locations.get(index).copied()
+    }
+
+    pub fn locations(&self) -> &[Location] {
+        &self.locations
+    }
+
+    /// Returns a [ReaderCheckpoint] for reading the array from the element with a given index on,
+    ///  or `None` if the array has no such element
+    pub fn checkpoint(&self, index: usize) -> Option<ReaderCheckpoint> {
+        self.location(index).map(ReaderCheckpoint::in_top_level_array)
+    }
+}
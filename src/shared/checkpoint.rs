@@ -0,0 +1,224 @@
+use crate::shared::*;
+use core::error::Error;
+
+const CHECKPOINT_VERSION: u8 = 1;
+/// version, flags, parked byte, and depth, offset, line and column as 64-bit integers
+const CHECKPOINT_HEADER_LEN: usize = 3 + 4 * 8;
+
+const FLAG_LENIENT: u8 = 1;
+const FLAG_PARKED: u8 = 2;
+const STATE_SHIFT: u8 = 2;
+
+/// A [ReaderCheckpoint] captures the state of a `JsonReader` between two tokens, so that reading
+///  can be resumed later from the same position in the input, e.g. after a crash during a long
+///  running ingestion. It contains the position in the input, the stack of open objects and
+///  arrays and the parsing state, but no data that was read.
+///
+/// A checkpoint is created by `JsonReader::checkpoint()`, and `JsonReader::new_from_checkpoint()`
+///  creates a reader that continues from it. The new reader must read from the input at the
+///  checkpoint's [location](ReaderCheckpoint::location), e.g. a file that was seeked to
+///  `location.offset`.
+///
+/// A checkpoint can be persisted in a compact binary encoding (see [ReaderCheckpoint::encode]
+///  and [ReaderCheckpoint::decode]) that is independent of the platform.
+///
+/// Configuration like JSON5 support or path tracking is not part of a checkpoint and needs to be
+///  applied to the resumed reader again. Keys and path segments of the objects and arrays that
+///  were open at the checkpoint are not part of it either, so the resumed reader does not detect
+///  duplicate keys in those objects, and it reports no path until it is back at the top level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReaderCheckpoint<S: AsMut<[u8]> = DefaultNestingBuffer> {
+    /// the location in the input at which reading continues
+    pub location: Location,
+    state: ReaderState,
+    nesting_buf: S,
+    depth: usize,
+    parked_next: Option<u8>,
+    lenient_comma_handling: bool,
+}
+
+impl ReaderCheckpoint {
+    /// A checkpoint before an entry of a top-level array that starts at a given location
+    pub(crate) fn in_top_level_array(location: Location) -> ReaderCheckpoint {
+        ReaderCheckpoint {
+            location,
+            state: ReaderState::BeforeEntry,
+            nesting_buf: default_nesting_buffer(),
+            depth: 1,
+            parked_next: None,
+            lenient_comma_handling: false,
+        }
+    }
+}
+
+impl <S: AsMut<[u8]>> ReaderCheckpoint<S> {
+    /// Returns the number of objects and arrays that were open at the checkpoint
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of bytes that [ReaderCheckpoint::encode] writes
+    pub fn encoded_len(&self) -> usize {
+        CHECKPOINT_HEADER_LEN + self.depth.div_ceil(8)
+    }
+
+    /// Writes the checkpoint's binary encoding to a buffer, returning the number of bytes written,
+    ///  or `None` if the buffer is too small (see [ReaderCheckpoint::encoded_len]).
+    pub fn encode(&mut self, out: &mut [u8]) -> Option<usize> {
+        let len = self.encoded_len();
+        if out.len() < len {
+            return None;
+        }
+
+        let state = match self.state {
+            ReaderState::Initial => 0,
+            ReaderState::BeforeEntry => 1,
+            ReaderState::AfterKey => 2,
+            ReaderState::AfterValue => 3,
+        };
+        let mut flags = state << STATE_SHIFT;
+        if self.lenient_comma_handling {
+            flags |= FLAG_LENIENT;
+        }
+        if self.parked_next.is_some() {
+            flags |= FLAG_PARKED;
+        }
+
+        out[0] = CHECKPOINT_VERSION;
+        out[1] = flags;
+        out[2] = self.parked_next.unwrap_or_default();
+        let numbers = [self.depth, self.location.offset, self.location.line, self.location.column];
+        for (i, n) in numbers.into_iter().enumerate() {
+            out[3 + i * 8 .. 11 + i * 8].copy_from_slice(&(n as u64).to_le_bytes());
+        }
+        out[CHECKPOINT_HEADER_LEN..len].copy_from_slice(&self.nesting_buf.as_mut()[..self.depth.div_ceil(8)]);
+        Some(len)
+    }
+
+    /// Reads a checkpoint from its binary encoding, using a given buffer for the nesting of objects
+    ///  and arrays. Returns `None` if the encoding is malformed or if the nesting buffer is too
+    ///  small for the checkpoint's depth.
+    pub fn decode(bytes: &[u8], mut nesting_buf: S) -> Option<Self> {
+        if bytes.len() < CHECKPOINT_HEADER_LEN || bytes[0] != CHECKPOINT_VERSION {
+            return None;
+        }
+        let flags = bytes[1];
+        if flags >> (STATE_SHIFT + 2) != 0 {
+            return None;
+        }
+        let state = match (flags >> STATE_SHIFT) & 3 {
+            0 => ReaderState::Initial,
+            1 => ReaderState::BeforeEntry,
+            2 => ReaderState::AfterKey,
+            _ => ReaderState::AfterValue,
+        };
+
+        let mut numbers = [0usize; 4];
+        for (i, n) in numbers.iter_mut().enumerate() {
+            let mut le_bytes = [0u8; 8];
+            le_bytes.copy_from_slice(&bytes[3 + i * 8 .. 11 + i * 8]);
+            *n = usize::try_from(u64::from_le_bytes(le_bytes)).ok()?;
+        }
+        let [depth, offset, line, column] = numbers;
+
+        let nesting_len = depth.div_ceil(8);
+        let nesting = nesting_buf.as_mut();
+        if bytes.len() != CHECKPOINT_HEADER_LEN + nesting_len || nesting.len() < nesting_len || line == 0 || column == 0 {
+            return None;
+        }
+        nesting[..nesting_len].copy_from_slice(&bytes[CHECKPOINT_HEADER_LEN..]);
+
+        Some(ReaderCheckpoint {
+            location: Location { offset, line, column },
+            state,
+            nesting_buf,
+            depth,
+            parked_next: (flags & FLAG_PARKED != 0).then_some(bytes[2]),
+            lenient_comma_handling: flags & FLAG_LENIENT != 0,
+        })
+    }
+}
+
+impl <B: AsMut<[u8]>, S: AsMut<[u8]>, E: Error> ReaderInner<B, S, E> {
+    /// Captures the reader's state, or returns `None` if it is not between two tokens, i.e. if a
+    ///  token was peeked but not read, or after an error inside a string literal
+    pub fn checkpoint(&self) -> Option<ReaderCheckpoint<S>> where S: Clone {
+        if self.parked_token.is_some() || self.in_string.is_some() {
+            return None;
+        }
+        Some(ReaderCheckpoint {
+            location: self.cur_location,
+            state: self.state,
+            nesting_buf: self.nesting_buf.clone(),
+            depth: self.depth,
+            parked_next: self.parked_next,
+            lenient_comma_handling: self.lenient_comma_handling,
+        })
+    }
+
+    pub fn from_checkpoint(buf: B, checkpoint: ReaderCheckpoint<S>) -> Self {
+        let mut inner = ReaderInner::new(buf, checkpoint.nesting_buf, checkpoint.lenient_comma_handling);
+        inner.state = checkpoint.state;
+        inner.depth = checkpoint.depth;
+        inner.resumed_depth = checkpoint.depth;
+        inner.current_container = inner.innermost_container();
+        inner.parked_next = checkpoint.parked_next;
+        inner.cur_location = checkpoint.location;
+        inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn checkpoint(depth: usize, nesting: u8, parked_next: Option<u8>, lenient_comma_handling: bool) -> ReaderCheckpoint {
+        let mut nesting_buf = default_nesting_buffer();
+        nesting_buf[0] = nesting;
+        ReaderCheckpoint {
+            location: Location { offset: 1234, line: 56, column: 7 },
+            state: ReaderState::AfterKey,
+            nesting_buf,
+            depth,
+            parked_next,
+            lenient_comma_handling,
+        }
+    }
+
+    #[rstest]
+    #[case::top_level(checkpoint(0, 0, None, false), 35)]
+    #[case::nested(checkpoint(3, 0b101, None, false), 36)]
+    #[case::parked(checkpoint(1, 0, Some(b','), false), 36)]
+    #[case::lenient(checkpoint(2, 0b10, Some(b'\n'), true), 36)]
+    #[case::nested_deeply(checkpoint(9, 0b1, None, false), 37)]
+    fn test_encode_decode(#[case] mut checkpoint: ReaderCheckpoint, #[case] expected_len: usize) {
+        assert_eq!(checkpoint.encoded_len(), expected_len);
+        let mut out = [0u8; 64];
+        assert_eq!(checkpoint.encode(&mut out), Some(expected_len));
+        assert_eq!(checkpoint.encode(&mut out[..expected_len - 1]), None);
+
+        let decoded = ReaderCheckpoint::decode(&out[..expected_len], default_nesting_buffer());
+        assert_eq!(decoded, Some(checkpoint));
+    }
+
+    #[rstest]
+    #[case::empty(b"")]
+    #[case::truncated(&[1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0])]
+    #[case::wrong_version(&[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0])]
+    #[case::unknown_flags(&[1, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0])]
+    #[case::line_zero(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0])]
+    #[case::trailing_bytes(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0])]
+    #[case::too_deep(&[1, 0, 0, 129, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])]
+    fn test_decode_malformed(#[case] bytes: &[u8]) {
+        assert_eq!(ReaderCheckpoint::decode(bytes, [0u8; 16]), None);
+    }
+
+    #[test]
+    fn test_decode_valid() {
+        let bytes = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+        let checkpoint = ReaderCheckpoint::decode(&bytes, default_nesting_buffer()).unwrap();
+        assert_eq!(checkpoint.location, Location::start());
+        assert_eq!(checkpoint.depth(), 0);
+    }
+}
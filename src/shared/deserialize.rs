@@ -0,0 +1,44 @@
+use crate::shared::*;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// A [JsonDeserializeError] is the error type of the serde `Deserializer` on top of a
+///  `JsonReader`: Either the JSON stream is invalid or can not be read, or it does not match
+///  the deserialized type, e.g. because a field is missing or has the wrong type.
+#[derive(Debug)]
+pub enum JsonDeserializeError<E: Error> {
+    Json(JsonParseError<E>),
+    /// a mismatch between the JSON and the deserialized type, with the location at which it was
+    ///  detected (if it is known)
+    Data(String, Option<Location>),
+}
+impl <E: Error> JsonDeserializeError<E> {
+    /// Adds a location to a [JsonDeserializeError::Data] error that does not have one yet
+    pub(crate) fn at_location(self, location: Location) -> Self {
+        match self {
+            JsonDeserializeError::Data(msg, None) => JsonDeserializeError::Data(msg, Some(location)),
+            other => other,
+        }
+    }
+}
+impl <E: Error> Display for JsonDeserializeError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JsonDeserializeError::Json(e) => write!(f, "{}", e),
+            JsonDeserializeError::Data(msg, None) => write!(f, "data error: {}", msg),
+            JsonDeserializeError::Data(msg, Some(location)) => write!(f, "data error: {} @ {}", msg, location),
+        }
+    }
+}
+impl <E: Error> Error for JsonDeserializeError<E> {
+}
+impl <E: Error> From<JsonParseError<E>> for JsonDeserializeError<E> {
+    fn from(value: JsonParseError<E>) -> Self {
+        JsonDeserializeError::Json(value)
+    }
+}
+impl <E: Error> serde::de::Error for JsonDeserializeError<E> {
+    fn custom<T: Display>(msg: T) -> Self {
+        JsonDeserializeError::Data(msg.to_string(), None)
+    }
+}
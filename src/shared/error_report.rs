@@ -0,0 +1,175 @@
+use crate::shared::{JsonParseError, JsonPath};
+use core::error::Error;
+use core::fmt::{Display, Formatter, Write};
+
+/// Records consumed input in a ring buffer of recent bytes, `offset` being the offset of the
+///  first byte in `bytes`. The byte at offset `n` is stored at index `n % ring.len()`.
+pub(crate) fn record_context(ring: &mut [u8], offset: usize, bytes: &[u8]) {
+    let size = ring.len();
+    if size == 0 {
+        return;
+    }
+    // only the most recent bytes fit into the ring buffer
+    let skip = bytes.len().saturating_sub(size);
+    let bytes = &bytes[skip..];
+    let start = (offset + skip) % size;
+    let len_first = bytes.len().min(size - start);
+    ring[start..start + len_first].copy_from_slice(&bytes[..len_first]);
+    ring[..bytes.len() - len_first].copy_from_slice(&bytes[len_first..]);
+}
+
+/// A [JsonErrorReport] renders a [JsonParseError] for humans, e.g. for logging: The error
+///  message is followed by the line of input that contains the error's location, as far as it
+///  is in the `JsonReader`'s context buffer (see `JsonReader::with_provided_context_buffer()`),
+///  and a caret under the error's column.
+///
+/// ```text
+/// parse error: missing comma @ line 2, column 18 (offset 19) (path /a/2)
+///   "a": [1, 2, 3 4
+///                  ^
+/// ```
+///
+/// The context buffer contains only input that was consumed, so the line may end at the error.
+///
+/// If the `JsonReader` tracks the JSON path, the report contains the path of the error.
+pub struct JsonErrorReport<'a, E: Error> {
+    error: &'a JsonParseError<E>,
+    context: &'a [u8],
+    /// the offset after the last byte that was consumed
+    end_offset: usize,
+    path: Option<JsonPath<'a>>,
+}
+impl <'a, E: Error> JsonErrorReport<'a, E> {
+    pub(crate) fn new(error: &'a JsonParseError<E>, context: &'a [u8], end_offset: usize, path: Option<JsonPath<'a>>) -> JsonErrorReport<'a, E> {
+        JsonErrorReport {
+            error,
+            context,
+            end_offset,
+            path,
+        }
+    }
+
+    fn byte_at(&self, offset: usize) -> u8 {
+        self.context[offset % self.context.len()]
+    }
+
+    /// Writes the context between two offsets, which must be contiguous in the ring buffer
+    fn write_text(&self, f: &mut Formatter<'_>, from: usize, to: usize) -> core::fmt::Result {
+        let size = self.context.len();
+        let start = from % size;
+        let bytes = &self.context[start..start + (to - from)];
+        for chunk in bytes.utf8_chunks() {
+            for ch in chunk.valid().chars() {
+                f.write_char(if ch.is_control() && ch != '\t' { char::REPLACEMENT_CHARACTER } else { ch })?;
+            }
+            if !chunk.invalid().is_empty() {
+                f.write_char(char::REPLACEMENT_CHARACTER)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_context(&self, f: &mut Formatter<'_>, error_offset: usize) -> core::fmt::Result {
+        let size = self.context.len();
+        let first_offset = self.end_offset.saturating_sub(size);
+        if size == 0 || error_offset < first_offset || error_offset > self.end_offset {
+            return Ok(());
+        }
+
+        // an error at the start of a line is usually about the preceding newline
+        let error_offset = if error_offset > first_offset && self.byte_at(error_offset - 1) == b'\n' {
+            error_offset - 1
+        }
+        else {
+            error_offset
+        };
+
+        let mut line_start = error_offset;
+        while line_start > first_offset && self.byte_at(line_start - 1) != b'\n' {
+            line_start -= 1;
+        }
+        let mut line_end = error_offset;
+        while line_end < self.end_offset && self.byte_at(line_end) != b'\n' {
+            line_end += 1;
+        }
+        if line_end > line_start && self.byte_at(line_end - 1) == b'\r' {
+            line_end -= 1;
+        }
+        let is_cut_off = line_start == first_offset && first_offset > 0;
+
+        f.write_char('\n')?;
+        if is_cut_off {
+            f.write_str("...")?;
+        }
+        // the line may wrap around the end of the ring buffer
+        let wrap_offset = (line_start / size + 1) * size;
+        if line_end > wrap_offset {
+            self.write_text(f, line_start, wrap_offset)?;
+            self.write_text(f, wrap_offset, line_end)?;
+        }
+        else {
+            self.write_text(f, line_start, line_end)?;
+        }
+
+        f.write_char('\n')?;
+        if is_cut_off {
+            f.write_str("   ")?;
+        }
+        for offset in line_start..error_offset {
+            match self.byte_at(offset) {
+                b'\t' => f.write_char('\t')?,
+                0x80..=0xbf => {} // UTF-8 continuation byte
+                _ => f.write_char(' ')?,
+            }
+        }
+        f.write_char('^')
+    }
+}
+impl <E: Error> Display for JsonErrorReport<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.error)?;
+        let error_offset = match self.error {
+            JsonParseError::Io(_) => return Ok(()),
+            JsonParseError::Utf8(_) => self.end_offset,
+            JsonParseError::Parse(_, location, path) => {
+                if path.is_none() {
+                    if let Some(path) = &self.path {
+                        write!(f, " (path {})", path)?;
+                    }
+                }
+                location.offset
+            }
+            JsonParseError::BufferOverflow(location) |
+            JsonParseError::MaxNestingDepthExceeded(location) => {
+                if let Some(path) = &self.path {
+                    write!(f, " (path {})", path)?;
+                }
+                location.offset
+            }
+        };
+        self.write_context(f, error_offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case::empty(0, vec!["abc"], b"")]
+    #[case::partial(5, vec!["abc"], b"abc\0\0")]
+    #[case::exact(3, vec!["abc"], b"abc")]
+    #[case::wrap(4, vec!["abc", "de"], b"ebcd")]
+    #[case::single_bytes(3, vec!["a", "b", "c", "d"], b"dbc")]
+    #[case::longer_than_buffer(4, vec!["ab", "cdefghi"], b"ifgh")]
+    fn test_record_context(#[case] size: usize, #[case] chunks: Vec<&str>, #[case] expected: &[u8]) {
+        let mut ring = vec![0u8; size];
+        let mut offset = 0;
+        for chunk in chunks {
+            record_context(&mut ring, offset, chunk.as_bytes());
+            offset += chunk.len();
+        }
+        assert_eq!(ring, expected);
+    }
+}
@@ -0,0 +1,68 @@
+/// The escape sequences for control characters, which must not occur in JSON strings unescaped
+static CONTROL_CHARACTER_ESCAPES: [&[u8]; 32] = [
+    b"\\u0000", b"\\u0001", b"\\u0002", b"\\u0003", b"\\u0004", b"\\u0005", b"\\u0006", b"\\u0007",
+    b"\\b",     b"\\t",     b"\\n",     b"\\u000b", b"\\f",     b"\\r",     b"\\u000e", b"\\u000f",
+    b"\\u0010", b"\\u0011", b"\\u0012", b"\\u0013", b"\\u0014", b"\\u0015", b"\\u0016", b"\\u0017",
+    b"\\u0018", b"\\u0019", b"\\u001a", b"\\u001b", b"\\u001c", b"\\u001d", b"\\u001e", b"\\u001f",
+];
+
+/// Returns the escape sequence for a byte in a JSON string, or `None` if it is written as it is.
+fn escape_sequence(b: u8) -> Option<&'static [u8]> {
+    match b {
+        b'"' => Some(b"\\\""),
+        b'\\' => Some(b"\\\\"),
+        b if b < 0x20 => Some(CONTROL_CHARACTER_ESCAPES[b as usize]),
+        _ => None,
+    }
+}
+
+/// Splits a string's representation as an escaped JSON string (without the enclosing quotes) into
+///  chunks: runs of bytes that are written unchanged, and escape sequences. This is the escaping
+///  logic shared by the blocking and non-blocking writers and the serializers.
+pub(crate) struct JsonEscapedChunks<'s> {
+    remaining: &'s [u8],
+}
+
+impl<'s> Iterator for JsonEscapedChunks<'s> {
+    type Item = &'s [u8];
+
+    fn next(&mut self) -> Option<&'s [u8]> {
+        let first = *self.remaining.first()?;
+        if let Some(escaped) = escape_sequence(first) {
+            self.remaining = &self.remaining[1..];
+            return Some(escaped);
+        }
+        let len = self.remaining.iter()
+            .position(|&b| escape_sequence(b).is_some())
+            .unwrap_or(self.remaining.len());
+        let (chunk, remaining) = self.remaining.split_at(len);
+        self.remaining = remaining;
+        Some(chunk)
+    }
+}
+
+/// Returns the chunks of a string's escaped JSON representation, see [JsonEscapedChunks]
+pub(crate) fn json_escaped_chunks(s: &str) -> JsonEscapedChunks<'_> {
+    JsonEscapedChunks {
+        remaining: s.as_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case::empty("", vec![])]
+    #[case::unescaped("abc", vec!["abc"])]
+    #[case::escaped_only("\"\n", vec!["\\\"", "\\n"])]
+    #[case::mixed("a\\b\u{1}c", vec!["a", "\\\\", "b", "\\u0001", "c"])]
+    #[case::non_ascii("äö\tü", vec!["äö", "\\t", "ü"])]
+    fn test_json_escaped_chunks(#[case] s: &str, #[case] expected: Vec<&str>) {
+        let chunks = json_escaped_chunks(s)
+            .map(|chunk| core::str::from_utf8(chunk).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, expected);
+    }
+}
@@ -34,6 +34,11 @@ impl PrettyFormatter {
         }
     }
 }
+impl Default for PrettyFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl JsonFormatter for PrettyFormatter {
     fn after_key(&self) -> &str {
         " "
@@ -59,7 +64,7 @@ impl JsonFormatter for PrettyFormatter {
     }
 
     fn indent(&self) -> &str {
-        static INDENT: &'static str = "\n                                                                                                                                                                                                                                                 ";
+        static INDENT: &str = "\n                                                                                                                                                                                                                                                 ";
         &INDENT[..2*self.indent_level + 1]
     }
 }
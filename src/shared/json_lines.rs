@@ -0,0 +1,54 @@
+use crate::shared::*;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// A [JsonLinesError] is a [JsonParseError] in a [JSON Lines](https://jsonlines.org) stream,
+///  together with the number of the record in which it occurred.
+#[derive(Debug)]
+pub struct JsonLinesError<E: Error> {
+    /// the 0-based number of the record, not counting blank lines
+    pub record: usize,
+    /// the offset of the error in the stream, or of the record's start if the error has no
+    ///  location
+    pub offset: usize,
+    pub error: JsonParseError<E>,
+}
+impl <E: Error> Display for JsonLinesError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "record {} (offset {}): {}", self.record, self.offset, self.error)
+    }
+}
+impl <E: Error> Error for JsonLinesError<E> {
+}
+
+/// Adjusts a location for input that was read starting at `base` rather than at the start of
+///  the stream, e.g. a chunk of a larger stream: The offset and line number are shifted, and the
+///  column is kept. `base` must be at the start of a line.
+pub(crate) fn shift_location(location: Location, base: Location) -> Location {
+    Location {
+        offset: base.offset + location.offset,
+        line: base.line + location.line - 1,
+        column: location.column,
+    }
+}
+
+/// Same as [shift_location] for an error's location
+pub(crate) fn shift_error_location<E: Error>(error: JsonParseError<E>, base: Location) -> JsonParseError<E> {
+    let shift = |location| shift_location(location, base);
+    match error {
+        JsonParseError::Parse(kind, location, path) => JsonParseError::Parse(kind, shift(location), path),
+        JsonParseError::BufferOverflow(location) => JsonParseError::BufferOverflow(shift(location)),
+        JsonParseError::MaxNestingDepthExceeded(location) => JsonParseError::MaxNestingDepthExceeded(shift(location)),
+        error @ (JsonParseError::Io(_) | JsonParseError::Utf8(_)) => error,
+    }
+}
+
+/// Returns the location of an error, if it has one
+pub(crate) fn error_location<E: Error>(error: &JsonParseError<E>) -> Option<Location> {
+    match error {
+        JsonParseError::Parse(_, location, _) |
+        JsonParseError::BufferOverflow(location) |
+        JsonParseError::MaxNestingDepthExceeded(location) => Some(*location),
+        JsonParseError::Io(_) | JsonParseError::Utf8(_) => None,
+    }
+}
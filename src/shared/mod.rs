@@ -1,7 +1,31 @@
+#[cfg(feature = "std")]
+mod array_index;
+mod checkpoint;
+#[cfg(feature = "serde")]
+mod deserialize;
+mod error_report;
+mod escape;
 mod float_format;
 mod json_formatter;
+mod json_lines;
+mod query;
 mod read;
+#[cfg(feature = "serde")]
+mod serialize;
+mod validate;
 
+#[cfg(feature = "std")]
+pub use crate::shared::array_index::*;
+pub use crate::shared::checkpoint::*;
+#[cfg(feature = "serde")]
+pub use crate::shared::deserialize::*;
+pub use crate::shared::error_report::*;
+pub(crate) use crate::shared::escape::*;
 pub use crate::shared::float_format::*;
 pub use crate::shared::json_formatter::*;
-pub use crate::shared::read::*;
\ No newline at end of file
+pub use crate::shared::json_lines::*;
+pub use crate::shared::query::*;
+pub use crate::shared::read::*;
+#[cfg(feature = "serde")]
+pub use crate::shared::serialize::*;
+pub use crate::shared::validate::*;
\ No newline at end of file
@@ -0,0 +1,433 @@
+use crate::shared::DEFAULT_MAX_NESTING_DEPTH;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// The maximum number of segments in a [JsonQuery]
+pub const MAX_QUERY_SEGMENTS: usize = 16;
+
+/// The default number of nested objects and arrays that a query matcher can descend into. It is
+///  the reader's default nesting depth, so a query matcher can descend into every document that
+///  the reader accepts by default.
+pub const DEFAULT_MAX_QUERY_DEPTH: usize = DEFAULT_MAX_NESTING_DEPTH;
+
+/// The type of the buffer that a query matcher uses for tracking the objects and arrays it
+///  descended into, by default with room for [DEFAULT_MAX_QUERY_DEPTH] levels.
+pub type DefaultQueryLevels = [JsonQueryLevel; DEFAULT_MAX_QUERY_DEPTH];
+
+/// A [JsonQueryError] is a syntax error in a JSONPath expression, with the offset of the
+///  offending character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonQueryError {
+    pub msg: &'static str,
+    pub offset: usize,
+}
+impl Display for JsonQueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid JSONPath query: {} @ {}", self.msg, self.offset)
+    }
+}
+impl Error for JsonQueryError {
+}
+
+/// A [JsonPathSelector] selects some of an object's members or an array's elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonPathSelector<'q> {
+    /// `.name`, `['name']` or `["name"]`
+    Name(&'q str),
+    /// `.*` or `[*]`
+    Wildcard,
+    /// `[3]`
+    Index(usize),
+    /// `[start:end:step]`, with `start` defaulting to 0, `end` to the end of the array and `step`
+    ///  to 1
+    Slice { start: usize, end: Option<usize>, step: usize },
+}
+impl JsonPathSelector<'_> {
+    fn matches_key(&self, key: &str) -> bool {
+        match self {
+            JsonPathSelector::Name(name) => *name == key,
+            JsonPathSelector::Wildcard => true,
+            _ => false,
+        }
+    }
+
+    fn matches_index(&self, index: usize) -> bool {
+        match *self {
+            JsonPathSelector::Index(i) => i == index,
+            JsonPathSelector::Wildcard => true,
+            JsonPathSelector::Slice { start, end, step } => {
+                index >= start && end.is_none_or(|end| index < end) && step != 0 && (index - start) % step == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A [JsonPathSegment] is a single step in a [JsonQuery]: a selector applied either to the
+///  current values' children or (for `..`) to all their descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonPathSegment<'q> {
+    pub selector: JsonPathSelector<'q>,
+    pub is_descendant: bool,
+}
+
+/// A [JsonQuery] is a compiled JSONPath expression (RFC 9535) for matching values while reading
+///  a JSON document in a single pass. It supports the subset of JSONPath that can be evaluated
+///  without looking ahead or keeping values in memory:
+///
+/// * child segments: `$.data`, `$['data']`
+/// * wildcards: `$.data.*`, `$.data[*]`
+/// * array indexes: `$.data[3]`
+/// * array slices: `$.data[1:10:2]`, `$.data[5:]`
+/// * recursive descent: `$..id`, `$..[0]`
+///
+/// Negative indexes, filter expressions, functions and unions are not supported, and names in
+///  brackets must not contain escape sequences. A query consists of up to [MAX_QUERY_SEGMENTS]
+///  segments.
+///
+/// Compiling a query does not allocate memory; its names borrow from the query string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonQuery<'q> {
+    segments: [JsonPathSegment<'q>; MAX_QUERY_SEGMENTS],
+    num_segments: usize,
+}
+
+impl<'q> JsonQuery<'q> {
+    pub fn parse(query: &'q str) -> Result<JsonQuery<'q>, JsonQueryError> {
+        QueryParser { query, offset: 0 }.parse()
+    }
+
+    pub fn segments(&self) -> &[JsonPathSegment<'q>] {
+        &self.segments[..self.num_segments]
+    }
+
+    /// The set of matched segment counts for the document's root: zero segments matched
+    pub(crate) fn initial_states(&self) -> u32 {
+        1
+    }
+
+    /// A value matches the query if all segments are matched
+    pub(crate) fn is_match(&self, states: u32) -> bool {
+        states & (1 << self.num_segments) != 0
+    }
+
+    /// Returns the set of matched segment counts for an object member, given its parent's set
+    pub(crate) fn key_states(&self, parent_states: u32, key: &str) -> u32 {
+        self.child_states(parent_states, |selector| selector.matches_key(key))
+    }
+
+    /// Returns the set of matched segment counts for an array element, given its parent's set
+    pub(crate) fn index_states(&self, parent_states: u32, index: usize) -> u32 {
+        self.child_states(parent_states, |selector| selector.matches_index(index))
+    }
+
+    fn child_states(&self, parent_states: u32, matches: impl Fn(&JsonPathSelector) -> bool) -> u32 {
+        let mut result = 0;
+        for (i, segment) in self.segments().iter().enumerate() {
+            if parent_states & (1 << i) == 0 {
+                continue;
+            }
+            if matches(&segment.selector) {
+                result |= 1 << (i + 1);
+            }
+            if segment.is_descendant {
+                // the segment can match further down the tree
+                result |= 1 << i;
+            }
+        }
+        result
+    }
+}
+
+struct QueryParser<'q> {
+    query: &'q str,
+    offset: usize,
+}
+
+impl<'q> QueryParser<'q> {
+    fn parse(mut self) -> Result<JsonQuery<'q>, JsonQueryError> {
+        let mut result = JsonQuery {
+            segments: [JsonPathSegment { selector: JsonPathSelector::Wildcard, is_descendant: false }; MAX_QUERY_SEGMENTS],
+            num_segments: 0,
+        };
+
+        if self.peek_byte() != Some(b'$') {
+            return self.err("query must start with '$'");
+        }
+        self.offset += 1;
+        while let Some(b) = self.peek_byte() {
+            let segment = match b {
+                b'.' => {
+                    self.offset += 1;
+                    if self.peek_byte() == Some(b'.') {
+                        self.offset += 1;
+                        let selector = if self.peek_byte() == Some(b'[') {
+                            self.parse_bracketed()?
+                        }
+                        else {
+                            self.parse_dotted()?
+                        };
+                        JsonPathSegment { selector, is_descendant: true }
+                    }
+                    else {
+                        JsonPathSegment { selector: self.parse_dotted()?, is_descendant: false }
+                    }
+                }
+                b'[' => JsonPathSegment { selector: self.parse_bracketed()?, is_descendant: false },
+                _ => return self.err("expected '.' or '['"),
+            };
+
+            if result.num_segments == MAX_QUERY_SEGMENTS {
+                return self.err("too many segments");
+            }
+            result.segments[result.num_segments] = segment;
+            result.num_segments += 1;
+        }
+        Ok(result)
+    }
+
+    /// parses the selector after a '.', i.e. a name or a wildcard
+    fn parse_dotted(&mut self) -> Result<JsonPathSelector<'q>, JsonQueryError> {
+        if self.peek_byte() == Some(b'*') {
+            self.offset += 1;
+            return Ok(JsonPathSelector::Wildcard);
+        }
+        let start = self.offset;
+        while let Some(b) = self.peek_byte() {
+            match b {
+                b'.' | b'[' => break,
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' | 0x80.. => self.offset += 1,
+                _ => return self.err("invalid character in name"),
+            }
+        }
+        if self.offset == start {
+            return self.err("expected a name or '*'");
+        }
+        Ok(JsonPathSelector::Name(&self.query[start..self.offset]))
+    }
+
+    /// parses a selector in brackets, starting at the '['
+    fn parse_bracketed(&mut self) -> Result<JsonPathSelector<'q>, JsonQueryError> {
+        self.offset += 1;
+        let selector = match self.peek_byte() {
+            Some(b'*') => {
+                self.offset += 1;
+                JsonPathSelector::Wildcard
+            }
+            Some(quote @ (b'\'' | b'"')) => {
+                self.offset += 1;
+                let start = self.offset;
+                loop {
+                    match self.peek_byte() {
+                        Some(b) if b == quote => break,
+                        Some(b'\\') => return self.err("escape sequences in names are not supported"),
+                        Some(_) => self.offset += 1,
+                        None => return self.err("unterminated name"),
+                    }
+                }
+                self.offset += 1;
+                JsonPathSelector::Name(&self.query[start..self.offset - 1])
+            }
+            _ => {
+                let start = self.parse_opt_number()?;
+                if self.peek_byte() == Some(b':') {
+                    self.offset += 1;
+                    let end = self.parse_opt_number()?;
+                    let step = if self.peek_byte() == Some(b':') {
+                        self.offset += 1;
+                        self.parse_opt_number()?.unwrap_or(1)
+                    }
+                    else {
+                        1
+                    };
+                    if step == 0 {
+                        return self.err("slice step must not be zero");
+                    }
+                    JsonPathSelector::Slice { start: start.unwrap_or(0), end, step }
+                }
+                else {
+                    match start {
+                        Some(index) => JsonPathSelector::Index(index),
+                        None => return self.err("expected a selector"),
+                    }
+                }
+            }
+        };
+        if self.next_byte() != Some(b']') {
+            return self.err("expected ']'");
+        }
+        Ok(selector)
+    }
+
+    fn parse_opt_number(&mut self) -> Result<Option<usize>, JsonQueryError> {
+        if self.peek_byte() == Some(b'-') {
+            return self.err("negative indexes are not supported");
+        }
+        let start = self.offset;
+        while let Some(b'0'..=b'9') = self.peek_byte() {
+            self.offset += 1;
+        }
+        if self.offset == start {
+            return Ok(None);
+        }
+        match self.query[start..self.offset].parse() {
+            Ok(n) => Ok(Some(n)),
+            Err(_) => self.err("index out of range"),
+        }
+    }
+
+    fn peek_byte(&self) -> Option<u8> {
+        self.query.as_bytes().get(self.offset).copied()
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let result = self.peek_byte();
+        if result.is_some() {
+            self.offset += 1;
+        }
+        result
+    }
+
+    fn err<T>(&self, msg: &'static str) -> Result<T, JsonQueryError> {
+        Err(JsonQueryError { msg, offset: self.offset })
+    }
+}
+
+/// A [JsonQueryLevel] is the state of a query matcher for an object or array it descended into.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonQueryLevel {
+    states: u32,
+    next_index: usize,
+    is_array: bool,
+}
+
+/// The part of a query matcher that is independent of the reader: the query and the matching state
+///  for the objects and arrays that were entered.
+pub(crate) struct QueryMatcherInner<'q, L: AsMut<[JsonQueryLevel]>> {
+    pub query: JsonQuery<'q>,
+    levels: L,
+    depth: usize,
+    pub is_started: bool,
+}
+
+/// The next step for a query matcher, based on the current level
+pub(crate) enum QueryStep {
+    /// the next token is a member's key
+    ReadKey(u32),
+    /// the next token is an array element with a given index, or the end of the array
+    ReadElement(u32, usize),
+    /// the root value was processed
+    Done,
+}
+
+impl<'q, L: AsMut<[JsonQueryLevel]>> QueryMatcherInner<'q, L> {
+    pub fn new(query: JsonQuery<'q>, levels: L) -> Self {
+        Self {
+            query,
+            levels,
+            depth: 0,
+            is_started: false,
+        }
+    }
+
+    pub fn next_step(&mut self) -> QueryStep {
+        if self.depth == 0 {
+            return QueryStep::Done;
+        }
+        let level = &mut self.levels.as_mut()[self.depth - 1];
+        if level.is_array {
+            QueryStep::ReadElement(level.states, level.next_index)
+        }
+        else {
+            QueryStep::ReadKey(level.states)
+        }
+    }
+
+    /// Called when an array element was found, before it is read
+    pub fn on_element(&mut self) {
+        self.levels.as_mut()[self.depth - 1].next_index += 1;
+    }
+
+    /// Enter an object or array, returning `false` if the levels buffer is full
+    pub fn push(&mut self, states: u32, is_array: bool) -> bool {
+        let levels = self.levels.as_mut();
+        if self.depth == levels.len() {
+            return false;
+        }
+        levels[self.depth] = JsonQueryLevel { states, next_index: 0, is_array };
+        self.depth += 1;
+        true
+    }
+
+    pub fn pop(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn segment(selector: JsonPathSelector, is_descendant: bool) -> JsonPathSegment {
+        JsonPathSegment { selector, is_descendant }
+    }
+
+    #[rstest]
+    #[case::root("$", vec![])]
+    #[case::child("$.data", vec![segment(JsonPathSelector::Name("data"), false)])]
+    #[case::child_bracket_single("$['a b']", vec![segment(JsonPathSelector::Name("a b"), false)])]
+    #[case::child_bracket_double(r#"$["a.b"]"#, vec![segment(JsonPathSelector::Name("a.b"), false)])]
+    #[case::child_nested("$.a.b_c", vec![segment(JsonPathSelector::Name("a"), false), segment(JsonPathSelector::Name("b_c"), false)])]
+    #[case::child_unicode("$.äö", vec![segment(JsonPathSelector::Name("äö"), false)])]
+    #[case::wildcard_dotted("$.*", vec![segment(JsonPathSelector::Wildcard, false)])]
+    #[case::wildcard_bracket("$[*]", vec![segment(JsonPathSelector::Wildcard, false)])]
+    #[case::index("$[12]", vec![segment(JsonPathSelector::Index(12), false)])]
+    #[case::slice_full("$[1:5:2]", vec![segment(JsonPathSelector::Slice { start: 1, end: Some(5), step: 2 }, false)])]
+    #[case::slice_start("$[3:]", vec![segment(JsonPathSelector::Slice { start: 3, end: None, step: 1 }, false)])]
+    #[case::slice_end("$[:3]", vec![segment(JsonPathSelector::Slice { start: 0, end: Some(3), step: 1 }, false)])]
+    #[case::slice_step("$[::2]", vec![segment(JsonPathSelector::Slice { start: 0, end: None, step: 2 }, false)])]
+    #[case::slice_empty_step("$[1::]", vec![segment(JsonPathSelector::Slice { start: 1, end: None, step: 1 }, false)])]
+    #[case::descendant("$..id", vec![segment(JsonPathSelector::Name("id"), true)])]
+    #[case::descendant_wildcard("$..*", vec![segment(JsonPathSelector::Wildcard, true)])]
+    #[case::descendant_bracket("$..[0]", vec![segment(JsonPathSelector::Index(0), true)])]
+    #[case::combined("$.data[*]..id", vec![segment(JsonPathSelector::Name("data"), false), segment(JsonPathSelector::Wildcard, false), segment(JsonPathSelector::Name("id"), true)])]
+    fn test_parse(#[case] query: &str, #[case] expected: Vec<JsonPathSegment>) {
+        let parsed = JsonQuery::parse(query).unwrap();
+        assert_eq!(parsed.segments(), expected.as_slice());
+    }
+
+    #[rstest]
+    #[case::empty("", 0)]
+    #[case::no_root("data", 0)]
+    #[case::missing_dot("$data", 1)]
+    #[case::empty_name("$.", 2)]
+    #[case::empty_name_before_bracket("$.[0]", 2)]
+    #[case::invalid_name("$.a b", 3)]
+    #[case::descendant_without_selector("$..", 3)]
+    #[case::unterminated_bracket("$[0", 3)]
+    #[case::empty_bracket("$[]", 2)]
+    #[case::negative_index("$[-1]", 2)]
+    #[case::zero_step("$[1:2:0]", 7)]
+    #[case::unterminated_name("$['abc", 6)]
+    #[case::escaped_name(r#"$['a\'b']"#, 4)]
+    #[case::index_overflow("$[99999999999999999999999]", 25)]
+    #[case::too_many_segments("$.a.b.c.d.e.f.g.h.i.j.k.l.m.n.o.p.q", 35)]
+    fn test_parse_invalid(#[case] query: &str, #[case] expected_offset: usize) {
+        let err = JsonQuery::parse(query).unwrap_err();
+        assert_eq!(err.offset, expected_offset);
+    }
+
+    #[rstest]
+    #[case::index(JsonPathSelector::Index(2), vec![2])]
+    #[case::wildcard(JsonPathSelector::Wildcard, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9])]
+    #[case::slice(JsonPathSelector::Slice { start: 1, end: Some(7), step: 3 }, vec![1, 4])]
+    #[case::slice_open(JsonPathSelector::Slice { start: 6, end: None, step: 1 }, vec![6, 7, 8, 9])]
+    #[case::name(JsonPathSelector::Name("1"), vec![])]
+    fn test_matches_index(#[case] selector: JsonPathSelector, #[case] expected: Vec<usize>) {
+        let actual = (0..10)
+            .filter(|&i| selector.matches_index(i))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+}
@@ -2,6 +2,8 @@ use core::error::Error;
 use core::fmt::{Display, Formatter};
 use core::str::{FromStr, Utf8Error};
 use core::marker::PhantomData;
+use crate::shared::error_report::record_context;
+use crate::shared::JsonErrorReport;
 
 /// [JsonReadToken] represents a single token read from a `JsonReader`. It does not own string 
 ///  data, but references the reader's internal buffer.
@@ -18,29 +20,92 @@ pub enum JsonReadToken<'a> {
     BooleanLiteral(bool),
     NullLiteral,
 
+    /// A comment including its delimiters, e.g. `// abc` or `/* abc */` - this is returned only
+    ///  if the `JsonReader` is configured to report comments
+    Comment(&'a str),
+
     EndOfStream,
 }
 impl <'a> JsonReadToken<'a> {
     pub fn kind(&self) -> &'static str {
+        self.token_kind().as_str()
+    }
+
+    /// Returns this token's [JsonReadTokenKind], i.e. the token without its payload
+    pub fn token_kind(&self) -> JsonReadTokenKind {
         match self {
-            JsonReadToken::StartObject => "{",
-            JsonReadToken::EndObject => "}",
-            JsonReadToken::StartArray => "[",
-            JsonReadToken::EndArray => "]",
-            JsonReadToken::Key(_) => "key",
-            JsonReadToken::StringLiteral(_) => "string",
-            JsonReadToken::NumberLiteral(_) => "number",
-            JsonReadToken::BooleanLiteral(_) => "boolean",
-            JsonReadToken::NullLiteral => "null",
-            JsonReadToken::EndOfStream => "<EOF>",
+            JsonReadToken::StartObject => JsonReadTokenKind::StartObject,
+            JsonReadToken::EndObject => JsonReadTokenKind::EndObject,
+            JsonReadToken::StartArray => JsonReadTokenKind::StartArray,
+            JsonReadToken::EndArray => JsonReadTokenKind::EndArray,
+            JsonReadToken::Key(_) => JsonReadTokenKind::Key,
+            JsonReadToken::StringLiteral(_) => JsonReadTokenKind::StringLiteral,
+            JsonReadToken::NumberLiteral(_) => JsonReadTokenKind::NumberLiteral,
+            JsonReadToken::BooleanLiteral(_) => JsonReadTokenKind::BooleanLiteral,
+            JsonReadToken::NullLiteral => JsonReadTokenKind::NullLiteral,
+            JsonReadToken::Comment(_) => JsonReadTokenKind::Comment,
+            JsonReadToken::EndOfStream => JsonReadTokenKind::EndOfStream,
         }
     }
 }
 
+/// [JsonReadTokenKind] is the kind of a [JsonReadToken] without its payload, e.g. for looking ahead
+///  at the next token with `JsonReader::peek_kind()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonReadTokenKind {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+
+    Key,
+    StringLiteral,
+    NumberLiteral,
+    BooleanLiteral,
+    NullLiteral,
+
+    Comment,
+
+    EndOfStream,
+}
+/// the kinds of tokens that can start a value
+pub(crate) const VALUE_START_KINDS: &[JsonReadTokenKind] = &[
+    JsonReadTokenKind::StartObject,
+    JsonReadTokenKind::StartArray,
+    JsonReadTokenKind::StringLiteral,
+    JsonReadTokenKind::NumberLiteral,
+    JsonReadTokenKind::BooleanLiteral,
+    JsonReadTokenKind::NullLiteral,
+];
+
+impl JsonReadTokenKind {
+    /// The same short description that [JsonReadToken::kind] returns
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JsonReadTokenKind::StartObject => "{",
+            JsonReadTokenKind::EndObject => "}",
+            JsonReadTokenKind::StartArray => "[",
+            JsonReadTokenKind::EndArray => "]",
+            JsonReadTokenKind::Key => "key",
+            JsonReadTokenKind::StringLiteral => "string",
+            JsonReadTokenKind::NumberLiteral => "number",
+            JsonReadTokenKind::BooleanLiteral => "boolean",
+            JsonReadTokenKind::NullLiteral => "null",
+            JsonReadTokenKind::Comment => "comment",
+            JsonReadTokenKind::EndOfStream => "<EOF>",
+        }
+    }
+}
+impl Display for JsonReadTokenKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 
 /// A [JsonNumber] is the raw representation of a number. It is a parsed representation in the
-///  sense that a `JsonReader` (more or less) verified that it is a valid JSON number, but it has 
-///  not been parsed into an actual Rust numeric type yet.
+///  sense that a `JsonReader` verified that it is a valid JSON number as specified in RFC 8259,
+///  but it has not been parsed into an actual Rust numeric type yet.
 ///
 /// Client code can either access the string representation, or call the [JsonNumber::parse]
 ///  function to parse it into a numeric Rust type.
@@ -54,6 +119,82 @@ impl <'a> JsonNumber<'a> {
     pub fn parse<F: FromStr>(&self) -> Result<F, F::Err> {
         self.0.parse()
     }
+
+    /// Returns `true` if the number is an integer literal, i.e. it has neither a fraction nor an
+    ///  exponent. Note that this is a purely syntactic check: `1.0` and `1e3` are not considered
+    ///  integers even though their values are integral.
+    pub fn is_integer(&self) -> bool {
+        self.0.bytes().all(|b| b.is_ascii_digit() || b == b'-')
+    }
+}
+
+/// State machine for validating number literals against the grammar in RFC 8259:
+///
+/// ```text
+/// number = [ minus ] int [ frac ] [ exp ]
+/// int    = zero / ( digit1-9 *DIGIT )
+/// frac   = decimal-point 1*DIGIT
+/// exp    = e [ minus / plus ] 1*DIGIT
+/// ```
+///
+/// The reader feeds all bytes that can be part of a number literal into the state machine, and
+///  it fails on the first byte that is not valid at its position.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum NumberState {
+    /// after a leading `-`
+    Minus,
+    /// after a leading `0` which must not be followed by other digits
+    Zero,
+    /// in the integer part, after a non-zero first digit
+    Int,
+    /// after the `.`, at least one digit is required
+    FractionStart,
+    /// in the digits of the fraction
+    Fraction,
+    /// after the `e` or `E`
+    ExponentStart,
+    /// after the exponent's sign, at least one digit is required
+    ExponentSign,
+    /// in the digits of the exponent
+    Exponent,
+}
+impl NumberState {
+    /// The state after a number's first byte, or `None` if the byte can not start a number literal
+    pub fn start(b: u8) -> Option<NumberState> {
+        match b {
+            b'-' => Some(NumberState::Minus),
+            b'0' => Some(NumberState::Zero),
+            b'1'..=b'9' => Some(NumberState::Int),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for all bytes that can be part of a number literal's continuation, even
+    ///  if they are not valid in every position. The first byte that is not a number byte
+    ///  ends the literal.
+    pub fn is_number_byte(b: u8) -> bool {
+        matches!(b, b'0'..=b'9' | b'+' | b'-' | b'e' | b'E' | b'.')
+    }
+
+    /// The state after a given byte, or `None` if the byte is not valid in the current state
+    pub fn next(self, b: u8) -> Option<NumberState> {
+        match (self, b) {
+            (NumberState::Minus, b'0') => Some(NumberState::Zero),
+            (NumberState::Minus, b'1'..=b'9') => Some(NumberState::Int),
+            (NumberState::Int, b'0'..=b'9') => Some(NumberState::Int),
+            (NumberState::Zero | NumberState::Int, b'.') => Some(NumberState::FractionStart),
+            (NumberState::FractionStart | NumberState::Fraction, b'0'..=b'9') => Some(NumberState::Fraction),
+            (NumberState::Zero | NumberState::Int | NumberState::Fraction, b'e' | b'E') => Some(NumberState::ExponentStart),
+            (NumberState::ExponentStart, b'+' | b'-') => Some(NumberState::ExponentSign),
+            (NumberState::ExponentStart | NumberState::ExponentSign | NumberState::Exponent, b'0'..=b'9') => Some(NumberState::Exponent),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if a number literal can end in this state
+    pub fn is_complete(self) -> bool {
+        matches!(self, NumberState::Zero | NumberState::Int | NumberState::Fraction | NumberState::Exponent)
+    }
 }
 
 /// Represents a location in a parsed stream: offset as well as line and column. This location
@@ -90,29 +231,238 @@ impl Location {
             self.column += 1;
         }
     }
+
+    pub fn after_bytes(&mut self, bytes: &[u8]) {
+        self.offset += bytes.len();
+        match bytes.iter().rposition(|&b| b == b'\n') {
+            Some(ind_last_newline) => {
+                self.line += bytes.iter().filter(|&&b| b == b'\n').count();
+                self.column = bytes.len() - ind_last_newline;
+            }
+            None => {
+                self.column += bytes.len();
+            }
+        }
+    }
+}
+
+/// [JsonPath] is the position of the most recent token in a JSON document as a JSON Pointer
+///  (RFC 6901), e.g. `/orders/17/items/3/price`, with object keys and (zero-based) array indices
+///  as segments. It is maintained by a `JsonReader` if path tracking is enabled.
+///
+/// The path is stored in a fixed-size buffer, and segments that do not fit into the buffer are
+///  omitted. [JsonPath::is_truncated] indicates this, and [JsonPath]'s [Display] implementation
+///  renders it as a trailing `/...`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonPath<'a> {
+    pointer: &'a str,
+    is_truncated: bool,
+}
+impl <'a> JsonPath<'a> {
+    pub fn new(pointer: &'a str, is_truncated: bool) -> JsonPath<'a> {
+        JsonPath {
+            pointer,
+            is_truncated,
+        }
+    }
+
+    /// The JSON Pointer, or its leading part if the path is truncated. The document's root is
+    ///  represented by an empty string.
+    pub fn pointer(&self) -> &'a str {
+        self.pointer
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+}
+impl Display for JsonPath<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.pointer)?;
+        if self.is_truncated {
+            write!(f, "/...")?;
+        }
+        Ok(())
+    }
+}
+
+/// An owned copy of a [JsonPath] for inclusion in a [JsonParseError]. Copying requires memory
+///  allocation, so parse errors contain a path only if the `std` feature is active.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedJsonPath {
+    #[cfg(feature = "std")]
+    path: std::string::String,
+}
+impl OwnedJsonPath {
+    #[cfg(feature = "std")]
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+}
+#[cfg(feature = "std")]
+impl From<JsonPath<'_>> for OwnedJsonPath {
+    fn from(value: JsonPath<'_>) -> Self {
+        OwnedJsonPath {
+            path: std::format!("{}", value),
+        }
+    }
+}
+impl Display for OwnedJsonPath {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.path)
+    }
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _f: &mut Formatter<'_>) -> core::fmt::Result {
+        Ok(())
+    }
 }
 
+/// [ParseErrorKind] is the reason for a [JsonParseError::Parse], i.e. a violation of JSON
+///  tokenization or grammar, or a token that does not match an `expect_*` function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// the stream ended where a token was expected, e.g. inside an object or array
+    UnexpectedEndOfInput,
+    /// an `expect_*` or `skip_*` function found a token that it does not accept
+    UnexpectedToken { expected: &'static [JsonReadTokenKind], found: JsonReadTokenKind },
+    /// a token that starts with a character that can not start a JSON token
+    InvalidLiteral,
+    /// a `null`, `true` or `false` literal that is cut short
+    IncompleteLiteral(&'static str),
+    InvalidNumberLiteral,
+    IncompleteNumberLiteral,
+    /// a number literal that can not be parsed to the type requested by `expect_number()`
+    InvalidNumber,
+    UnterminatedString,
+    InvalidEscape,
+    /// a `\u` escape that is not followed by four hex digits
+    InvalidUnicodeEscape,
+    /// a JSON5 `\x` escape that is not followed by two hex digits
+    InvalidHexEscape,
+    IncompleteUtf8Sequence,
+    UnpairedSurrogate,
+    MissingComma,
+    UnexpectedComma,
+    TrailingComma,
+    KeyWithoutValue,
+    TwoKeysWithoutValue,
+    MemberWithoutKey,
+    KeyOutsideOfObject,
+    /// a `}` that does not close an object
+    MismatchedEndObject,
+    /// a `]` that does not close an array
+    MismatchedEndArray,
+    /// a JSON5 unquoted key that is not followed by `:`
+    MissingColon,
+    UnterminatedComment,
+    /// a `/` that does not start a comment
+    InvalidComment,
+    DuplicateKey,
+    /// a control character (U+0000 to U+001F) in a string literal that is not escaped, see
+    ///  `JsonReader::with_control_character_check()`
+    ControlCharacterInString,
+    /// a value after the (single) top-level value, or a second value in a line of JSON Lines
+    MultipleTopLevelValues,
+    /// a JSON Lines record that continues in the next line
+    MultiLineRecord,
+    /// a JSON text sequence record that is cut short by the next record separator, or a top-level
+    ///  number in a JSON text sequence that is not followed by whitespace and may be cut short
+    TruncatedRecord,
+    /// a value at the start of a JSON text sequence that is not preceded by a record separator
+    MissingRecordSeparator,
+    /// an application specific error, see `JsonReader::parse_err()`
+    Custom(&'static str),
+}
+impl ParseErrorKind {
+    /// Creates an error for an unexpected token, or for an unexpected end of the stream.
+    pub fn unexpected_token(expected: &'static [JsonReadTokenKind], found: JsonReadTokenKind) -> ParseErrorKind {
+        if found == JsonReadTokenKind::EndOfStream {
+            ParseErrorKind::UnexpectedEndOfInput
+        }
+        else {
+            ParseErrorKind::UnexpectedToken { expected, found }
+        }
+    }
+}
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            ParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected ")?;
+                for (i, kind) in expected.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{}", if i + 1 == expected.len() { " or " } else { ", " })?;
+                    }
+                    write!(f, "{}", kind.as_str())?;
+                }
+                return write!(f, ", found {}", found.as_str());
+            }
+            ParseErrorKind::IncompleteLiteral(literal) => return write!(f, "incomplete {} literal", literal),
+            ParseErrorKind::Custom(msg) => msg,
+            ParseErrorKind::UnexpectedEndOfInput => "unexpected end of input",
+            ParseErrorKind::InvalidLiteral => "invalid JSON literal",
+            ParseErrorKind::InvalidNumberLiteral => "invalid number literal",
+            ParseErrorKind::IncompleteNumberLiteral => "incomplete number literal",
+            ParseErrorKind::InvalidNumber => "invalid number",
+            ParseErrorKind::UnterminatedString => "unterminated string literal",
+            ParseErrorKind::InvalidEscape => "invalid escape in string literal",
+            ParseErrorKind::InvalidUnicodeEscape => "not a four-digit hex number after \\u",
+            ParseErrorKind::InvalidHexEscape => "not a two-digit hex number after \\x",
+            ParseErrorKind::IncompleteUtf8Sequence => "incomplete UTF codepoint in string literal",
+            ParseErrorKind::UnpairedSurrogate => "unpaired UTF-16 surrogate in string literal",
+            ParseErrorKind::MissingComma => "missing comma",
+            ParseErrorKind::UnexpectedComma => "unexpected comma",
+            ParseErrorKind::TrailingComma => "trailing comma",
+            ParseErrorKind::KeyWithoutValue => "key without a value",
+            ParseErrorKind::TwoKeysWithoutValue => "two keys without value",
+            ParseErrorKind::MemberWithoutKey => "object member without a key",
+            ParseErrorKind::KeyOutsideOfObject => "key outside of an object",
+            ParseErrorKind::MismatchedEndObject => "'}' does not close an object",
+            ParseErrorKind::MismatchedEndArray => "']' does not close an array",
+            ParseErrorKind::MissingColon => "expected ':' after key",
+            ParseErrorKind::UnterminatedComment => "unterminated block comment",
+            ParseErrorKind::InvalidComment => "invalid comment",
+            ParseErrorKind::DuplicateKey => "duplicate key",
+            ParseErrorKind::ControlCharacterInString => "unescaped control character in string literal",
+            ParseErrorKind::MultipleTopLevelValues => "more than one top-level value",
+            ParseErrorKind::MultiLineRecord => "JSON Lines record spans more than one line",
+            ParseErrorKind::TruncatedRecord => "truncated JSON text sequence record",
+            ParseErrorKind::MissingRecordSeparator => "missing record separator",
+        };
+        write!(f, "{}", msg)
+    }
+}
 
 /// A [JsonParseError] represents the range of things that can go wrong while reading a JSON
 ///  stream: I/O error, byte sequences that are invalid UTF-8, violations of JSON tokenization
-///  or grammar, and tokens that exceed the `JsonReader`'s configured token buffer size.
+///  or grammar, tokens that exceed the `JsonReader`'s configured token buffer size, and objects
+///  or arrays that are nested deeper than the `JsonReader`'s maximum nesting depth.
 ///
 /// Note that the representation of I/O errors depends on the reader implementation and is therefore
 ///  a generic parameter of [JsonParseError]. 
+///
+/// If the `JsonReader` tracks the JSON path, [JsonParseError::Parse] contains the path of the
+///  token at which the error occurred (requires the `std` feature).
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum JsonParseError<E: Error> {
     Io(E),
     Utf8(Utf8Error),
-    Parse(&'static str, Location),
+    Parse(ParseErrorKind, Location, Option<OwnedJsonPath>),
     BufferOverflow(Location),
+    MaxNestingDepthExceeded(Location),
 }
 impl <E: Error> Display for JsonParseError<E> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             JsonParseError::Io(err) => write!(f, "I/O error: {}", err),
             JsonParseError::Utf8(err) => write!(f, "Invalid UTF8: {}", err),
-            JsonParseError::Parse(msg, location) => write!(f, "parse error: {} @ {}", msg, location),
+            JsonParseError::Parse(kind, location, None) => write!(f, "parse error: {} @ {}", kind, location),
+            JsonParseError::Parse(kind, location, Some(path)) => write!(f, "parse error: {} @ {} (path {})", kind, location, path),
             JsonParseError::BufferOverflow(location) => write!(f, "buffer overflow @ {}", location),
+            JsonParseError::MaxNestingDepthExceeded(location) => write!(f, "maximum nesting depth exceeded @ {}", location),
         }
     }
 }
@@ -130,8 +480,36 @@ impl <E: Error> From<E> for JsonParseError<E> {
 pub type JsonParseResult<T, E> = Result<T, JsonParseError<E>>;
 
 
-/// Simple state tracking to handle those parts of the grammar that require only local context. That
-///  is essentially everything except the distinction between objects and arrays.
+/// The kind of container (object or array) that a `JsonReader` is currently inside of.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ContainerKind {
+    Object,
+    Array,
+}
+
+/// The default maximum nesting depth of objects and arrays for a `JsonReader` that does not
+///  have an explicitly provided nesting buffer. It is chosen generously so that documents which
+///  were readable before nesting was tracked continue to be readable - applications that want a
+///  tighter limit can use `JsonReader::with_max_nesting_depth()`.
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 1024;
+
+/// The size of the read-ahead buffer that a `JsonReader` allocates in its `std` constructors, for
+///  readers without slice-oriented access of their own
+pub(crate) const DEFAULT_READ_AHEAD_SIZE: usize = 8192;
+
+/// The nesting buffer used by a `JsonReader` unless the application provides one explicitly. It
+///  stores one bit per nesting level and has room for [DEFAULT_MAX_NESTING_DEPTH] levels.
+pub type DefaultNestingBuffer = [u8; DEFAULT_MAX_NESTING_DEPTH / 8];
+
+/// Returns an empty [DefaultNestingBuffer]
+pub(crate) const fn default_nesting_buffer() -> DefaultNestingBuffer {
+    [0; DEFAULT_MAX_NESTING_DEPTH / 8]
+}
+
+
+/// Simple state tracking to handle those parts of the grammar that require only local context. The
+///  distinction between objects and arrays is tracked separately by `ReaderInner`'s container
+///  stack.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum ReaderState {
     /// Immediately after a nested object or array starts. This needs separate handling from
@@ -146,24 +524,236 @@ pub(crate) enum ReaderState {
     AfterValue,
 }
 
-pub(crate) struct ReaderInner<B: AsMut<[u8]>, E: Error> {
+/// Returns `true` for bytes that can start an unquoted JSON5 key. Non-ASCII characters are
+///  accepted without checking that they are Unicode letters.
+pub(crate) fn is_json5_identifier_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+/// Returns `true` for bytes that can continue an unquoted JSON5 key
+pub(crate) fn is_json5_identifier_part(b: u8) -> bool {
+    is_json5_identifier_start(b) || b.is_ascii_digit()
+}
+
+/// Returns `true` for bytes that can be part of a JSON5 number literal, even if they are not
+///  valid in every position: this includes hexadecimal digits and `Infinity` / `NaN`.
+pub(crate) fn is_json5_number_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.')
+}
+
+/// Writes the decimal representation of a number to the end of a buffer, returning the slice
+///  that contains it
+fn format_decimal(value: u128, digits: &mut [u8; 39]) -> &[u8] {
+    let mut ind_digits = digits.len();
+    let mut remaining = value;
+    loop {
+        ind_digits -= 1;
+        digits[ind_digits] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+    &digits[ind_digits..]
+}
+
+/// Returns the index of the '/' that starts a JSON Pointer's last segment
+fn last_path_segment_start(path: &[u8]) -> usize {
+    path.iter().rposition(|&b| b == b'/').unwrap_or(0)
+}
+
+/// Appends a '/' and a segment to a JSON Pointer, escaping '~' as "~0" and '/' as "~1". If it does
+///  not fit, the path remains unchanged, and the function returns `false`.
+fn append_path_segment(path: &mut [u8], path_len: &mut usize, segment: &[u8]) -> bool {
+    let mut len = *path_len;
+    let mut append = |bytes: &[u8]| {
+        if len + bytes.len() > path.len() {
+            return false;
+        }
+        path[len..len + bytes.len()].copy_from_slice(bytes);
+        len += bytes.len();
+        true
+    };
+    let is_complete = append(b"/") && segment.iter().all(|b| match b {
+        b'~' => append(b"~0"),
+        b'/' => append(b"~1"),
+        b => append(core::slice::from_ref(b)),
+    });
+    if is_complete {
+        *path_len = len;
+    }
+    is_complete
+}
+
+/// A 64-bit hash of a key in the object whose slot in the key table is `object_slot`, for
+///  duplicate key detection: FNV-1a starting from a state that depends on the application's
+///  seed, followed by a finalizer that mixes in the object. It is never 0, which marks an empty
+///  slot in the key table's hash set.
+fn key_hash(seed: u64, object_slot: usize, key: &[u8]) -> u64 {
+    let hash = key.iter().fold(0xcbf2_9ce4_8422_2325 ^ seed, |hash, &b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+    // the finalizer of SplitMix64
+    let mut hash = (hash ^ object_slot as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (hash ^ (hash >> 31)).max(1)
+}
+
+// The key table consists of 8-byte slots and has two parts. The first part is used like a stack:
+//  For each open object, there is a slot with the index of the enclosing object's slot, followed
+//  by the hashes of its keys. The second part is an open-addressing hash set with linear probing
+//  and twice as many slots, which contains the hashes of all keys on the stack for finding
+//  duplicates in constant time. Keys are removed from the set in the reverse order in which
+//  they were added, which restores its previous state exactly, so there is no need for
+//  tombstones.
+
+/// Splits the key table into the stack and the hash set
+fn key_table_parts(key_table: &mut [u8]) -> (&mut [u8], &mut [u8]) {
+    let num_stack_slots = key_table.len() / 24;
+    let (stack, set) = key_table.split_at_mut(num_stack_slots * 8);
+    (stack, &mut set[..num_stack_slots * 16])
+}
+
+fn key_table_slot(key_table: &[u8], slot: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&key_table[slot * 8 .. slot * 8 + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn set_key_table_slot(key_table: &mut [u8], slot: usize, value: u64) {
+    key_table[slot * 8 .. slot * 8 + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn key_table_push(key_table: &mut [u8], key_table_len: &mut usize, value: u64) -> bool {
+    let slot = *key_table_len;
+    if (slot + 1) * 8 > key_table.len() {
+        return false;
+    }
+    set_key_table_slot(key_table, slot, value);
+    *key_table_len += 1;
+    true
+}
+
+/// Returns the slot of the key set that contains a hash, or the empty slot at which it can be
+///  inserted, and whether the hash was found. The set must not be full.
+fn key_set_find(key_set: &[u8], hash: u64) -> (usize, bool) {
+    let num_slots = key_set.len() / 8;
+    let mut slot = (hash % num_slots as u64) as usize;
+    loop {
+        match key_table_slot(key_set, slot) {
+            0 => return (slot, false),
+            h if h == hash => return (slot, true),
+            _ => slot = (slot + 1) % num_slots,
+        }
+    }
+}
+
+/// The ASCII record separator (RS) that precedes each record of a JSON text sequence
+///  ([RFC 7464](https://www.rfc-editor.org/rfc/rfc7464))
+pub const RECORD_SEPARATOR: u8 = 0x1e;
+
+pub(crate) struct ReaderInner<B: AsMut<[u8]>, S: AsMut<[u8]>, E: Error> {
     pub buf: B,
     pub ind_end_buf: usize,
+    /// a bitset with one bit per nesting level, set for objects and cleared for arrays
+    pub nesting_buf: S,
+    pub depth: usize,
+    pub max_nesting_depth: usize,
+    pub current_container: Option<ContainerKind>,
     pub lenient_comma_handling: bool,
+    /// accept JSON5 syntax, see `JsonReader::with_json5()`
+    pub json5: bool,
+    /// treat comments as whitespace
+    pub allow_comments: bool,
+    /// return comments as [JsonReadToken::Comment] (requires `allow_comments`)
+    pub report_comments: bool,
+    pub lossy_surrogate_handling: bool,
+    /// reject unescaped control characters in strings, see `JsonReader::with_control_character_check()`
+    pub reject_control_characters: bool,
+    /// the quote character while reading a string literal, so that recovery after an error
+    ///  inside a string can skip the rest of it
+    pub in_string: Option<u8>,
+    /// a high surrogate from a `\u` escape that was not yet combined with its low surrogate
+    pub pending_high_surrogate: Option<u16>,
     pub state: ReaderState,
     pub parked_next: Option<u8>,
+    /// a token that was read ahead by `peek_kind()`: its payload (if any) is in the buffer, except
+    ///  for a boolean's value which is stored here
+    pub parked_token: Option<(JsonReadTokenKind, bool)>,
+    /// the range of the buffer with comments that were read after a string while looking for a
+    ///  `:`, if comments are returned as tokens: they follow the string's contents, each preceded
+    ///  by its length, and they are returned after the string
+    pub parked_comments: Option<(usize, usize)>,
+    /// the current JSON Pointer if path tracking is enabled
+    pub path_buf: Option<B>,
+    pub path_len: usize,
+    /// the number of innermost nesting levels whose path segments did not fit into the buffer
+    pub path_num_missing: usize,
+    /// the keys of each open object if duplicate key detection is enabled
+    #[cfg(feature = "std")]
+    pub key_sets: Option<Vec<std::collections::HashSet<Vec<u8>>>>,
+    /// an externally provided table for duplicate key detection, see `key_table_parts()`
+    pub key_table: Option<B>,
+    /// the seed for the hashes in the key table
+    pub key_table_seed: u64,
+    /// the number of used slots of the key table's stack
+    pub key_table_len: usize,
+    /// the slot that starts the innermost open object's keys
+    pub key_table_object: usize,
+    /// a ring buffer with the most recently consumed bytes for error reports
+    pub context_buf: Option<B>,
     pub cur_location: Location,
+    /// the number of outermost nesting levels that were opened before the reader was resumed
+    ///  from a `ReaderCheckpoint`: their keys and path segments are unknown, so duplicate key
+    ///  detection and path tracking ignore them
+    pub resumed_depth: usize,
+    /// the line of the current JSON Lines record if the reader is used by a `JsonLinesReader`:
+    ///  tokens inside the record's objects and arrays must start in that line
+    pub record_line: Option<usize>,
+    /// treat the ASCII record separator as the end of a record if the reader is used by a
+    ///  `JsonSeqReader`, see [RECORD_SEPARATOR]
+    pub record_separators: bool,
+    /// whether the last consumed byte was a record separator (only tracked with
+    ///  `record_separators`): a record separator that cuts a literal or an escape short is
+    ///  consumed by the failing token, and recovery needs to know it was there
+    pub consumed_record_separator: bool,
     pd: PhantomData<E>,
 }
-impl <B: AsMut<[u8]>, E: Error> ReaderInner<B, E> {
-    pub fn new(buf: B, lenient_comma_handling: bool) -> Self {
+impl <B: AsMut<[u8]>, S: AsMut<[u8]>, E: Error> ReaderInner<B, S, E> {
+    pub fn new(buf: B, nesting_buf: S, lenient_comma_handling: bool) -> Self {
         Self {
             buf,
             ind_end_buf: 0,
+            nesting_buf,
+            depth: 0,
+            max_nesting_depth: usize::MAX,
+            current_container: None,
             lenient_comma_handling,
+            json5: false,
+            allow_comments: false,
+            report_comments: false,
+            lossy_surrogate_handling: false,
+            reject_control_characters: false,
+            in_string: None,
+            pending_high_surrogate: None,
             state: ReaderState::Initial,
             parked_next: None,
+            parked_token: None,
+            parked_comments: None,
+            path_buf: None,
+            path_len: 0,
+            path_num_missing: 0,
+            #[cfg(feature = "std")]
+            key_sets: None,
+            key_table: None,
+            key_table_seed: 0,
+            key_table_len: 0,
+            key_table_object: 0,
+            context_buf: None,
             cur_location: Location::start(),
+            resumed_depth: 0,
+            record_line: None,
+            record_separators: false,
+            consumed_record_separator: false,
             pd: PhantomData,
         }
     }
@@ -178,7 +768,7 @@ impl <B: AsMut<[u8]>, E: Error> ReaderInner<B, E> {
     }
 
     /// see https://de.wikipedia.org/wiki/UTF-8
-    pub fn append_code_point(&mut self, cp: u16) -> JsonParseResult<(), E> {
+    pub fn append_code_point(&mut self, cp: u32) -> JsonParseResult<(), E> {
         match cp {
             0x0000..=0x007F => {
                 self.append_to_buf(cp as u8)
@@ -187,14 +777,128 @@ impl <B: AsMut<[u8]>, E: Error> ReaderInner<B, E> {
                 self.append_to_buf(0xC0 | ((cp >> 6) as u8 & 0x1F))?;
                 self.append_to_buf(0x80 | ( cp       as u8 & 0x3F))
             }
-            _ => { // 0x00800..0xffff
+            0x0800..=0xFFFF => {
                 self.append_to_buf(0xE0 | ((cp >> 12) as u8 & 0x0F))?;
                 self.append_to_buf(0x80 | ((cp >>  6) as u8 & 0x3F))?;
                 self.append_to_buf(0x80 | ( cp        as u8 & 0x3F))
             }
+            _ => { // 0x10000..0x10ffff
+                self.append_to_buf(0xF0 | ((cp >> 18) as u8 & 0x07))?;
+                self.append_to_buf(0x80 | ((cp >> 12) as u8 & 0x3F))?;
+                self.append_to_buf(0x80 | ((cp >>  6) as u8 & 0x3F))?;
+                self.append_to_buf(0x80 | ( cp        as u8 & 0x3F))
+            }
+        }
+    }
+
+    /// Append a UTF-16 code unit from a `\u` escape. Characters outside the Basic Multilingual
+    ///  Plane are escaped as a surrogate pair, so a high surrogate is held back until the
+    ///  matching low surrogate arrives.
+    pub fn append_utf16_code_unit(&mut self, unit: u16) -> JsonParseResult<(), E> {
+        if let Some(high) = self.pending_high_surrogate.take() {
+            if let 0xDC00..=0xDFFF = unit {
+                let cp = 0x10000 + (((high as u32) - 0xD800) << 10) + ((unit as u32) - 0xDC00);
+                return self.append_code_point(cp);
+            }
+            self.on_unpaired_surrogate()?;
+        }
+
+        match unit {
+            0xD800..=0xDBFF => {
+                self.pending_high_surrogate = Some(unit);
+                Ok(())
+            }
+            0xDC00..=0xDFFF => {
+                self.on_unpaired_surrogate()
+            }
+            _ => {
+                self.append_code_point(unit as u32)
+            }
+        }
+    }
+
+    /// This must be called for every part of a string literal other than a `\u` escape, ensuring
+    ///  that a high surrogate is followed by a low surrogate immediately.
+    pub fn end_utf16_sequence(&mut self) -> JsonParseResult<(), E> {
+        if self.pending_high_surrogate.take().is_some() {
+            return self.on_unpaired_surrogate();
+        }
+        Ok(())
+    }
+
+    fn on_unpaired_surrogate(&mut self) -> JsonParseResult<(), E> {
+        if self.lossy_surrogate_handling {
+            self.append_code_point(char::REPLACEMENT_CHARACTER as u32)
+        }
+        else {
+            self.parse_err(ParseErrorKind::UnpairedSurrogate)
         }
     }
 
+    /// Skips the whitespace at the start of a slice of input, returning the number of bytes skipped
+    pub fn scan_whitespace(&mut self, bytes: &[u8]) -> usize {
+        let len = bytes.iter()
+            .take_while(|&&b| matches!(b, b' ' | b'\t' | b'\n' | b'\r') || (self.json5 && matches!(b, 0x0b | 0x0c)))
+            .count();
+        self.on_consumed(&bytes[..len]);
+        len
+    }
+
+    /// Appends the run of unescaped characters at the start of a slice of input to the buffer,
+    ///  as far as it fits, and returns the number of bytes appended. Everything else (the closing
+    ///  quote, escapes, a full buffer) is left to byte-wise processing, which is also the case
+    ///  after a `\u` escape where a low surrogate may follow.
+    pub fn scan_string_run(&mut self, bytes: &[u8], quote: u8) -> usize {
+        if self.pending_high_surrogate.is_some() {
+            return 0;
+        }
+        let capacity = self.buf.as_mut().len() - self.ind_end_buf;
+        let len = bytes.iter()
+            .take(capacity)
+            .take_while(|&&b| b != quote && b != b'\\' && (b >= 0x20 || !(self.reject_control_characters || self.record_separators)))
+            .count();
+        self.buf.as_mut()[self.ind_end_buf..self.ind_end_buf + len].copy_from_slice(&bytes[..len]);
+        self.ind_end_buf += len;
+        self.on_consumed(&bytes[..len]);
+        len
+    }
+
+    /// Continues a number literal with the number bytes at the start of a slice of input. Returns
+    ///  the number of bytes processed, and whether the literal ends inside the slice. The byte
+    ///  terminating the literal is parked, just like when reading byte by byte.
+    pub fn scan_number_run(&mut self, state: &mut NumberState, bytes: &[u8]) -> JsonParseResult<(usize, bool), E> {
+        for (i, &b) in bytes.iter().enumerate() {
+            self.on_consumed_byte(b);
+            if !NumberState::is_number_byte(b) {
+                self.parked_next = Some(b);
+                return Ok((i + 1, true));
+            }
+            match state.next(b) {
+                Some(s) => *state = s,
+                None => return self.parse_err(ParseErrorKind::InvalidNumberLiteral),
+            }
+            self.append_to_buf(b)?;
+        }
+        Ok((bytes.len(), false))
+    }
+
+    /// Passes the longest prefix of the buffer that is valid UTF-8 to a callback, moving an
+    ///  incomplete UTF-8 sequence at the end (if any) to the start of the buffer.
+    pub fn flush_chunk(&mut self, on_chunk: &mut impl FnMut(&str)) -> JsonParseResult<(), E> {
+        let buf = &mut self.buf.as_mut()[..self.ind_end_buf];
+        let valid_up_to = match core::str::from_utf8(buf) {
+            Ok(s) => s.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(JsonParseError::Utf8(e)),
+        };
+        if valid_up_to > 0 {
+            on_chunk(core::str::from_utf8(&buf[..valid_up_to]).map_err(JsonParseError::Utf8)?);
+        }
+        buf.copy_within(valid_up_to.., 0);
+        self.ind_end_buf -= valid_up_to;
+        Ok(())
+    }
+
     pub fn buf_as_str(&mut self) -> JsonParseResult<&str, E> {
         // the reference is used only immutably, but all callers have a mutable refrence anyway
         //  and calling as_mut() avoids the need for another type bound
@@ -206,21 +910,32 @@ impl <B: AsMut<[u8]>, E: Error> ReaderInner<B, E> {
     pub fn ensure_accept_value(&mut self) -> JsonParseResult<(), E> {
         match self.state {
             ReaderState::Initial |
-            ReaderState::BeforeEntry |
+            ReaderState::BeforeEntry => {
+                self.ensure_not_in_object()
+            }
             ReaderState::AfterKey => {
                 Ok(())
             }
             ReaderState::AfterValue => {
                 if self.lenient_comma_handling {
-                    Ok(())
+                    self.ensure_not_in_object()
                 }
                 else {
-                    self.parse_err("missing comma")
+                    self.parse_err(ParseErrorKind::MissingComma)
                 }
             }
         }
     }
 
+    fn ensure_not_in_object(&mut self) -> JsonParseResult<(), E> {
+        if self.current_container == Some(ContainerKind::Object) {
+            self.parse_err(ParseErrorKind::MemberWithoutKey)
+        }
+        else {
+            Ok(())
+        }
+    }
+
     pub fn ensure_accept_end_nested(&mut self) -> JsonParseResult<(), E> {
         match self.state {
             ReaderState::Initial |
@@ -228,26 +943,305 @@ impl <B: AsMut<[u8]>, E: Error> ReaderInner<B, E> {
                 Ok(())
             }
             ReaderState::BeforeEntry => {
-                self.parse_err("trailing comma")
+                if self.json5 {
+                    Ok(())
+                }
+                else {
+                    self.parse_err(ParseErrorKind::TrailingComma)
+                }
             }
             ReaderState::AfterKey => {
-                self.parse_err("key without a value")
+                self.parse_err(ParseErrorKind::KeyWithoutValue)
             }
         }
     }
 
+    /// Handle the `{` or `[` that starts an object or array
+    pub fn on_start_nested(&mut self, kind: ContainerKind) -> JsonParseResult<(), E> {
+        self.ensure_accept_value()?;
+        self.path_on_value();
+
+        let nesting_buf = self.nesting_buf.as_mut();
+        if self.depth >= nesting_buf.len() * 8 || self.depth >= self.max_nesting_depth {
+            return Err(JsonParseError::MaxNestingDepthExceeded(self.cur_location));
+        }
+        let mask = 1u8 << (self.depth % 8);
+        match kind {
+            ContainerKind::Object => nesting_buf[self.depth / 8] |= mask,
+            ContainerKind::Array => nesting_buf[self.depth / 8] &= !mask,
+        }
+        if kind == ContainerKind::Object {
+            self.keys_on_start_object()?;
+        }
+        self.depth += 1;
+        self.current_container = Some(kind);
+        self.state = ReaderState::Initial;
+        if self.path_num_missing > 0 {
+            self.path_num_missing += 1;
+        }
+        Ok(())
+    }
+
+    /// Handle the `}` or `]` that ends an object or array, checking that it matches the
+    ///  innermost open container
+    pub fn on_end_nested(&mut self, kind: ContainerKind) -> JsonParseResult<(), E> {
+        if self.current_container != Some(kind) {
+            return match kind {
+                ContainerKind::Object => self.parse_err(ParseErrorKind::MismatchedEndObject),
+                ContainerKind::Array => self.parse_err(ParseErrorKind::MismatchedEndArray),
+            };
+        }
+        self.ensure_accept_end_nested()?;
+        self.end_nested(kind);
+        Ok(())
+    }
+
+    fn end_nested(&mut self, kind: ContainerKind) {
+        self.path_on_end_nested();
+        if kind == ContainerKind::Object {
+            self.keys_on_end_object();
+        }
+
+        self.depth -= 1;
+        self.resumed_depth = self.resumed_depth.min(self.depth);
+        self.current_container = self.innermost_container();
+        self.state = ReaderState::AfterValue;
+    }
+
+    /// Determines the kind of the innermost open container from the nesting buffer
+    pub fn innermost_container(&mut self) -> Option<ContainerKind> {
+        if self.depth == 0 {
+            return None;
+        }
+        let ind = self.depth - 1;
+        if self.nesting_buf.as_mut()[ind / 8] & (1u8 << (ind % 8)) != 0 {
+            Some(ContainerKind::Object)
+        }
+        else {
+            Some(ContainerKind::Array)
+        }
+    }
+
     pub fn state_change_for_value(&mut self) -> JsonParseResult<(), E> {
         match self.state {
             ReaderState::Initial |
-            ReaderState::BeforeEntry |
+            ReaderState::BeforeEntry => {
+                self.ensure_not_in_object()?;
+                self.path_on_value();
+                self.state = ReaderState::AfterValue;
+                Ok(())
+            }
             ReaderState::AfterKey => {
                 self.state = ReaderState::AfterValue;
                 Ok(())
             }
             ReaderState::AfterValue => {
-                self.parse_err("missing comma")
+                self.parse_err(ParseErrorKind::MissingComma)
+            }
+        }
+    }
+
+    pub fn on_key(&mut self) -> JsonParseResult<(), E> {
+        if self.current_container != Some(ContainerKind::Object) {
+            return self.parse_err(ParseErrorKind::KeyOutsideOfObject);
+        }
+        match self.state {
+            ReaderState::Initial |
+            ReaderState::BeforeEntry => {
+                self.path_on_key();
+                self.state = ReaderState::AfterKey;
+                Ok(())
+            }
+            ReaderState::AfterKey => {
+                self.parse_err(ParseErrorKind::TwoKeysWithoutValue)
+            }
+            ReaderState::AfterValue => {
+                self.parse_err(ParseErrorKind::MissingComma)
+            }
+        }
+    }
+
+    /// Fails if the key in the buffer was already read in the innermost open object, provided
+    ///  that duplicate key detection is enabled. This must be called after `on_key()`.
+    pub fn check_duplicate_key(&mut self, key_location: Location) -> JsonParseResult<(), E> {
+        if self.depth <= self.resumed_depth {
+            return Ok(());
+        }
+        let key = &self.buf.as_mut()[..self.ind_end_buf];
+        #[allow(unused_mut)]
+        let mut is_duplicate = false;
+
+        #[cfg(feature = "std")]
+        if let Some(keys) = self.key_sets.as_mut().and_then(|key_sets| key_sets.last_mut()) {
+            is_duplicate = !keys.insert(key.to_vec());
+        }
+
+        if let Some(key_table) = self.key_table.as_mut() {
+            let (stack, set) = key_table_parts(key_table.as_mut());
+            let hash = key_hash(self.key_table_seed, self.key_table_object, key);
+            let (set_slot, is_found) = key_set_find(set, hash);
+            if is_found {
+                is_duplicate = true;
+            }
+            else if key_table_push(stack, &mut self.key_table_len, hash) {
+                set_key_table_slot(set, set_slot, hash);
+            }
+            else {
+                return self.buf_overflow();
+            }
+        }
+
+        if is_duplicate {
+            return self.parse_err_at(ParseErrorKind::DuplicateKey, key_location);
+        }
+        Ok(())
+    }
+
+    fn keys_on_start_object(&mut self) -> JsonParseResult<(), E> {
+        #[cfg(feature = "std")]
+        if let Some(key_sets) = self.key_sets.as_mut() {
+            key_sets.push(Default::default());
+        }
+
+        if let Some(key_table) = self.key_table.as_mut() {
+            let (stack, _) = key_table_parts(key_table.as_mut());
+            let object_slot = self.key_table_len;
+            if !key_table_push(stack, &mut self.key_table_len, self.key_table_object as u64) {
+                return self.buf_overflow();
+            }
+            self.key_table_object = object_slot;
+        }
+        Ok(())
+    }
+
+    fn keys_on_end_object(&mut self) {
+        if self.depth <= self.resumed_depth {
+            return;
+        }
+        #[cfg(feature = "std")]
+        if let Some(key_sets) = self.key_sets.as_mut() {
+            key_sets.pop();
+        }
+
+        if let Some(key_table) = self.key_table.as_mut() {
+            let (stack, set) = key_table_parts(key_table.as_mut());
+            for slot in (self.key_table_object + 1 .. self.key_table_len).rev() {
+                let (set_slot, _) = key_set_find(set, key_table_slot(stack, slot));
+                set_key_table_slot(set, set_slot, 0);
+            }
+            self.key_table_len = self.key_table_object;
+            self.key_table_object = key_table_slot(stack, self.key_table_object) as usize;
+        }
+    }
+
+    /// Fails if a token inside a JSON Lines record's objects and arrays starts in another line than
+    ///  the record, see [ReaderInner::record_line]. The token's first byte, which was just read, is
+    ///  parked so that the next record can start with it.
+    pub fn check_record_line(&mut self, b: u8) -> JsonParseResult<(), E> {
+        if self.depth > 0 && self.record_line.is_some_and(|line| line != self.cur_location.line) {
+            self.parked_next = Some(b);
+            let location = self.token_start_location();
+            return self.parse_err_at(ParseErrorKind::MultiLineRecord, location);
+        }
+        Ok(())
+    }
+
+    /// Fails for a record separator that was just read inside a JSON text sequence record, see
+    ///  [ReaderInner::record_separators]. The record separator is parked so that the next record
+    ///  can start with it.
+    pub fn truncated_record<T>(&mut self) -> JsonParseResult<T, E> {
+        self.parked_next = Some(RECORD_SEPARATOR);
+        let location = self.token_start_location();
+        self.parse_err_at(ParseErrorKind::TruncatedRecord, location)
+    }
+
+    /// RFC 7464 requires a top-level number in a JSON text sequence to be followed by whitespace:
+    ///  Otherwise the record may have been cut short in the middle of the number, which can not
+    ///  be detected from the number itself. The byte after the number (if any) is parked.
+    pub fn check_top_level_number_end(&mut self, location: Location) -> JsonParseResult<(), E> {
+        if self.record_separators && self.depth == 0 && !matches!(self.parked_next, Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            return self.parse_err_at(ParseErrorKind::TruncatedRecord, location);
+        }
+        Ok(())
+    }
+
+    /// Returns the location of the current token's first byte, which was just read and is not a
+    ///  newline
+    pub fn token_start_location(&self) -> Location {
+        Location {
+            offset: self.cur_location.offset - 1,
+            line: self.cur_location.line,
+            column: self.cur_location.column - 1,
+        }
+    }
+
+    /// Returns `true` if the next token must be an object's key (or its end)
+    pub fn expects_key(&self) -> bool {
+        self.current_container == Some(ContainerKind::Object)
+            && matches!(self.state, ReaderState::Initial | ReaderState::BeforeEntry)
+    }
+
+    /// Converts a JSON5 number literal in the buffer to JSON syntax, so that applications can
+    ///  parse it like any other number: A leading `+` is dropped, hexadecimal numbers are converted
+    ///  to decimal, and a leading or trailing decimal point gets a `0` added. `Infinity` and `NaN`
+    ///  are left unchanged since Rust's float parsing understands them.
+    pub fn finish_json5_number(&mut self) -> JsonParseResult<(), E> {
+        if self.buf.as_mut()[..self.ind_end_buf].first() == Some(&b'+') {
+            self.buf.as_mut().copy_within(1..self.ind_end_buf, 0);
+            self.ind_end_buf -= 1;
+        }
+        let sign_len = usize::from(self.buf.as_mut()[..self.ind_end_buf].first() == Some(&b'-'));
+
+        let unsigned = &self.buf.as_mut()[sign_len..self.ind_end_buf];
+        if unsigned == b"Infinity" || unsigned == b"NaN" {
+            return Ok(());
+        }
+        if let Some(hex) = unsigned.strip_prefix(b"0x").or_else(|| unsigned.strip_prefix(b"0X")) {
+            let value = core::str::from_utf8(hex).ok()
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u128::from_str_radix(hex, 16).ok());
+            let Some(value) = value else {
+                return self.parse_err(ParseErrorKind::InvalidNumberLiteral);
+            };
+            self.ind_end_buf = sign_len;
+            let mut digits = [0u8; 39];
+            for &b in format_decimal(value, &mut digits) {
+                self.append_to_buf(b)?;
+            }
+            return Ok(());
+        }
+
+        if let [b'.', b'0'..=b'9', ..] = unsigned {
+            self.insert_into_buf(sign_len, b'0')?;
+        }
+        else if let Some(ind_point) = unsigned.iter().position(|&b| b == b'.') {
+            let ind_point = sign_len + ind_point;
+            if !self.buf.as_mut()[ind_point + 1..self.ind_end_buf].first().is_some_and(u8::is_ascii_digit) {
+                self.insert_into_buf(ind_point + 1, b'0')?;
+            }
+        }
+
+        let mut state = None;
+        for &b in &self.buf.as_mut()[..self.ind_end_buf] {
+            state = match state {
+                None => NumberState::start(b),
+                Some(s) => NumberState::next(s, b),
+            };
+            if state.is_none() {
+                return self.parse_err(ParseErrorKind::InvalidNumberLiteral);
             }
         }
+        if !state.is_some_and(NumberState::is_complete) {
+            return self.parse_err(ParseErrorKind::IncompleteNumberLiteral);
+        }
+        Ok(())
+    }
+
+    fn insert_into_buf(&mut self, index: usize, ch: u8) -> JsonParseResult<(), E> {
+        self.append_to_buf(ch)?;
+        let buf = &mut self.buf.as_mut()[index..self.ind_end_buf];
+        buf.rotate_right(1);
+        Ok(())
     }
 
     pub fn on_comma(&mut self) -> JsonParseResult<(), E> {
@@ -259,13 +1253,231 @@ impl <B: AsMut<[u8]>, E: Error> ReaderInner<B, E> {
             ReaderState::Initial |
             ReaderState::BeforeEntry |
             ReaderState::AfterKey => {
-                self.parse_err("unexpected comma")
+                self.parse_err(ParseErrorKind::UnexpectedComma)
             }
         }
     }
 
-    pub fn parse_err<T>(&self, msg: &'static str) -> JsonParseResult<T, E> {
-        Err(JsonParseError::Parse(msg, self.cur_location))
+    /// Adjusts the state after a parse error so that reading can continue at the next structural
+    ///  boundary: An error at a `}` or `]` that closes the current container (e.g. a trailing
+    ///  comma) leaves the container open, so it is closed here.
+    pub fn recover_from(&mut self, error: &JsonParseError<E>) {
+        self.parked_token = None;
+        self.parked_comments = None;
+        if let JsonParseError::Parse(ParseErrorKind::TrailingComma | ParseErrorKind::KeyWithoutValue, _, _) = error {
+            if let Some(kind) = self.current_container {
+                self.end_nested(kind);
+            }
+        }
+    }
+
+    /// Discards all open objects and arrays, e.g. to continue with the next line of JSON Lines
+    ///  after an error
+    pub fn reset_to_top_level(&mut self) {
+        self.depth = 0;
+        self.current_container = None;
+        self.state = ReaderState::Initial;
+        self.parked_token = None;
+        self.parked_comments = None;
+        self.in_string = None;
+        self.path_len = 0;
+        self.path_num_missing = 0;
+        #[cfg(feature = "std")]
+        if let Some(key_sets) = self.key_sets.as_mut() {
+            key_sets.clear();
+        }
+        if let Some(key_table) = self.key_table.as_mut() {
+            key_table.as_mut().fill(0);
+        }
+        self.key_table_len = 0;
+        self.key_table_object = 0;
+        self.resumed_depth = 0;
+    }
+
+    /// Updates the location for consumed input, recording it in the context buffer if there is one
+    pub fn on_consumed(&mut self, bytes: &[u8]) {
+        if let Some(context_buf) = self.context_buf.as_mut() {
+            record_context(context_buf.as_mut(), self.cur_location.offset, bytes);
+        }
+        self.cur_location.after_bytes(bytes);
+        if self.record_separators && !bytes.is_empty() {
+            // slices are scanned only up to a record separator
+            self.consumed_record_separator = false;
+        }
+    }
+
+    /// Same as `on_consumed()` for a single byte
+    pub fn on_consumed_byte(&mut self, byte: u8) {
+        if let Some(context_buf) = self.context_buf.as_mut() {
+            record_context(context_buf.as_mut(), self.cur_location.offset, &[byte]);
+        }
+        self.cur_location.after_byte(byte);
+        if self.record_separators {
+            self.consumed_record_separator = byte == RECORD_SEPARATOR;
+        }
+    }
+
+    pub fn error_report<'a>(&'a mut self, error: &'a JsonParseError<E>) -> JsonErrorReport<'a, E> {
+        let context = match self.context_buf.as_mut() {
+            Some(context_buf) => &*context_buf.as_mut(),
+            None => &[],
+        };
+        let is_truncated = self.path_num_missing > 0;
+        let path = self.path_buf.as_mut()
+            .filter(|_| self.resumed_depth == 0)
+            .map(|path_buf| JsonPath::new(core::str::from_utf8(&path_buf.as_mut()[..self.path_len]).unwrap_or_default(), is_truncated));
+        JsonErrorReport::new(error, context, self.cur_location.offset, path)
+    }
+
+    pub fn current_path(&mut self) -> Option<JsonPath<'_>> {
+        if self.resumed_depth > 0 {
+            return None;
+        }
+        let is_truncated = self.path_num_missing > 0;
+        let path = &self.path_buf.as_mut()?.as_mut()[..self.path_len];
+        // the path consists of complete segments of valid UTF-8 only
+        Some(JsonPath::new(core::str::from_utf8(path).unwrap_or_default(), is_truncated))
+    }
+
+    /// Updates the path for a value, which is a new array element if the current container is an
+    ///  array. This must be called before the state changes.
+    fn path_on_value(&mut self) {
+        if self.current_container != Some(ContainerKind::Array) || self.path_num_missing > 0 || self.resumed_depth > 0 {
+            // if a segment is missing, the array's index may be lost, so it stays missing
+            return;
+        }
+        let Some(path_buf) = self.path_buf.as_mut() else {
+            return;
+        };
+        let path = path_buf.as_mut();
+
+        let index = if self.state == ReaderState::Initial {
+            0
+        }
+        else {
+            let ind_slash = last_path_segment_start(&path[..self.path_len]);
+            let segment = core::str::from_utf8(&path[ind_slash + 1..self.path_len]).unwrap_or_default();
+            self.path_len = ind_slash;
+            segment.parse::<usize>().map(|i| i + 1).unwrap_or_default()
+        };
+
+        let mut digits = [0u8; 39];
+        if !append_path_segment(path, &mut self.path_len, format_decimal(index as u128, &mut digits)) {
+            self.path_num_missing = 1;
+        }
+    }
+
+    /// Updates the path for a key, which is in the buffer. This must be called before the state
+    ///  changes.
+    fn path_on_key(&mut self) {
+        if self.path_num_missing > 1 || self.resumed_depth > 0 {
+            return;
+        }
+        let Some(path_buf) = self.path_buf.as_mut() else {
+            return;
+        };
+        let path = path_buf.as_mut();
+
+        if self.path_num_missing == 0 && self.state != ReaderState::Initial {
+            self.path_len = last_path_segment_start(&path[..self.path_len]);
+        }
+        let key = &self.buf.as_mut()[..self.ind_end_buf];
+        self.path_num_missing = if core::str::from_utf8(key).is_ok() && append_path_segment(path, &mut self.path_len, key) {
+            0
+        }
+        else {
+            1
+        };
+    }
+
+    fn path_on_end_nested(&mut self) {
+        if self.resumed_depth > 0 {
+            return;
+        }
+        let Some(path_buf) = self.path_buf.as_mut() else {
+            return;
+        };
+        if self.path_num_missing > 0 {
+            self.path_num_missing -= 1;
+        }
+        else if self.state != ReaderState::Initial {
+            self.path_len = last_path_segment_start(&path_buf.as_mut()[..self.path_len]);
+        }
+    }
+
+    /// Returns a token that was read ahead by `peek_kind()`, see [ReaderInner::parked_token]
+    pub fn unpark_token(&mut self, kind: JsonReadTokenKind, bool_value: bool) -> JsonParseResult<JsonReadToken<'_>, E> {
+        match kind {
+            JsonReadTokenKind::StartObject => Ok(JsonReadToken::StartObject),
+            JsonReadTokenKind::EndObject => Ok(JsonReadToken::EndObject),
+            JsonReadTokenKind::StartArray => Ok(JsonReadToken::StartArray),
+            JsonReadTokenKind::EndArray => Ok(JsonReadToken::EndArray),
+            JsonReadTokenKind::Key => self.buf_as_str().map(JsonReadToken::Key),
+            JsonReadTokenKind::StringLiteral => self.buf_as_str().map(JsonReadToken::StringLiteral),
+            JsonReadTokenKind::NumberLiteral => self.buf_as_str().map(|s| JsonReadToken::NumberLiteral(JsonNumber(s))),
+            JsonReadTokenKind::BooleanLiteral => Ok(JsonReadToken::BooleanLiteral(bool_value)),
+            JsonReadTokenKind::NullLiteral => Ok(JsonReadToken::NullLiteral),
+            JsonReadTokenKind::Comment => self.buf_as_str().map(JsonReadToken::Comment),
+            JsonReadTokenKind::EndOfStream => Ok(JsonReadToken::EndOfStream),
+        }
+    }
+
+    /// Appends a placeholder for a parked comment's length to the buffer, returning its position,
+    ///  see [ReaderInner::parked_comments]
+    pub fn start_parked_comment(&mut self) -> JsonParseResult<usize, E> {
+        let len_pos = self.ind_end_buf;
+        for _ in 0..size_of::<usize>() {
+            self.append_to_buf(0)?;
+        }
+        Ok(len_pos)
+    }
+
+    /// Stores the length of a parked comment that was appended to the buffer after its
+    ///  placeholder
+    pub fn end_parked_comment(&mut self, len_pos: usize) {
+        let start = len_pos + size_of::<usize>();
+        if self.buf.as_mut()[start..self.ind_end_buf].ends_with(b"\r") {
+            // a line comment ending with "\r\n"
+            self.ind_end_buf -= 1;
+        }
+        let len = self.ind_end_buf - start;
+        self.buf.as_mut()[len_pos..start].copy_from_slice(&len.to_le_bytes());
+    }
+
+    /// Parks the comments that were appended to the buffer after a string's contents, leaving
+    ///  only the string in the buffer
+    pub fn park_comments(&mut self, string_end: usize) {
+        if self.ind_end_buf > string_end {
+            self.parked_comments = Some((string_end, self.ind_end_buf));
+        }
+        self.ind_end_buf = string_end;
+    }
+
+    /// Returns the next parked comment, see [ReaderInner::parked_comments]
+    pub fn unpark_comment(&mut self, (start, end): (usize, usize)) -> JsonParseResult<JsonReadToken<'_>, E> {
+        let text_start = start + size_of::<usize>();
+        let buf = self.buf.as_mut();
+        let mut len_bytes = [0u8; size_of::<usize>()];
+        len_bytes.copy_from_slice(&buf[start..text_start]);
+        let len = usize::from_le_bytes(len_bytes);
+        buf.copy_within(text_start..text_start + len, 0);
+        self.ind_end_buf = len;
+        self.parked_comments = Some((text_start + len, end))
+            .filter(|(next, end)| next < end);
+        self.buf_as_str().map(JsonReadToken::Comment)
+    }
+
+    pub fn parse_err<T>(&mut self, kind: ParseErrorKind) -> JsonParseResult<T, E> {
+        self.parse_err_at(kind, self.cur_location)
+    }
+
+    /// Creates a parse error at a given location, adding the current path if it is tracked
+    pub fn parse_err_at<T>(&mut self, kind: ParseErrorKind, location: Location) -> JsonParseResult<T, E> {
+        #[cfg(feature = "std")]
+        let path = self.current_path().map(OwnedJsonPath::from);
+        #[cfg(not(feature = "std"))]
+        let path = None;
+        Err(JsonParseError::Parse(kind, location, path))
     }
 
     pub fn buf_overflow<T>(&self) -> JsonParseResult<T, E> {
@@ -0,0 +1,474 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
+use serde::ser::{Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant};
+
+/// A [JsonSerializeError] is the error type of `write_serialize()` for objects and arrays: Either
+///  writing the JSON failed, or the serialized value can not be represented as JSON, e.g. a map
+///  with keys that are not strings, or a `Serialize` implementation reported an error.
+#[derive(Debug)]
+pub enum JsonSerializeError<E: Error> {
+    Io(E),
+    Data(String),
+}
+impl <E: Error> Display for JsonSerializeError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JsonSerializeError::Io(err) => write!(f, "I/O error: {}", err),
+            JsonSerializeError::Data(msg) => write!(f, "data error: {}", msg),
+        }
+    }
+}
+impl <E: Error> Error for JsonSerializeError<E> {
+}
+impl <E: Error> From<E> for JsonSerializeError<E> {
+    fn from(value: E) -> Self {
+        JsonSerializeError::Io(value)
+    }
+}
+impl <E: Error> serde::ser::Error for JsonSerializeError<E> {
+    fn custom<T: Display>(msg: T) -> Self {
+        JsonSerializeError::Data(msg.to_string())
+    }
+}
+
+/// The output of serialization: the blocking `JsonWriter`, or a buffer for the non-blocking API
+///  (serde's API is synchronous), with the formatting functions of `JsonWriter`
+pub(crate) trait JsonSink {
+    type Error: Error;
+
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+    fn write_escaped_string(&mut self, s: &str) -> Result<(), Self::Error>;
+    fn write_f64(&mut self, value: f64) -> Result<(), Self::Error>;
+    fn write_f32(&mut self, value: f32) -> Result<(), Self::Error>;
+    fn write_raw_num(&mut self, value: impl Display) -> Result<(), Self::Error>;
+    fn write_format_after_key(&mut self) -> Result<(), Self::Error>;
+    fn write_format_after_start_nested(&mut self) -> Result<(), Self::Error>;
+    fn write_format_after_element(&mut self) -> Result<(), Self::Error>;
+    fn write_format_before_end_nested(&mut self, is_empty: bool) -> Result<(), Self::Error>;
+    fn write_format_indent(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Serializes a value to a [JsonSink] in the same representation as `serde_json`: structs and
+///  maps as objects, sequences and tuples as arrays, unit variants as strings and other enum
+///  variants as an object with the variant's name as its single key.
+pub(crate) fn serialize_to_sink<S: JsonSink, T: Serialize + ?Sized>(sink: &mut S, value: &T) -> Result<(), JsonSerializeError<S::Error>> {
+    value.serialize(JsonValueSerializer { sink })
+}
+
+struct JsonValueSerializer<'s, S: JsonSink> {
+    sink: &'s mut S,
+}
+impl<'s, S: JsonSink> JsonValueSerializer<'s, S> {
+    /// Writes the start of an object with the variant's name as its single key
+    fn start_variant(&mut self, variant: &str) -> Result<(), S::Error> {
+        self.sink.write_bytes(b"{")?;
+        self.sink.write_format_after_start_nested()?;
+        self.sink.write_format_indent()?;
+        self.sink.write_escaped_string(variant)?;
+        self.sink.write_bytes(b":")?;
+        self.sink.write_format_after_key()
+    }
+}
+
+impl<'s, S: JsonSink> serde::Serializer for JsonValueSerializer<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+    type SerializeSeq = Compound<'s, S>;
+    type SerializeTuple = Compound<'s, S>;
+    type SerializeTupleStruct = Compound<'s, S>;
+    type SerializeTupleVariant = Compound<'s, S>;
+    type SerializeMap = Compound<'s, S>;
+    type SerializeStruct = Compound<'s, S>;
+    type SerializeStructVariant = Compound<'s, S>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
+        self.sink.write_bytes(if v { b"true" } else { b"false" })?;
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Self::Error> {
+        Ok(self.sink.write_raw_num(v)?)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Self::Error> {
+        Ok(self.sink.write_f32(v)?)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Self::Error> {
+        Ok(self.sink.write_f64(v)?)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Self::Error> {
+        Ok(self.sink.write_escaped_string(v.encode_utf8(&mut [0; 4]))?)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
+        Ok(self.sink.write_escaped_string(v)?)
+    }
+
+    /// bytes are written as an array of numbers
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        let mut seq = self.serialize_seq(Some(v.len()))?;
+        for b in v {
+            seq.write_element(b)?;
+        }
+        SerializeSeq::end(seq)
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(self.sink.write_bytes(b"null")?)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<(), Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(mut self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.start_variant(variant)?;
+        value.serialize(JsonValueSerializer { sink: &mut *self.sink })?;
+        self.sink.write_format_before_end_nested(false)?;
+        Ok(self.sink.write_bytes(b"}")?)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'s, S>, Self::Error> {
+        Ok(Compound::start(self.sink, b"[", false)?)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'s, S>, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Compound<'s, S>, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(mut self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Compound<'s, S>, Self::Error> {
+        self.start_variant(variant)?;
+        Ok(Compound::start(self.sink, b"[", true)?)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'s, S>, Self::Error> {
+        Ok(Compound::start(self.sink, b"{", false)?)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'s, S>, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(mut self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Compound<'s, S>, Self::Error> {
+        self.start_variant(variant)?;
+        Ok(Compound::start(self.sink, b"{", true)?)
+    }
+}
+
+/// An object or array that is being serialized
+struct Compound<'s, S: JsonSink> {
+    sink: &'s mut S,
+    is_initial: bool,
+    /// the object or array is wrapped in an object with the enum variant's name as its key
+    is_variant: bool,
+}
+impl<'s, S: JsonSink> Compound<'s, S> {
+    fn start(sink: &'s mut S, bracket: &[u8], is_variant: bool) -> Result<Self, S::Error> {
+        sink.write_bytes(bracket)?;
+        sink.write_format_after_start_nested()?;
+        Ok(Compound {
+            sink,
+            is_initial: true,
+            is_variant,
+        })
+    }
+
+    fn write_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonSerializeError<S::Error>> {
+        if !self.is_initial {
+            self.sink.write_bytes(b",")?;
+            self.sink.write_format_after_element()?;
+        }
+        self.is_initial = false;
+        self.sink.write_format_indent()?;
+        value.serialize(JsonValueSerializer { sink: &mut *self.sink })
+    }
+
+    fn write_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), JsonSerializeError<S::Error>> {
+        let key = key.serialize(MapKeySerializer { pd: PhantomData })?;
+        if !self.is_initial {
+            self.sink.write_bytes(b",")?;
+            self.sink.write_format_after_element()?;
+        }
+        self.is_initial = false;
+        self.sink.write_format_indent()?;
+        self.sink.write_escaped_string(&key)?;
+        self.sink.write_bytes(b":")?;
+        Ok(self.sink.write_format_after_key()?)
+    }
+
+    fn write_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonSerializeError<S::Error>> {
+        value.serialize(JsonValueSerializer { sink: &mut *self.sink })
+    }
+
+    fn end(self, bracket: &[u8]) -> Result<(), JsonSerializeError<S::Error>> {
+        self.sink.write_format_before_end_nested(self.is_initial)?;
+        self.sink.write_bytes(bracket)?;
+        if self.is_variant {
+            self.sink.write_format_before_end_nested(false)?;
+            self.sink.write_bytes(b"}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'s, S: JsonSink> SerializeSeq for Compound<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.write_element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self, b"]")
+    }
+}
+
+impl<'s, S: JsonSink> SerializeTuple for Compound<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.write_element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self, b"]")
+    }
+}
+
+impl<'s, S: JsonSink> SerializeTupleStruct for Compound<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.write_element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self, b"]")
+    }
+}
+
+impl<'s, S: JsonSink> SerializeTupleVariant for Compound<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.write_element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self, b"]")
+    }
+}
+
+impl<'s, S: JsonSink> SerializeMap for Compound<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.write_key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.write_value(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self, b"}")
+    }
+}
+
+impl<'s, S: JsonSink> SerializeStruct for Compound<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.write_key(key)?;
+        self.write_value(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self, b"}")
+    }
+}
+
+impl<'s, S: JsonSink> SerializeStructVariant for Compound<'s, S> {
+    type Ok = ();
+    type Error = JsonSerializeError<S::Error>;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.write_key(key)?;
+        self.write_value(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self, b"}")
+    }
+}
+
+/// Converts map keys to strings: JSON object keys must be strings, but strings, chars, bools,
+///  integers and unit variants are accepted and converted like `serde_json` does
+struct MapKeySerializer<E: Error> {
+    pd: PhantomData<E>,
+}
+impl<E: Error> MapKeySerializer<E> {
+    fn key_must_be_a_string() -> JsonSerializeError<E> {
+        JsonSerializeError::Data("key must be a string".to_string())
+    }
+}
+
+macro_rules! serialize_key_to_string {
+    ($($f:ident: $t:ty),*) => {
+        $(
+        fn $f(self, v: $t) -> Result<String, Self::Error> {
+            Ok(v.to_string())
+        }
+        )*
+    };
+}
+
+macro_rules! serialize_key_unsupported {
+    ($($f:ident: $t:ty),*) => {
+        $(
+        fn $f(self, _v: $t) -> Result<String, Self::Error> {
+            Err(Self::key_must_be_a_string())
+        }
+        )*
+    };
+}
+
+impl<E: Error> serde::Serializer for MapKeySerializer<E> {
+    type Ok = String;
+    type Error = JsonSerializeError<E>;
+    type SerializeSeq = Impossible<String, Self::Error>;
+    type SerializeTuple = Impossible<String, Self::Error>;
+    type SerializeTupleStruct = Impossible<String, Self::Error>;
+    type SerializeTupleVariant = Impossible<String, Self::Error>;
+    type SerializeMap = Impossible<String, Self::Error>;
+    type SerializeStruct = Impossible<String, Self::Error>;
+    type SerializeStructVariant = Impossible<String, Self::Error>;
+
+    serialize_key_to_string!(
+        serialize_bool: bool, serialize_char: char, serialize_str: &str,
+        serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64, serialize_i128: i128,
+        serialize_u8: u8, serialize_u16: u16, serialize_u32: u32, serialize_u64: u64, serialize_u128: u128
+    );
+    serialize_key_unsupported!(serialize_f32: f32, serialize_f64: f64, serialize_bytes: &[u8]);
+
+    fn serialize_none(self) -> Result<String, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<String, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<String, Self::Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<String, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<String, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Self::key_must_be_a_string())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::shared::{JsonReadTokenKind, ParseErrorKind, VALUE_START_KINDS};
+
+/// The maximum number of errors that a `JsonValidator` collects by default before it stops
+pub const DEFAULT_MAX_VALIDATION_ERRORS: usize = 100;
+
+/// [JsonValidationStats] describes a stream that a `JsonValidator` found to be valid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonValidationStats {
+    /// the length of the stream in bytes
+    pub bytes: usize,
+    /// the maximum nesting depth of objects and arrays, `0` for a stream of scalar values
+    pub max_depth: usize,
+    /// the number of top-level values, i.e. the number of records for JSON Lines
+    pub values: usize,
+    pub objects: usize,
+    pub arrays: usize,
+    pub keys: usize,
+    pub strings: usize,
+    pub numbers: usize,
+    pub booleans: usize,
+    pub nulls: usize,
+}
+
+/// The part of a validator that is independent of the reader: statistics, and the checks for
+///  the number and placement of top-level values that `JsonReader` leaves to application code.
+pub(crate) struct ValidatorInner {
+    pub json_lines: bool,
+    pub stats: JsonValidationStats,
+    /// the line in which the most recent top-level value started
+    record_line: usize,
+}
+
+impl ValidatorInner {
+    pub fn new(json_lines: bool) -> Self {
+        Self {
+            json_lines,
+            stats: JsonValidationStats::default(),
+            record_line: 0,
+        }
+    }
+
+    /// Checks that a token continues the current JSON Lines record, based on the reader's depth
+    ///  and the line in which the token starts
+    pub fn check_line(&self, depth: usize, line: usize) -> Result<(), ParseErrorKind> {
+        if depth > 0 && self.json_lines && line != self.record_line {
+            return Err(ParseErrorKind::MultiLineRecord);
+        }
+        Ok(())
+    }
+
+    /// Checks that a token is not a second top-level value (in the same line for JSON Lines)
+    pub fn check_top_level_value(&self, kind: JsonReadTokenKind, depth: usize, line: usize) -> Result<(), ParseErrorKind> {
+        if depth == 0 && self.stats.values > 0 && VALUE_START_KINDS.contains(&kind) && (!self.json_lines || line == self.record_line) {
+            return Err(ParseErrorKind::MultipleTopLevelValues);
+        }
+        Ok(())
+    }
+
+    /// Checks that the stream may end at the reader's current depth
+    pub fn check_end_of_stream(&self, depth: usize) -> Result<(), ParseErrorKind> {
+        if depth > 0 || (self.stats.values == 0 && !self.json_lines) {
+            return Err(ParseErrorKind::UnexpectedEndOfInput);
+        }
+        Ok(())
+    }
+
+    /// Updates the statistics for a token that was read successfully, starting at a given depth
+    ///  and line
+    pub fn on_token(&mut self, kind: JsonReadTokenKind, depth: usize, line: usize) {
+        if depth == 0 && VALUE_START_KINDS.contains(&kind) {
+            self.stats.values += 1;
+            self.record_line = line;
+        }
+        match kind {
+            JsonReadTokenKind::StartObject => {
+                self.stats.objects += 1;
+                self.stats.max_depth = self.stats.max_depth.max(depth + 1);
+            }
+            JsonReadTokenKind::StartArray => {
+                self.stats.arrays += 1;
+                self.stats.max_depth = self.stats.max_depth.max(depth + 1);
+            }
+            JsonReadTokenKind::Key => self.stats.keys += 1,
+            JsonReadTokenKind::StringLiteral => self.stats.strings += 1,
+            JsonReadTokenKind::NumberLiteral => self.stats.numbers += 1,
+            JsonReadTokenKind::BooleanLiteral => self.stats.booleans += 1,
+            JsonReadTokenKind::NullLiteral => self.stats.nulls += 1,
+            JsonReadTokenKind::EndObject |
+            JsonReadTokenKind::EndArray |
+            JsonReadTokenKind::Comment |
+            JsonReadTokenKind::EndOfStream => {}
+        }
+    }
+}